@@ -0,0 +1,280 @@
+use crate::error::AppError;
+use crate::types::*;
+use redis::aio::MultiplexedConnection;
+
+fn text(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::SimpleString(s) => Some(s.clone()),
+        redis::Value::BulkString(b) => Some(String::from_utf8_lossy(b).to_string()),
+        redis::Value::Okay => Some("OK".to_string()),
+        redis::Value::Int(n) => Some(n.to_string()),
+        redis::Value::Double(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn texts(value: Option<&redis::Value>) -> Vec<String> {
+    match value {
+        Some(redis::Value::Array(items)) | Some(redis::Value::Set(items)) => items.iter().filter_map(text).collect(),
+        // Redis 7 reports keys and channels as one space-separated string
+        Some(other) => text(other).map(|s| s.split_whitespace().map(String::from).collect()).unwrap_or_default(),
+        None => Vec::new(),
+    }
+}
+
+fn number(value: Option<&redis::Value>) -> Option<f64> {
+    match value? {
+        redis::Value::Int(n) => Some(*n as f64),
+        redis::Value::Double(n) => Some(*n),
+        other => text(other)?.parse().ok(),
+    }
+}
+
+/// Field/value pairs of a RESP2 flat array or a RESP3 map.
+fn fields(value: &redis::Value) -> Vec<(String, &redis::Value)> {
+    match value {
+        redis::Value::Map(entries) => entries.iter().filter_map(|(k, v)| Some((text(k)?, v))).collect(),
+        redis::Value::Array(items) => items.chunks(2)
+            .filter_map(|pair| Some((text(pair.first()?)?, pair.get(1)?)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn field<'a>(fields: &'a [(String, &'a redis::Value)], name: &str) -> Option<&'a redis::Value> {
+    fields.iter().find(|(k, _)| k == name).map(|(_, v)| *v)
+}
+
+fn split_patterns(patterns: Vec<String>, prefix: char) -> Vec<String> {
+    patterns.into_iter().map(|p| p.strip_prefix(prefix).map(String::from).unwrap_or(p)).collect()
+}
+
+/// Parses one line of `ACL LIST`, e.g. `user app on #5e88... ~app:* &* -@all +get`.
+pub fn parse_acl_rule_line(line: &str) -> Option<AclUser> {
+    let mut tokens = line.split_whitespace();
+    if tokens.next()? != "user" {
+        return None;
+    }
+    let mut user = AclUser {
+        name: tokens.next()?.to_string(),
+        enabled: false,
+        flags: Vec::new(),
+        password_hashes: Vec::new(),
+        commands: String::new(),
+        key_patterns: Vec::new(),
+        channel_patterns: Vec::new(),
+        selectors: Vec::new(),
+    };
+    let mut commands = Vec::new();
+    let mut selector: Option<Vec<&str>> = None;
+
+    for token in tokens {
+        if let Some(rules) = selector.as_mut() {
+            match token.strip_suffix(')') {
+                Some(last) => {
+                    rules.push(last);
+                    user.selectors.push(parse_selector(rules));
+                    selector = None;
+                }
+                None => rules.push(token),
+            }
+            continue;
+        }
+        match token {
+            "on" => {
+                user.enabled = true;
+                user.flags.push(token.to_string());
+            }
+            "off" => user.flags.push(token.to_string()),
+            "nopass" | "sanitize-payload" | "skip-sanitize-payload" => user.flags.push(token.to_string()),
+            "allkeys" => user.key_patterns.push("*".to_string()),
+            "allchannels" => user.channel_patterns.push("*".to_string()),
+            "resetchannels" | "resetkeys" | "resetpass" | "reset" => {}
+            _ if token.starts_with('#') => user.password_hashes.push(token[1..].to_string()),
+            _ if token.starts_with('~') || token.starts_with('%') => user.key_patterns.push(token.to_string()),
+            _ if token.starts_with('&') => user.channel_patterns.push(token[1..].to_string()),
+            _ if token.starts_with('(') => {
+                let first = &token[1..];
+                match first.strip_suffix(')') {
+                    Some(only) => user.selectors.push(parse_selector(&[only])),
+                    None => selector = Some(vec![first]),
+                }
+            }
+            _ => commands.push(token),
+        }
+    }
+    user.key_patterns = split_patterns(user.key_patterns, '~');
+    user.commands = commands.join(" ");
+    Some(user)
+}
+
+fn parse_selector(rules: &[&str]) -> AclSelector {
+    let mut selector = AclSelector { commands: String::new(), key_patterns: Vec::new(), channel_patterns: Vec::new() };
+    let mut commands = Vec::new();
+    for rule in rules.iter().filter(|r| !r.is_empty()) {
+        if rule.starts_with('~') || rule.starts_with('%') {
+            selector.key_patterns.push(rule.to_string());
+        } else if let Some(channel) = rule.strip_prefix('&') {
+            selector.channel_patterns.push(channel.to_string());
+        } else {
+            commands.push(*rule);
+        }
+    }
+    selector.key_patterns = split_patterns(selector.key_patterns, '~');
+    selector.commands = commands.join(" ");
+    selector
+}
+
+/// Parses an `ACL GETUSER` reply (Redis 6 arrays or Redis 7 strings).
+pub fn parse_getuser(name: &str, value: &redis::Value) -> Option<AclUser> {
+    let fields = fields(value);
+    if fields.is_empty() {
+        return None;
+    }
+    let flags = texts(field(&fields, "flags"));
+    let selectors = match field(&fields, "selectors") {
+        Some(redis::Value::Array(items)) => items.iter()
+            .map(|s| {
+                let f = self::fields(s);
+                AclSelector {
+                    commands: field(&f, "commands").and_then(text).unwrap_or_default(),
+                    key_patterns: split_patterns(texts(field(&f, "keys")), '~'),
+                    channel_patterns: split_patterns(texts(field(&f, "channels")), '&'),
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    Some(AclUser {
+        name: name.to_string(),
+        enabled: flags.iter().any(|f| f == "on"),
+        flags,
+        password_hashes: texts(field(&fields, "passwords")),
+        commands: field(&fields, "commands").and_then(text).unwrap_or_default(),
+        key_patterns: split_patterns(texts(field(&fields, "keys")), '~'),
+        channel_patterns: split_patterns(texts(field(&fields, "channels")), '&'),
+        selectors,
+    })
+}
+
+pub fn parse_acl_log(value: &redis::Value) -> Vec<AclLogEntry> {
+    let redis::Value::Array(entries) = value else {
+        return Vec::new();
+    };
+    entries.iter()
+        .map(|entry| {
+            let f = fields(entry);
+            let get = |name: &str| field(&f, name).and_then(text).unwrap_or_default();
+            AclLogEntry {
+                count: number(field(&f, "count")).unwrap_or(0.0) as u64,
+                reason: get("reason"),
+                context: get("context"),
+                object: get("object"),
+                username: get("username"),
+                age_seconds: number(field(&f, "age-seconds")).unwrap_or(0.0),
+                client_info: get("client-info"),
+                // Only reported from Redis 7.2
+                entry_id: number(field(&f, "entry-id")).map(|n| n as u64),
+                timestamp_created: number(field(&f, "timestamp-created")).map(|n| n as u64),
+                timestamp_last_updated: number(field(&f, "timestamp-last-updated")).map(|n| n as u64),
+            }
+        })
+        .collect()
+}
+
+fn acl_error(e: redis::RedisError) -> AppError {
+    AppError::from(e).context("ACL command failed (requires Redis 6+ and ACL permissions)")
+}
+
+pub async fn list_users(conn: &mut MultiplexedConnection) -> Result<Vec<AclUser>, AppError> {
+    let lines: Vec<String> = redis::cmd("ACL").arg("LIST").query_async(conn).await.map_err(acl_error)?;
+    Ok(lines.iter().filter_map(|l| parse_acl_rule_line(l)).collect())
+}
+
+pub async fn get_user(conn: &mut MultiplexedConnection, username: &str) -> Result<Option<AclUser>, AppError> {
+    let reply: redis::Value = redis::cmd("ACL").arg("GETUSER").arg(username).query_async(conn).await.map_err(acl_error)?;
+    Ok(parse_getuser(username, &reply))
+}
+
+pub async fn categories(conn: &mut MultiplexedConnection, category: Option<&str>) -> Result<Vec<String>, AppError> {
+    let mut cmd = redis::cmd("ACL");
+    cmd.arg("CAT");
+    if let Some(category) = category {
+        cmd.arg(category);
+    }
+    cmd.query_async(conn).await.map_err(acl_error)
+}
+
+pub async fn log(conn: &mut MultiplexedConnection, count: Option<u32>) -> Result<Vec<AclLogEntry>, AppError> {
+    let mut cmd = redis::cmd("ACL");
+    cmd.arg("LOG");
+    if let Some(count) = count {
+        cmd.arg(count);
+    }
+    let reply: redis::Value = cmd.query_async(conn).await.map_err(acl_error)?;
+    Ok(parse_acl_log(&reply))
+}
+
+/// Asks the server whether `username` could run `command` (with its key
+/// arguments) without actually running it. Needs Redis 7.
+pub async fn dry_run(conn: &mut MultiplexedConnection, username: &str, command: &[String]) -> Result<AclDryRunResult, AppError> {
+    if command.is_empty() {
+        return Err(AppError::invalid("Command is required"));
+    }
+    let reply: redis::Value = redis::cmd("ACL")
+        .arg("DRYRUN")
+        .arg(username)
+        .arg(command)
+        .query_async(conn)
+        .await
+        .map_err(acl_error)?;
+    Ok(match reply {
+        redis::Value::Okay => AclDryRunResult { allowed: true, reason: None },
+        other => match text(&other) {
+            Some(s) if s == "OK" => AclDryRunResult { allowed: true, reason: None },
+            reason => AclDryRunResult { allowed: false, reason },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> redis::Value {
+        redis::Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_acl_list_lines() {
+        let user = parse_acl_rule_line("user app on #abc123 ~app:* %R~cache:* &events:* -@all +get +set (~other:* +hget)").unwrap();
+        assert_eq!(user.name, "app");
+        assert!(user.enabled);
+        assert_eq!(user.password_hashes, vec!["abc123"]);
+        assert_eq!(user.key_patterns, vec!["app:*", "%R~cache:*"]);
+        assert_eq!(user.channel_patterns, vec!["events:*"]);
+        assert_eq!(user.commands, "-@all +get +set");
+        assert_eq!(user.selectors.len(), 1);
+        assert_eq!(user.selectors[0].key_patterns, vec!["other:*"]);
+        assert_eq!(user.selectors[0].commands, "+hget");
+        assert!(parse_acl_rule_line("nonsense").is_none());
+    }
+
+    #[test]
+    fn parses_getuser_replies() {
+        let reply = redis::Value::Array(vec![
+            bulk("flags"), redis::Value::Array(vec![bulk("on"), bulk("sanitize-payload")]),
+            bulk("passwords"), redis::Value::Array(vec![bulk("5e88")]),
+            bulk("commands"), bulk("+@all"),
+            bulk("keys"), bulk("~* %R~ro:*"),
+            bulk("channels"), bulk("&*"),
+            bulk("selectors"), redis::Value::Array(vec![]),
+        ]);
+        let user = parse_getuser("default", &reply).unwrap();
+        assert!(user.enabled);
+        assert_eq!(user.key_patterns, vec!["*", "%R~ro:*"]);
+        assert_eq!(user.channel_patterns, vec!["*"]);
+        assert_eq!(user.password_hashes, vec!["5e88"]);
+        assert!(parse_getuser("missing", &redis::Value::Nil).is_none());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::types::*;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+
+const MAX_EVENTS: usize = 5_000;
+const WINDOW_MS: u64 = 60_000;
+
+pub const AUTH_FAILURES_PER_MINUTE: &str = "aclAuthFailuresPerMinute";
+pub const DENIALS_PER_MINUTE: &str = "aclDenialsPerMinute";
+
+/// Used when the caller doesn't configure any alerts.
+pub fn default_alerts() -> Vec<CustomAlert> {
+    vec![CustomAlert {
+        id: "acl-auth-failures".to_string(),
+        name: "Repeated auth failures".to_string(),
+        condition_type: AUTH_FAILURES_PER_MINUTE.to_string(),
+        threshold: 10.0,
+        enabled: true,
+    }]
+}
+
+fn client_field<'a>(client_info: &'a str, name: &str) -> Option<&'a str> {
+    client_info.split_whitespace().find_map(|part| part.strip_prefix(name)?.strip_prefix('='))
+}
+
+/// Turns the aggregated `ACL LOG` into a stream of new occurrences. Redis
+/// folds repeats into one entry and bumps its count, so an entry is new when
+/// its id (or, before 7.2, its reason/context/object/user) is unseen, and
+/// repeated when its count grew. Redis keeps only the latest client of a
+/// folded entry, so the client isn't part of its identity.
+#[derive(Default)]
+pub struct AclLogTracker {
+    counts: HashMap<String, u64>,
+    primed: bool,
+}
+
+impl AclLogTracker {
+    /// False until the first poll, whose entries predate the monitor.
+    pub fn primed(&self) -> bool {
+        self.primed
+    }
+
+    pub fn observe(&mut self, server_id: &str, entries: &[AclLogEntry], now_ms: u64) -> Vec<AclSecurityEvent> {
+        self.primed = true;
+        let mut events = Vec::new();
+        let mut seen = HashMap::new();
+        for entry in entries {
+            let client_addr = client_field(&entry.client_info, "addr").map(String::from);
+            let key = match entry.entry_id {
+                Some(id) => id.to_string(),
+                None => format!("{}|{}|{}|{}", entry.reason, entry.context, entry.object, entry.username),
+            };
+            // A lower count means the log was reset and the entry started over
+            let occurrences = match self.counts.get(&key) {
+                Some(&previous) if entry.count >= previous => entry.count - previous,
+                _ => entry.count,
+            };
+            seen.insert(key, entry.count);
+            if occurrences == 0 {
+                continue;
+            }
+
+            let last_seen_at = entry.timestamp_last_updated
+                .unwrap_or_else(|| now_ms.saturating_sub((entry.age_seconds * 1000.0) as u64));
+            events.push(AclSecurityEvent {
+                server_id: server_id.to_string(),
+                entry_id: entry.entry_id,
+                reason: entry.reason.clone(),
+                context: entry.context.clone(),
+                object: entry.object.clone(),
+                username: entry.username.clone(),
+                client_ip: client_addr.as_deref().and_then(|a| a.rsplit_once(':')).map(|(ip, _)| ip.to_string()),
+                client_addr,
+                client_name: client_field(&entry.client_info, "name").filter(|n| !n.is_empty()).map(String::from),
+                occurrences,
+                total_count: entry.count,
+                observed_at: now_ms,
+                last_seen_at,
+            });
+        }
+        // Entries that fell off the log can't come back with the same key
+        self.counts = seen;
+        events
+    }
+}
+
+/// Sliding one-minute window over observed events that checks them against
+/// the configured alerts. Each alert fires at most once a minute per source.
+#[derive(Default)]
+pub struct AclAlertWindow {
+    events: VecDeque<AclSecurityEvent>,
+    last_fired: HashMap<(String, String), u64>,
+}
+
+impl AclAlertWindow {
+    pub fn evaluate(&mut self, new_events: &[AclSecurityEvent], alerts: &[CustomAlert], now_ms: u64) -> Vec<AlertTrigger> {
+        self.events.extend(new_events.iter().cloned());
+        while self.events.front().is_some_and(|e| e.observed_at + WINDOW_MS < now_ms) {
+            self.events.pop_front();
+        }
+
+        let mut triggers = Vec::new();
+        for alert in alerts.iter().filter(|a| a.enabled) {
+            // Auth failures are grouped by client IP, other denials by user
+            let mut totals: HashMap<String, u64> = HashMap::new();
+            for event in &self.events {
+                let source = match alert.condition_type.as_str() {
+                    AUTH_FAILURES_PER_MINUTE if event.reason == "auth" => event.client_ip.clone().unwrap_or_default(),
+                    DENIALS_PER_MINUTE if event.reason != "auth" => event.username.clone(),
+                    _ => continue,
+                };
+                *totals.entry(source).or_default() += event.occurrences;
+            }
+
+            for (source, total) in totals {
+                if (total as f64) <= alert.threshold {
+                    continue;
+                }
+                let fired_key = (alert.id.clone(), source.clone());
+                if self.last_fired.get(&fired_key).is_some_and(|at| at + WINDOW_MS > now_ms) {
+                    continue;
+                }
+                self.last_fired.insert(fired_key, now_ms);
+                let what = if alert.condition_type == AUTH_FAILURES_PER_MINUTE { "auth failures from" } else { "ACL denials for user" };
+                triggers.push(AlertTrigger {
+                    alert_id: alert.id.clone(),
+                    alert_name: alert.name.clone(),
+                    triggered_at: now_ms,
+                    current_value: total as f64,
+                    threshold: alert.threshold,
+                    message: format!("{} {} {} in the last minute", total, what, source),
+                });
+            }
+        }
+        triggers
+    }
+}
+
+/// Locally persisted history of ACL security events across servers.
+pub struct AclEventStore {
+    path: PathBuf,
+    events: Mutex<VecDeque<AclSecurityEvent>>,
+}
+
+impl AclEventStore {
+    pub fn new(path: PathBuf) -> Self {
+        let events = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, events: Mutex::new(events) }
+    }
+
+    pub fn append(&self, new_events: Vec<AclSecurityEvent>) -> Result<(), String> {
+        if new_events.is_empty() {
+            return Ok(());
+        }
+        let mut events = self.events.lock();
+        events.extend(new_events);
+        while events.len() > MAX_EVENTS {
+            events.pop_front();
+        }
+        let content = serde_json::to_string(&*events).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, content).map_err(|e| e.to_string())
+    }
+
+    /// Newest first.
+    pub fn list(&self, server_id: &str, since_ms: Option<u64>, limit: usize) -> Vec<AclSecurityEvent> {
+        self.events.lock().iter().rev()
+            .filter(|e| e.server_id == server_id)
+            .filter(|e| since_ms.map_or(true, |since| e.observed_at >= since))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Auth failures per client IP since `since_ms`, most first.
+    pub fn auth_failures_by_ip(&self, server_id: &str, since_ms: u64) -> Vec<(String, u64)> {
+        let mut totals: HashMap<String, u64> = HashMap::new();
+        for event in self.events.lock().iter().filter(|e| e.server_id == server_id && e.reason == "auth" && e.observed_at >= since_ms) {
+            *totals.entry(event.client_ip.clone().unwrap_or_default()).or_default() += event.occurrences;
+        }
+        let mut totals: Vec<(String, u64)> = totals.into_iter().collect();
+        totals.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_failure(count: u64, addr: &str) -> AclLogEntry {
+        AclLogEntry {
+            count,
+            reason: "auth".to_string(),
+            context: "toplevel".to_string(),
+            object: "AUTH".to_string(),
+            username: "admin".to_string(),
+            age_seconds: 1.0,
+            client_info: format!("id=7 addr={} laddr=10.0.0.1:6379 name=", addr),
+            entry_id: None,
+            timestamp_created: None,
+            timestamp_last_updated: None,
+        }
+    }
+
+    #[test]
+    fn tracks_new_occurrences_across_polls() {
+        let mut tracker = AclLogTracker::default();
+        let first = tracker.observe("s1", &[auth_failure(3, "10.0.0.9:5000")], 1_000);
+        assert_eq!(first[0].occurrences, 3);
+        assert_eq!(first[0].client_ip.as_deref(), Some("10.0.0.9"));
+
+        assert!(tracker.observe("s1", &[auth_failure(3, "10.0.0.9:5000")], 2_000).is_empty());
+        assert_eq!(tracker.observe("s1", &[auth_failure(5, "10.0.0.9:5000")], 3_000)[0].occurrences, 2);
+        // ACL LOG RESET
+        assert_eq!(tracker.observe("s1", &[auth_failure(1, "10.0.0.9:5000")], 4_000)[0].occurrences, 1);
+
+        // A reconnect from a new port is still the same folded entry
+        let reconnect = tracker.observe("s1", &[auth_failure(2, "10.0.0.9:5001")], 5_000);
+        assert_eq!(reconnect[0].occurrences, 1);
+        assert_eq!(reconnect[0].client_addr.as_deref(), Some("10.0.0.9:5001"));
+    }
+
+    #[test]
+    fn fires_auth_failure_alerts_once_per_window() {
+        let mut tracker = AclLogTracker::default();
+        let mut window = AclAlertWindow::default();
+        let alerts = default_alerts();
+
+        let events = tracker.observe("s1", &[auth_failure(11, "10.0.0.9:5000")], 1_000);
+        let triggers = window.evaluate(&events, &alerts, 1_000);
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].current_value, 11.0);
+
+        let events = tracker.observe("s1", &[auth_failure(15, "10.0.0.9:5000")], 2_000);
+        assert!(window.evaluate(&events, &alerts, 2_000).is_empty());
+    }
+}
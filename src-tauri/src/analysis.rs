@@ -0,0 +1,291 @@
+use crate::error::AppError;
+use crate::jobs::{throttle, JobHandle};
+use crate::namespace::NamespaceTree;
+use crate::types::*;
+use redis::aio::MultiplexedConnection;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+
+const TOP_KEYS_LIMIT: usize = 20;
+const TOP_NAMESPACES_LIMIT: usize = 20;
+const DEFAULT_BATCH_SIZE: u32 = 500;
+const PROGRESS_INTERVAL_MS: u128 = 1000;
+
+/// Accumulates per-key samples into a `DatabaseAnalysis`.
+///
+/// Shared by the live SCAN based analysis and the offline RDB analyzer so both
+/// produce identical reports for the same keyspace.
+#[derive(Default, Clone)]
+pub struct AnalysisAccumulator {
+    sampled: u64,
+    total_memory: u64,
+    type_counts: HashMap<String, u64>,
+    type_memory: HashMap<String, u64>,
+    namespaces: NamespaceTree,
+    top_keys: Vec<KeyMemoryInfo>,
+    keys_with_ttl: u64,
+    keys_without_ttl: u64,
+    expiring_1h: u64,
+    expiring_24h: u64,
+    expiring_7d: u64,
+    memory_1h: u64,
+    memory_24h: u64,
+}
+
+impl AnalysisAccumulator {
+    pub fn with_namespace_options(options: &NamespaceOptions) -> Self {
+        Self { namespaces: NamespaceTree::new(options), ..Self::default() }
+    }
+
+    pub fn sampled(&self) -> u64 {
+        self.sampled
+    }
+
+    pub fn record(&mut self, key: &str, key_type: &str, mem: u64, ttl: i64) {
+        self.sampled += 1;
+        *self.type_counts.entry(key_type.to_string()).or_insert(0) += 1;
+        *self.type_memory.entry(key_type.to_string()).or_insert(0) += mem;
+        self.total_memory += mem;
+
+        if ttl > 0 {
+            self.keys_with_ttl += 1;
+            if ttl <= 3600 { self.expiring_1h += 1; self.memory_1h += mem; }
+            if ttl <= 86400 { self.expiring_24h += 1; self.memory_24h += mem; }
+            if ttl <= 604800 { self.expiring_7d += 1; }
+        } else {
+            self.keys_without_ttl += 1;
+        }
+
+        self.namespaces.insert(key, mem);
+
+        self.top_keys.push(KeyMemoryInfo {
+            key: key.to_string(),
+            key_type: key_type.to_string(),
+            memory_bytes: mem,
+            ttl,
+        });
+        // Keep the candidate list bounded when walking millions of keys
+        if self.top_keys.len() >= TOP_KEYS_LIMIT * 10 {
+            self.trim_top_keys();
+        }
+    }
+
+    fn trim_top_keys(&mut self) {
+        self.top_keys.sort_by_key(|k| Reverse(k.memory_bytes));
+        self.top_keys.truncate(TOP_KEYS_LIMIT);
+    }
+
+    pub fn finish(mut self) -> DatabaseAnalysis {
+        self.trim_top_keys();
+
+        let total_keys = self.sampled;
+        let total_memory = self.total_memory;
+        let type_distribution: Vec<TypeDistribution> = self.type_counts.iter()
+            .map(|(t, c)| TypeDistribution {
+                key_type: t.clone(),
+                count: *c,
+                percentage: if total_keys > 0 { (*c as f64 / total_keys as f64) * 100.0 } else { 0.0 },
+            }).collect();
+
+        let memory_by_type: Vec<TypeMemory> = self.type_memory.iter()
+            .map(|(t, m)| TypeMemory {
+                key_type: t.clone(),
+                memory_bytes: *m,
+                percentage: if total_memory > 0 { (*m as f64 / total_memory as f64) * 100.0 } else { 0.0 },
+            }).collect();
+
+        let mut recommendations = Vec::new();
+        if self.keys_without_ttl > self.keys_with_ttl {
+            recommendations.push("Consider setting TTL on keys to prevent memory growth".to_string());
+        }
+        if let Some(top) = self.top_keys.first() {
+            if top.memory_bytes > 1024 * 1024 {
+                recommendations.push(format!("Large key detected: {} ({} MB)", top.key, top.memory_bytes / (1024 * 1024)));
+            }
+        }
+
+        DatabaseAnalysis {
+            total_keys,
+            total_memory,
+            type_distribution,
+            memory_by_type,
+            expiry_analysis: ExpiryAnalysis {
+                keys_with_ttl: self.keys_with_ttl,
+                keys_without_ttl: self.keys_without_ttl,
+                expiring_in_1h: self.expiring_1h,
+                expiring_in_24h: self.expiring_24h,
+                expiring_in_7d: self.expiring_7d,
+                memory_to_free_1h: self.memory_1h,
+                memory_to_free_24h: self.memory_24h,
+            },
+            top_keys_by_memory: self.top_keys,
+            namespaces: self.namespaces.top_level(TOP_NAMESPACES_LIMIT),
+            namespace_tree: self.namespaces.nodes(),
+            recommendations,
+        }
+    }
+}
+
+pub struct KeySample {
+    pub key: String,
+    pub key_type: String,
+    pub ttl: i64,
+    pub memory: u64,
+}
+
+/// Probes MEMORY USAGE once up front; an error inside a pipeline fails the
+/// whole batch, so servers that disable it must be detected beforehand.
+pub async fn supports_memory_usage(conn: &mut MultiplexedConnection) -> bool {
+    redis::cmd("MEMORY")
+        .arg("USAGE")
+        .arg("__redis_tics_probe__")
+        .query_async::<Option<u64>>(conn)
+        .await
+        .is_ok()
+}
+
+/// Fetches TYPE, TTL and optionally MEMORY USAGE for a batch of keys in a
+/// single pipelined round trip.
+pub async fn fetch_key_samples(conn: &mut MultiplexedConnection, keys: &[String], with_memory: bool) -> Result<Vec<KeySample>, AppError> {
+    if keys.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("TYPE").arg(key);
+        pipe.cmd("TTL").arg(key);
+        if with_memory {
+            pipe.cmd("MEMORY").arg("USAGE").arg(key).arg("SAMPLES").arg(0);
+        }
+    }
+
+    let results: Vec<redis::Value> = pipe
+        .query_async(conn)
+        .await?;
+
+    let stride = if with_memory { 3 } else { 2 };
+    Ok(keys.iter().enumerate().map(|(i, key)| {
+        let key_type = match results.get(i * stride) {
+            Some(redis::Value::SimpleString(s)) => s.clone(),
+            Some(redis::Value::BulkString(b)) => String::from_utf8_lossy(b).to_string(),
+            _ => "unknown".to_string(),
+        };
+        let ttl = match results.get(i * stride + 1) {
+            Some(redis::Value::Int(n)) => *n,
+            _ => -1,
+        };
+        let memory = match results.get(i * stride + 2).filter(|_| with_memory) {
+            Some(redis::Value::Int(n)) => *n as u64,
+            _ => 0,
+        };
+        KeySample { key: key.clone(), key_type, ttl, memory }
+    }).collect())
+}
+
+/// Walks the keyspace in the background, emitting `analysis-progress` events
+/// with partial results until it finishes or the job is cancelled.
+pub async fn run_analysis_job(
+    mut conn: MultiplexedConnection,
+    job: &JobHandle,
+    server_id: &str,
+    options: &AnalysisOptions,
+    app: &AppHandle,
+) -> AnalysisReport {
+    let started = std::time::Instant::now();
+    let started_at = chrono::Utc::now().timestamp_millis() as u64;
+    let total_keys: u64 = redis::cmd("DBSIZE").query_async(&mut conn).await.unwrap_or(0);
+    let with_memory = supports_memory_usage(&mut conn).await;
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).clamp(10, 10_000);
+    let pattern = options.pattern.clone().filter(|p| !p.is_empty()).unwrap_or_else(|| "*".to_string());
+    let limit = options.sample_size.unwrap_or(u64::MAX);
+
+    let mut acc = AnalysisAccumulator::with_namespace_options(&options.namespace_options.clone().unwrap_or_default());
+    let mut cursor = "0".to_string();
+    let mut last_progress = std::time::Instant::now();
+    let mut error = None;
+    let mut status = "completed";
+
+    loop {
+        if job.is_cancelled() {
+            status = "cancelled";
+            break;
+        }
+
+        let scan: Result<(String, Vec<String>), _> = redis::cmd("SCAN")
+            .arg(&cursor)
+            .arg("MATCH").arg(&pattern)
+            .arg("COUNT").arg(batch_size)
+            .query_async(&mut conn)
+            .await;
+        let (new_cursor, mut keys) = match scan {
+            Ok(r) => r,
+            Err(e) => {
+                error = Some(e.to_string());
+                status = "failed";
+                break;
+            }
+        };
+
+        keys.truncate((limit - acc.sampled()).min(keys.len() as u64) as usize);
+        match fetch_key_samples(&mut conn, &keys, with_memory).await {
+            Ok(samples) => {
+                for s in samples {
+                    acc.record(&s.key, &s.key_type, s.memory, s.ttl);
+                }
+            }
+            Err(e) => {
+                error = Some(e.to_string());
+                status = "failed";
+                break;
+            }
+        }
+
+        cursor = new_cursor;
+        if cursor == "0" || acc.sampled() >= limit {
+            break;
+        }
+
+        if last_progress.elapsed().as_millis() >= PROGRESS_INTERVAL_MS {
+            last_progress = std::time::Instant::now();
+            app.emit("analysis-progress", AnalysisProgress {
+                job_id: job.id.clone(),
+                server_id: server_id.to_string(),
+                status: "running".to_string(),
+                scanned_keys: acc.sampled(),
+                total_keys,
+                elapsed_ms: started.elapsed().as_millis() as u64,
+                partial: Some(acc.clone().finish()),
+                error: None,
+            }).ok();
+        }
+
+        throttle(options.throttle_ms.unwrap_or(0)).await;
+    }
+
+    let scanned_keys = acc.sampled();
+    let analysis = acc.finish();
+    app.emit("analysis-progress", AnalysisProgress {
+        job_id: job.id.clone(),
+        server_id: server_id.to_string(),
+        status: status.to_string(),
+        scanned_keys,
+        total_keys,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        partial: Some(analysis.clone()),
+        error,
+    }).ok();
+
+    AnalysisReport {
+        job_id: job.id.clone(),
+        server_id: server_id.to_string(),
+        status: status.to_string(),
+        started_at,
+        finished_at: chrono::Utc::now().timestamp_millis() as u64,
+        scanned_keys,
+        total_keys,
+        complete: status == "completed" && cursor == "0",
+        analysis,
+    }
+}
@@ -0,0 +1,1166 @@
+use crate::rdb::{RdbEntry, RdbParser, RdbValue};
+use crate::types::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+const TIMELINE_BUCKET_SECS: u64 = 60;
+const MAX_ARG_DISPLAY_LEN: usize = 256;
+const MAX_COLLECTION_DISPLAY: usize = 1000;
+/// Redis refuses to grow a string past 512 MB (`proto-max-bulk-len`).
+const MAX_STRING_LEN: usize = 512 * 1024 * 1024;
+
+struct AofPart {
+    path: PathBuf,
+    file_type: &'static str,
+}
+
+struct AofCommand {
+    offset: u64,
+    timestamp: Option<u64>,
+    db: u64,
+    args: Vec<Vec<u8>>,
+}
+
+impl AofCommand {
+    fn name(&self) -> String {
+        String::from_utf8_lossy(&self.args[0]).to_uppercase()
+    }
+}
+
+#[derive(Default)]
+struct WalkStats {
+    truncated: bool,
+    total_bytes: u64,
+    formats: Vec<&'static str>,
+}
+
+/// Resolves a path to the ordered list of files making up the AOF. Accepts a
+/// single AOF file, a Redis 7 manifest, or the `appendonlydir` directory.
+fn resolve_parts(path: &str) -> Result<Vec<AofPart>, String> {
+    let path = Path::new(path);
+    if path.is_dir() {
+        let manifest = std::fs::read_dir(path)
+            .map_err(|e| e.to_string())?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| p.extension().map(|ext| ext == "manifest").unwrap_or(false))
+            .ok_or("No AOF manifest found in directory")?;
+        return parse_manifest(&manifest);
+    }
+    if path.extension().map(|ext| ext == "manifest").unwrap_or(false) {
+        return parse_manifest(path);
+    }
+    Ok(vec![AofPart { path: path.to_path_buf(), file_type: "single" }])
+}
+
+fn parse_manifest(manifest: &Path) -> Result<Vec<AofPart>, String> {
+    let content = std::fs::read_to_string(manifest)
+        .map_err(|e| format!("Failed to read manifest {}: {}", manifest.display(), e))?;
+    let dir = manifest.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut base = None;
+    let mut incr: Vec<(u64, AofPart)> = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')) {
+        let tokens = split_manifest_line(line);
+        let get = |name: &str| {
+            tokens.chunks(2).find(|c| c.len() == 2 && c[0] == name).map(|c| c[1].clone())
+        };
+        let file = get("file").ok_or_else(|| format!("Invalid manifest line: {}", line))?;
+        let seq = get("seq").and_then(|s| s.parse().ok()).unwrap_or(0);
+        match get("type").as_deref() {
+            Some("b") => base = Some(AofPart { path: dir.join(file), file_type: "base" }),
+            Some("i") => incr.push((seq, AofPart { path: dir.join(file), file_type: "incr" })),
+            // History files are leftovers from a previous rewrite and not part of the dataset
+            _ => {}
+        }
+    }
+
+    incr.sort_by_key(|(seq, _)| *seq);
+    Ok(base.into_iter().chain(incr.into_iter().map(|(_, p)| p)).collect())
+}
+
+fn split_manifest_line(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut token = String::new();
+        if c == '"' {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => { if let Some(escaped) = chars.next() { token.push(escaped); } }
+                    '"' => break,
+                    _ => token.push(c),
+                }
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() { break; }
+                token.push(c);
+                chars.next();
+            }
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+enum RespItem {
+    Timestamp(u64),
+    Command(Vec<Vec<u8>>),
+}
+
+enum RespRead {
+    Item(RespItem),
+    End,
+    Truncated,
+}
+
+struct RespReader<R: BufRead> {
+    inner: R,
+    pos: u64,
+}
+
+enum Line {
+    Complete(Vec<u8>),
+    Partial,
+    Eof,
+}
+
+impl<R: BufRead> RespReader<R> {
+    fn read_line(&mut self) -> Result<Line, String> {
+        let mut line = Vec::new();
+        let n = self.inner.read_until(b'\n', &mut line).map_err(|e| e.to_string())?;
+        self.pos += n as u64;
+        if n == 0 {
+            return Ok(Line::Eof);
+        }
+        if !line.ends_with(b"\r\n") {
+            return Ok(Line::Partial);
+        }
+        line.truncate(line.len() - 2);
+        Ok(Line::Complete(line))
+    }
+
+    fn next(&mut self) -> Result<RespRead, String> {
+        let line = match self.read_line()? {
+            Line::Complete(line) => line,
+            Line::Partial => return Ok(RespRead::Truncated),
+            Line::Eof => return Ok(RespRead::End),
+        };
+
+        if let Some(annotation) = line.strip_prefix(b"#") {
+            let text = String::from_utf8_lossy(annotation);
+            if let Some(ts) = text.strip_prefix("TS:").and_then(|t| t.trim().parse().ok()) {
+                return Ok(RespRead::Item(RespItem::Timestamp(ts)));
+            }
+            return self.next();
+        }
+
+        let count = parse_prefixed(&line, b'*')?;
+        let mut args = Vec::with_capacity(count.min(1024) as usize);
+        for _ in 0..count {
+            let header = match self.read_line()? {
+                Line::Complete(h) => h,
+                _ => return Ok(RespRead::Truncated),
+            };
+            let len = parse_prefixed(&header, b'$')? as usize;
+            let mut data = Vec::with_capacity(len.min(64 * 1024) + 2);
+            let read = (&mut self.inner).take(len as u64 + 2).read_to_end(&mut data).map_err(|e| e.to_string())?;
+            self.pos += read as u64;
+            if read != len + 2 {
+                return Ok(RespRead::Truncated);
+            }
+            data.truncate(len);
+            args.push(data);
+        }
+        if args.is_empty() {
+            return Err(format!("Empty command at offset {}", self.pos));
+        }
+        Ok(RespRead::Item(RespItem::Command(args)))
+    }
+}
+
+fn parse_prefixed(line: &[u8], prefix: u8) -> Result<u64, String> {
+    if line.first() != Some(&prefix) {
+        return Err(format!(
+            "Invalid AOF format: expected '{}' but found '{}'",
+            prefix as char,
+            String::from_utf8_lossy(line)
+        ));
+    }
+    std::str::from_utf8(&line[1..])
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("Invalid length in '{}'", String::from_utf8_lossy(line)))
+}
+
+enum AofEvent<'a> {
+    BaseKey(RdbEntry),
+    Command(&'a AofCommand),
+}
+
+/// Streams every base key and logged command, in order, across all AOF parts.
+/// The callback can stop the walk early, e.g. at a point-in-time cutoff.
+fn walk_aof<F>(parts: &[AofPart], mut on_event: F) -> Result<WalkStats, String>
+where
+    F: FnMut(AofEvent) -> ControlFlow<()>,
+{
+    let mut stats = WalkStats::default();
+    let mut timestamp: Option<u64> = None;
+    let mut db = 0u64;
+
+    for part in parts {
+        let file = File::open(&part.path)
+            .map_err(|e| format!("Failed to open {}: {}", part.path.display(), e))?;
+        let mut reader = BufReader::new(file);
+        let file_offset = stats.total_bytes;
+
+        let has_preamble = reader.fill_buf().map(|b| b.starts_with(b"REDIS")).unwrap_or(false);
+        let mut rdb_bytes = 0;
+        if has_preamble {
+            let mut parser = RdbParser::new(reader)?;
+            while let Some(entry) = parser.next_entry()? {
+                if on_event(AofEvent::BaseKey(entry)).is_break() {
+                    stats.total_bytes = file_offset + parser.position();
+                    return Ok(stats);
+                }
+            }
+            rdb_bytes = parser.position();
+            reader = parser.into_inner();
+        }
+
+        let mut resp = RespReader { inner: reader, pos: 0 };
+        let mut saw_commands = false;
+        loop {
+            let offset = file_offset + rdb_bytes + resp.pos;
+            match resp.next()? {
+                RespRead::Item(RespItem::Timestamp(ts)) => timestamp = Some(ts),
+                RespRead::Item(RespItem::Command(args)) => {
+                    saw_commands = true;
+                    if args[0].eq_ignore_ascii_case(b"SELECT") {
+                        db = args.get(1)
+                            .and_then(|a| std::str::from_utf8(a).ok())
+                            .and_then(|a| a.parse().ok())
+                            .unwrap_or(db);
+                    }
+                    let command = AofCommand { offset, timestamp, db, args };
+                    if on_event(AofEvent::Command(&command)).is_break() {
+                        stats.total_bytes = offset;
+                        return Ok(stats);
+                    }
+                }
+                RespRead::End => break,
+                RespRead::Truncated => {
+                    stats.truncated = true;
+                    break;
+                }
+            }
+        }
+
+        stats.formats.push(match (has_preamble, saw_commands) {
+            (true, true) => "rdb+aof",
+            (true, false) => "rdb",
+            _ => "aof",
+        });
+        stats.total_bytes = file_offset + rdb_bytes + resp.pos;
+    }
+
+    Ok(stats)
+}
+
+fn command_keys(args: &[Vec<u8>]) -> Vec<&[u8]> {
+    let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+    match name.as_str() {
+        "SELECT" | "MULTI" | "EXEC" | "FLUSHDB" | "FLUSHALL" | "SWAPDB" | "PING" => vec![],
+        "DEL" | "UNLINK" | "EXISTS" | "TOUCH" => args[1..].iter().map(|a| a.as_slice()).collect(),
+        "MSET" | "MSETNX" => args[1..].iter().step_by(2).map(|a| a.as_slice()).collect(),
+        "RENAME" | "RENAMENX" | "COPY" | "SMOVE" | "RPOPLPUSH" | "LMOVE" => {
+            args[1..].iter().take(2).map(|a| a.as_slice()).collect()
+        }
+        _ => args.get(1).map(|a| vec![a.as_slice()]).unwrap_or_default(),
+    }
+}
+
+fn display_arg(arg: &[u8]) -> String {
+    let text = String::from_utf8_lossy(arg);
+    if text.len() > MAX_ARG_DISPLAY_LEN {
+        let mut end = MAX_ARG_DISPLAY_LEN;
+        while !text.is_char_boundary(end) { end -= 1; }
+        format!("{}... ({} bytes)", &text[..end], arg.len())
+    } else {
+        text.to_string()
+    }
+}
+
+fn sorted_counts(counts: HashMap<String, u64>) -> Vec<AofCommandCount> {
+    let mut out: Vec<AofCommandCount> = counts
+        .into_iter()
+        .map(|(command, count)| AofCommandCount { command, count })
+        .collect();
+    out.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.command.cmp(&b.command)));
+    out
+}
+
+/// Summarizes an AOF: files involved, command mix, and a per-minute timeline
+/// when the file carries `#TS:` annotations.
+pub fn inspect_aof(path: &str) -> Result<AofInspection, String> {
+    let parts = resolve_parts(path)?;
+    let mut base_keys = 0u64;
+    let mut total_commands = 0u64;
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    let mut buckets: Vec<(AofTimelineBucket, HashMap<String, u64>)> = Vec::new();
+    let mut first_timestamp = None;
+    let mut last_timestamp = None;
+
+    let stats = walk_aof(&parts, |event| {
+        let cmd = match event {
+            AofEvent::BaseKey(_) => {
+                base_keys += 1;
+                return ControlFlow::Continue(());
+            }
+            AofEvent::Command(cmd) => cmd,
+        };
+
+        let name = cmd.name();
+        total_commands += 1;
+        *counts.entry(name.clone()).or_insert(0) += 1;
+
+        let bucket = cmd.timestamp.map(|t| t - t % TIMELINE_BUCKET_SECS);
+        if cmd.timestamp.is_some() {
+            first_timestamp = first_timestamp.or(cmd.timestamp);
+            last_timestamp = cmd.timestamp;
+        }
+        if buckets.last().map(|(b, _)| b.timestamp != bucket).unwrap_or(true) {
+            let next = AofTimelineBucket { timestamp: bucket, start_offset: cmd.offset, total_commands: 0, commands: vec![] };
+            buckets.push((next, HashMap::new()));
+        }
+        if let Some((current, bucket_counts)) = buckets.last_mut() {
+            current.total_commands += 1;
+            *bucket_counts.entry(name).or_insert(0) += 1;
+        }
+        ControlFlow::Continue(())
+    })?;
+
+    let files = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| AofFileInfo {
+            name: part.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            file_type: part.file_type.to_string(),
+            size: std::fs::metadata(&part.path).map(|m| m.len()).unwrap_or(0),
+            format: stats.formats.get(i).copied().unwrap_or("aof").to_string(),
+        })
+        .collect();
+
+    Ok(AofInspection {
+        files,
+        total_commands,
+        total_bytes: stats.total_bytes,
+        base_keys,
+        has_timestamps: first_timestamp.is_some(),
+        first_timestamp,
+        last_timestamp,
+        truncated: stats.truncated,
+        command_counts: sorted_counts(counts),
+        timeline: buckets
+            .into_iter()
+            .map(|(bucket, counts)| AofTimelineBucket { commands: sorted_counts(counts), ..bucket })
+            .collect(),
+    })
+}
+
+/// Returns the most recent `limit` logged writes touching `key`.
+pub fn aof_key_history(path: &str, key: &str, limit: usize) -> Result<Vec<AofCommandRecord>, String> {
+    let parts = resolve_parts(path)?;
+    let mut history: VecDeque<AofCommandRecord> = VecDeque::new();
+
+    walk_aof(&parts, |event| {
+        let AofEvent::Command(cmd) = event else { return ControlFlow::Continue(()) };
+        if command_keys(&cmd.args).contains(&key.as_bytes()) {
+            history.push_back(AofCommandRecord {
+                offset: cmd.offset,
+                timestamp: cmd.timestamp,
+                db: cmd.db,
+                command: cmd.name(),
+                args: cmd.args[1..].iter().map(|a| display_arg(a)).collect(),
+            });
+            if history.len() > limit {
+                history.pop_front();
+            }
+        }
+        ControlFlow::Continue(())
+    })?;
+
+    Ok(history.into())
+}
+
+/// Replays the AOF into an in-memory keyspace up to a byte offset and/or a
+/// `#TS:` timestamp, returning the resulting keys.
+pub fn reconstruct_aof_state(
+    path: &str,
+    until_offset: Option<u64>,
+    until_timestamp: Option<u64>,
+    limit: usize,
+) -> Result<AofStateSnapshot, String> {
+    let parts = resolve_parts(path)?;
+    let mut keyspace = Keyspace::default();
+    let mut unsupported: HashMap<String, u64> = HashMap::new();
+    let mut commands_applied = 0u64;
+    let mut last_offset = 0u64;
+    let mut last_timestamp = None;
+    let mut replay_error = None;
+
+    let stats = walk_aof(&parts, |event| {
+        let cmd = match event {
+            AofEvent::BaseKey(entry) => {
+                keyspace.load_base(entry);
+                return ControlFlow::Continue(());
+            }
+            AofEvent::Command(cmd) => cmd,
+        };
+
+        let past_offset = until_offset.map(|o| cmd.offset >= o).unwrap_or(false);
+        let past_time = match (until_timestamp, cmd.timestamp) {
+            (Some(until), Some(ts)) => ts > until,
+            _ => false,
+        };
+        if past_offset || past_time {
+            return ControlFlow::Break(());
+        }
+
+        // Relative expiries are resolved against the last annotation when present
+        let now_ms = cmd.timestamp
+            .map(|t| t as i64 * 1000)
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        match keyspace.apply(cmd.db, &cmd.args, now_ms) {
+            Ok(true) => {}
+            Ok(false) => *unsupported.entry(cmd.name()).or_insert(0) += 1,
+            Err(e) => {
+                replay_error = Some(format!("Invalid command at offset {}: {}", cmd.offset, e));
+                return ControlFlow::Break(());
+            }
+        }
+        commands_applied += 1;
+        last_offset = cmd.offset;
+        last_timestamp = cmd.timestamp;
+        ControlFlow::Continue(())
+    })?;
+    if let Some(e) = replay_error {
+        return Err(e);
+    }
+
+    let at_ms = until_timestamp
+        .or(last_timestamp)
+        .map(|t| t as i64 * 1000)
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    let (total_keys, keys) = keyspace.snapshot(at_ms, limit);
+
+    Ok(AofStateSnapshot {
+        offset: if commands_applied > 0 { last_offset } else { stats.total_bytes },
+        timestamp: until_timestamp.or(last_timestamp),
+        commands_applied,
+        total_keys,
+        keys,
+        unsupported_commands: sorted_counts(unsupported),
+    })
+}
+
+enum Value {
+    String(Vec<u8>),
+    List(VecDeque<Vec<u8>>),
+    Set(HashSet<Vec<u8>>),
+    ZSet(HashMap<Vec<u8>, f64>),
+    Hash(HashMap<Vec<u8>, Vec<u8>>),
+    Stream(Vec<StreamEntry>),
+    Other(&'static str),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::List(_) => "list",
+            Value::Set(_) => "set",
+            Value::ZSet(_) => "zset",
+            Value::Hash(_) => "hash",
+            Value::Stream(_) => "stream",
+            Value::Other(t) => t,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Value::List(l) => l.is_empty(),
+            Value::Set(s) => s.is_empty(),
+            Value::ZSet(z) => z.is_empty(),
+            Value::Hash(h) => h.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+struct Entry {
+    value: Value,
+    expire_at_ms: Option<i64>,
+}
+
+type Db = HashMap<Vec<u8>, Entry>;
+
+#[derive(Default)]
+struct Keyspace {
+    dbs: HashMap<u64, Db>,
+}
+
+fn text(arg: &[u8]) -> String {
+    String::from_utf8_lossy(arg).to_string()
+}
+
+fn int_arg(arg: &[u8]) -> Option<i64> {
+    std::str::from_utf8(arg).ok()?.parse().ok()
+}
+
+fn float_arg(arg: &[u8]) -> Option<f64> {
+    match std::str::from_utf8(arg).ok()?.to_lowercase().as_str() {
+        "inf" | "+inf" => Some(f64::INFINITY),
+        "-inf" => Some(f64::NEG_INFINITY),
+        other => other.parse().ok(),
+    }
+}
+
+fn format_float(value: f64) -> Vec<u8> {
+    let mut s = format!("{}", value);
+    if s.contains('.') {
+        s = s.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    s.into_bytes()
+}
+
+/// Normalizes a Redis style index (negative counts from the end) into a range
+/// clamped to `len`.
+fn normalize_range(start: i64, stop: i64, len: usize) -> Option<(usize, usize)> {
+    let len = len as i64;
+    let start = if start < 0 { (len + start).max(0) } else { start };
+    let stop = if stop < 0 { len + stop } else { stop.min(len - 1) };
+    if start > stop || start >= len {
+        return None;
+    }
+    Some((start as usize, stop as usize))
+}
+
+impl Keyspace {
+    fn load_base(&mut self, entry: RdbEntry) {
+        let value = match entry.value {
+            RdbValue::String(s) => Value::String(s),
+            RdbValue::List(l) => Value::List(l.into()),
+            RdbValue::Set(s) => Value::Set(s.into_iter().collect()),
+            RdbValue::ZSet(z) => Value::ZSet(z.into_iter().collect()),
+            RdbValue::Hash(h) => Value::Hash(h.into_iter().collect()),
+            RdbValue::Stream { .. } => Value::Stream(Vec::new()),
+            RdbValue::Module { .. } => Value::Other("module"),
+        };
+        self.dbs.entry(entry.db).or_default().insert(entry.key, Entry { value, expire_at_ms: entry.expire_at_ms });
+    }
+
+    /// Applies one logged command. Returns false for commands the replay does
+    /// not model, so the caller can report them, and an error for commands
+    /// Redis itself would have refused.
+    fn apply(&mut self, db: u64, args: &[Vec<u8>], now_ms: i64) -> Result<bool, String> {
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        let arg = |i: usize| args.get(i).map(|a| a.as_slice()).unwrap_or(&[]);
+
+        let handled = match name.as_str() {
+            "SELECT" | "MULTI" | "EXEC" | "PING" => true,
+            "FLUSHALL" => { self.dbs.clear(); true }
+            "FLUSHDB" => { self.dbs.remove(&db); true }
+            "SWAPDB" => {
+                let (Some(a), Some(b)) = (int_arg(arg(1)), int_arg(arg(2))) else { return Ok(false) };
+                let first = self.dbs.remove(&(a as u64)).unwrap_or_default();
+                let second = self.dbs.remove(&(b as u64)).unwrap_or_default();
+                self.dbs.insert(a as u64, second);
+                self.dbs.insert(b as u64, first);
+                true
+            }
+            "MOVE" => {
+                let Some(target) = int_arg(arg(2)) else { return Ok(false) };
+                if let Some(entry) = self.db(db).remove(arg(1)) {
+                    self.db(target as u64).insert(arg(1).to_vec(), entry);
+                }
+                true
+            }
+            "COPY" => {
+                let mut target_db = db;
+                let mut i = 3;
+                while i < args.len() {
+                    if arg(i).eq_ignore_ascii_case(b"DB") {
+                        target_db = int_arg(arg(i + 1)).unwrap_or(db as i64) as u64;
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                let copied = self.db(db).get(arg(1)).map(|e| Entry {
+                    value: clone_value(&e.value),
+                    expire_at_ms: e.expire_at_ms,
+                });
+                if let Some(entry) = copied {
+                    self.db(target_db).insert(arg(2).to_vec(), entry);
+                }
+                true
+            }
+            _ => return self.apply_in_db(db, &name, args, now_ms),
+        };
+        Ok(handled)
+    }
+
+    fn db(&mut self, db: u64) -> &mut Db {
+        self.dbs.entry(db).or_default()
+    }
+
+    fn apply_in_db(&mut self, db: u64, name: &str, args: &[Vec<u8>], now_ms: i64) -> Result<bool, String> {
+        let keys = self.db(db);
+        let arg = |i: usize| args.get(i).map(|a| a.as_slice()).unwrap_or(&[]);
+        let key = arg(1).to_vec();
+
+        let handled = match name {
+            "SET" => {
+                let mut expire_at_ms = None;
+                let mut keep_ttl = false;
+                let mut i = 3;
+                while i < args.len() {
+                    let opt = text(arg(i)).to_uppercase();
+                    let n = int_arg(arg(i + 1)).unwrap_or(0);
+                    match opt.as_str() {
+                        "EX" => { expire_at_ms = Some(now_ms + n * 1000); i += 1; }
+                        "PX" => { expire_at_ms = Some(now_ms + n); i += 1; }
+                        "EXAT" => { expire_at_ms = Some(n * 1000); i += 1; }
+                        "PXAT" => { expire_at_ms = Some(n); i += 1; }
+                        "KEEPTTL" => keep_ttl = true,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                if keep_ttl {
+                    expire_at_ms = keys.get(&key).and_then(|e| e.expire_at_ms);
+                }
+                keys.insert(key, Entry { value: Value::String(arg(2).to_vec()), expire_at_ms });
+                true
+            }
+            "SETNX" | "GETSET" => {
+                keys.insert(key, Entry { value: Value::String(arg(2).to_vec()), expire_at_ms: None });
+                true
+            }
+            "SETEX" | "PSETEX" => {
+                let ttl = int_arg(arg(2)).unwrap_or(0);
+                let ms = if name == "SETEX" { ttl * 1000 } else { ttl };
+                keys.insert(key, Entry { value: Value::String(arg(3).to_vec()), expire_at_ms: Some(now_ms + ms) });
+                true
+            }
+            "MSET" | "MSETNX" => {
+                for pair in args[1..].chunks(2).filter(|p| p.len() == 2) {
+                    keys.insert(pair[0].clone(), Entry { value: Value::String(pair[1].clone()), expire_at_ms: None });
+                }
+                true
+            }
+            "APPEND" | "SETRANGE" | "INCR" | "DECR" | "INCRBY" | "DECRBY" | "INCRBYFLOAT" => {
+                let entry = keys.entry(key).or_insert(Entry { value: Value::String(Vec::new()), expire_at_ms: None });
+                let Value::String(current) = &mut entry.value else { return Ok(true) };
+                match name {
+                    "APPEND" => current.extend_from_slice(arg(2)),
+                    "SETRANGE" => {
+                        let offset = int_arg(arg(2)).unwrap_or(0).max(0) as usize;
+                        let patch = arg(3);
+                        if offset.saturating_add(patch.len()) > MAX_STRING_LEN {
+                            return Err(format!("SETRANGE on {} would grow it past 512 MB", text(arg(1))));
+                        }
+                        if current.len() < offset + patch.len() {
+                            current.resize(offset + patch.len(), 0);
+                        }
+                        current[offset..offset + patch.len()].copy_from_slice(patch);
+                    }
+                    "INCRBYFLOAT" => {
+                        let value = float_arg(current).unwrap_or(0.0) + float_arg(arg(2)).unwrap_or(0.0);
+                        *current = format_float(value);
+                    }
+                    _ => {
+                        let delta = match name {
+                            "INCR" => 1,
+                            "DECR" => -1,
+                            "INCRBY" => int_arg(arg(2)).unwrap_or(0),
+                            _ => -int_arg(arg(2)).unwrap_or(0),
+                        };
+                        *current = (int_arg(current).unwrap_or(0) + delta).to_string().into_bytes();
+                    }
+                }
+                true
+            }
+            "DEL" | "UNLINK" | "GETDEL" => {
+                for k in &args[1..] {
+                    keys.remove(k);
+                }
+                true
+            }
+            "EXPIRE" | "PEXPIRE" | "EXPIREAT" | "PEXPIREAT" => {
+                let n = int_arg(arg(2)).unwrap_or(0);
+                let at = match name {
+                    "EXPIRE" => now_ms + n * 1000,
+                    "PEXPIRE" => now_ms + n,
+                    "EXPIREAT" => n * 1000,
+                    _ => n,
+                };
+                if let Some(entry) = keys.get_mut(&key) {
+                    entry.expire_at_ms = Some(at);
+                }
+                true
+            }
+            "PERSIST" => {
+                if let Some(entry) = keys.get_mut(&key) {
+                    entry.expire_at_ms = None;
+                }
+                true
+            }
+            "RENAME" | "RENAMENX" => {
+                if let Some(entry) = keys.remove(&key) {
+                    keys.insert(arg(2).to_vec(), entry);
+                }
+                true
+            }
+            "HSET" | "HMSET" | "HSETNX" => {
+                if let Value::Hash(h) = &mut hash_entry(keys, key).value {
+                    for pair in args[2..].chunks(2).filter(|p| p.len() == 2) {
+                        h.insert(pair[0].clone(), pair[1].clone());
+                    }
+                }
+                true
+            }
+            "HINCRBY" | "HINCRBYFLOAT" => {
+                if let Value::Hash(h) = &mut hash_entry(keys, key).value {
+                    let field = h.entry(arg(2).to_vec()).or_insert_with(|| b"0".to_vec());
+                    *field = if name == "HINCRBY" {
+                        (int_arg(field).unwrap_or(0) + int_arg(arg(3)).unwrap_or(0)).to_string().into_bytes()
+                    } else {
+                        format_float(float_arg(field).unwrap_or(0.0) + float_arg(arg(3)).unwrap_or(0.0))
+                    };
+                }
+                true
+            }
+            "HDEL" => {
+                if let Some(Entry { value: Value::Hash(h), .. }) = keys.get_mut(&key) {
+                    for field in &args[2..] {
+                        h.remove(field);
+                    }
+                }
+                true
+            }
+            "LPUSH" | "RPUSH" | "LPUSHX" | "RPUSHX" => {
+                let entry = keys.entry(key).or_insert(Entry { value: Value::List(VecDeque::new()), expire_at_ms: None });
+                if let Value::List(l) = &mut entry.value {
+                    for item in &args[2..] {
+                        if name.starts_with('L') { l.push_front(item.clone()) } else { l.push_back(item.clone()) }
+                    }
+                }
+                true
+            }
+            "LPOP" | "RPOP" => {
+                if let Some(Entry { value: Value::List(l), .. }) = keys.get_mut(&key) {
+                    let count = int_arg(arg(2)).unwrap_or(1).max(0);
+                    for _ in 0..count {
+                        if name == "LPOP" { l.pop_front(); } else { l.pop_back(); }
+                    }
+                }
+                true
+            }
+            "LSET" => {
+                if let Some(Entry { value: Value::List(l), .. }) = keys.get_mut(&key) {
+                    let len = l.len() as i64;
+                    let index = int_arg(arg(2)).unwrap_or(0);
+                    let index = if index < 0 { len + index } else { index };
+                    if let Some(slot) = l.get_mut(index.max(0) as usize) {
+                        *slot = arg(3).to_vec();
+                    }
+                }
+                true
+            }
+            "LREM" => {
+                if let Some(Entry { value: Value::List(l), .. }) = keys.get_mut(&key) {
+                    let count = int_arg(arg(2)).unwrap_or(0);
+                    let target = arg(3);
+                    let limit = if count == 0 { usize::MAX } else { count.unsigned_abs() as usize };
+                    let mut removed = 0;
+                    if count >= 0 {
+                        l.retain(|item| {
+                            if removed < limit && item.as_slice() == target { removed += 1; false } else { true }
+                        });
+                    } else {
+                        let mut items: Vec<Vec<u8>> = l.drain(..).rev().collect();
+                        items.retain(|item| {
+                            if removed < limit && item.as_slice() == target { removed += 1; false } else { true }
+                        });
+                        *l = items.into_iter().rev().collect();
+                    }
+                }
+                true
+            }
+            "LTRIM" => {
+                if let Some(Entry { value: Value::List(l), .. }) = keys.get_mut(&key) {
+                    match normalize_range(int_arg(arg(2)).unwrap_or(0), int_arg(arg(3)).unwrap_or(-1), l.len()) {
+                        Some((start, stop)) => { l.truncate(stop + 1); l.drain(..start); }
+                        None => l.clear(),
+                    }
+                }
+                true
+            }
+            "LINSERT" => {
+                if let Some(Entry { value: Value::List(l), .. }) = keys.get_mut(&key) {
+                    if let Some(pos) = l.iter().position(|item| item.as_slice() == arg(3)) {
+                        let at = if arg(2).eq_ignore_ascii_case(b"BEFORE") { pos } else { pos + 1 };
+                        l.insert(at, arg(4).to_vec());
+                    }
+                }
+                true
+            }
+            "RPOPLPUSH" | "LMOVE" => {
+                let from_left = name == "LMOVE" && arg(3).eq_ignore_ascii_case(b"LEFT");
+                let to_right = name == "LMOVE" && arg(4).eq_ignore_ascii_case(b"RIGHT");
+                let item = match keys.get_mut(&key) {
+                    Some(Entry { value: Value::List(l), .. }) => if from_left { l.pop_front() } else { l.pop_back() },
+                    _ => None,
+                };
+                if let Some(item) = item {
+                    // Clean up before touching the destination in case source == destination
+                    if keys.get(&key).map(|e| e.value.is_empty()).unwrap_or(false) {
+                        keys.remove(&key);
+                    }
+                    let dest = keys.entry(arg(2).to_vec()).or_insert(Entry { value: Value::List(VecDeque::new()), expire_at_ms: None });
+                    if let Value::List(l) = &mut dest.value {
+                        if to_right { l.push_back(item) } else { l.push_front(item) }
+                    }
+                }
+                true
+            }
+            "SADD" => {
+                let entry = keys.entry(key).or_insert(Entry { value: Value::Set(HashSet::new()), expire_at_ms: None });
+                if let Value::Set(s) = &mut entry.value {
+                    s.extend(args[2..].iter().cloned());
+                }
+                true
+            }
+            "SREM" => {
+                if let Some(Entry { value: Value::Set(s), .. }) = keys.get_mut(&key) {
+                    for member in &args[2..] {
+                        s.remove(member);
+                    }
+                }
+                true
+            }
+            "SMOVE" => {
+                let moved = match keys.get_mut(&key) {
+                    Some(Entry { value: Value::Set(s), .. }) => s.remove(arg(3)),
+                    _ => false,
+                };
+                if moved {
+                    let dest = keys.entry(arg(2).to_vec()).or_insert(Entry { value: Value::Set(HashSet::new()), expire_at_ms: None });
+                    if let Value::Set(s) = &mut dest.value {
+                        s.insert(arg(3).to_vec());
+                    }
+                }
+                true
+            }
+            "ZADD" => {
+                let entry = keys.entry(key).or_insert(Entry { value: Value::ZSet(HashMap::new()), expire_at_ms: None });
+                let Value::ZSet(z) = &mut entry.value else { return Ok(true) };
+                let (mut nx, mut xx, mut gt, mut lt, mut incr) = (false, false, false, false, false);
+                let mut i = 2;
+                while i < args.len() {
+                    match text(arg(i)).to_uppercase().as_str() {
+                        "NX" => nx = true,
+                        "XX" => xx = true,
+                        "GT" => gt = true,
+                        "LT" => lt = true,
+                        "INCR" => incr = true,
+                        "CH" => {}
+                        _ => break,
+                    }
+                    i += 1;
+                }
+                for pair in args[i..].chunks(2).filter(|p| p.len() == 2) {
+                    let Some(score) = float_arg(&pair[0]) else { continue };
+                    let existing = z.get(&pair[1]).copied();
+                    if (nx && existing.is_some()) || (xx && existing.is_none()) {
+                        continue;
+                    }
+                    let new_score = if incr { existing.unwrap_or(0.0) + score } else { score };
+                    if let Some(old) = existing {
+                        if (gt && new_score <= old) || (lt && new_score >= old) {
+                            continue;
+                        }
+                    }
+                    z.insert(pair[1].clone(), new_score);
+                }
+                true
+            }
+            "ZINCRBY" => {
+                let entry = keys.entry(key).or_insert(Entry { value: Value::ZSet(HashMap::new()), expire_at_ms: None });
+                if let Value::ZSet(z) = &mut entry.value {
+                    *z.entry(arg(3).to_vec()).or_insert(0.0) += float_arg(arg(2)).unwrap_or(0.0);
+                }
+                true
+            }
+            "ZREM" => {
+                if let Some(Entry { value: Value::ZSet(z), .. }) = keys.get_mut(&key) {
+                    for member in &args[2..] {
+                        z.remove(member);
+                    }
+                }
+                true
+            }
+            "ZREMRANGEBYRANK" | "ZPOPMIN" | "ZPOPMAX" => {
+                if let Some(Entry { value: Value::ZSet(z), .. }) = keys.get_mut(&key) {
+                    let mut ranked: Vec<(Vec<u8>, f64)> = z.iter().map(|(m, s)| (m.clone(), *s)).collect();
+                    ranked.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+                    let range = match name {
+                        "ZPOPMIN" => normalize_range(0, int_arg(arg(2)).unwrap_or(1) - 1, ranked.len()),
+                        "ZPOPMAX" => {
+                            let count = int_arg(arg(2)).unwrap_or(1);
+                            normalize_range(-count, -1, ranked.len())
+                        }
+                        _ => normalize_range(int_arg(arg(2)).unwrap_or(0), int_arg(arg(3)).unwrap_or(-1), ranked.len()),
+                    };
+                    if let Some((start, stop)) = range {
+                        for (member, _) in &ranked[start..=stop] {
+                            z.remove(member);
+                        }
+                    }
+                }
+                true
+            }
+            "ZREMRANGEBYSCORE" => {
+                if let Some(Entry { value: Value::ZSet(z), .. }) = keys.get_mut(&key) {
+                    let bound = |raw: &[u8]| -> (f64, bool) {
+                        match raw.strip_prefix(b"(") {
+                            Some(rest) => (float_arg(rest).unwrap_or(0.0), true),
+                            None => (float_arg(raw).unwrap_or(0.0), false),
+                        }
+                    };
+                    let (min, min_excl) = bound(arg(2));
+                    let (max, max_excl) = bound(arg(3));
+                    z.retain(|_, s| {
+                        let above = if min_excl { *s > min } else { *s >= min };
+                        let below = if max_excl { *s < max } else { *s <= max };
+                        !(above && below)
+                    });
+                }
+                true
+            }
+            "XADD" => {
+                let mut i = 2;
+                while i < args.len() {
+                    match text(arg(i)).to_uppercase().as_str() {
+                        "NOMKSTREAM" => i += 1,
+                        "MAXLEN" | "MINID" => {
+                            i += 1;
+                            if arg(i) == b"=" || arg(i) == b"~" { i += 1; }
+                            i += 1;
+                            if arg(i).eq_ignore_ascii_case(b"LIMIT") { i += 2; }
+                        }
+                        _ => break,
+                    }
+                }
+                let entry = keys.entry(key).or_insert(Entry { value: Value::Stream(Vec::new()), expire_at_ms: None });
+                if let Value::Stream(entries) = &mut entry.value {
+                    let fields = args.get(i + 1..).unwrap_or(&[])
+                        .chunks(2)
+                        .filter(|p| p.len() == 2)
+                        .map(|p| (text(&p[0]), text(&p[1])))
+                        .collect();
+                    entries.push(StreamEntry { id: text(arg(i)), fields });
+                }
+                true
+            }
+            "XDEL" => {
+                if let Some(Entry { value: Value::Stream(entries), .. }) = keys.get_mut(&key) {
+                    let ids: HashSet<String> = args[2..].iter().map(|a| text(a)).collect();
+                    entries.retain(|e| !ids.contains(&e.id));
+                }
+                true
+            }
+            _ => false,
+        };
+
+        // Redis deletes collections once their last element is removed
+        if keys.get(arg(1)).map(|e| e.value.is_empty()).unwrap_or(false) {
+            keys.remove(arg(1));
+        }
+        Ok(handled)
+    }
+
+    fn snapshot(&self, at_ms: i64, limit: usize) -> (u64, Vec<KeyValue>) {
+        let mut live: Vec<(&Vec<u8>, &Entry)> = self.dbs.values()
+            .flat_map(|db| db.iter())
+            .filter(|(_, e)| e.expire_at_ms.map(|at| at > at_ms).unwrap_or(true))
+            .collect();
+        let total = live.len() as u64;
+        live.sort_by(|a, b| a.0.cmp(b.0));
+
+        let keys = live
+            .into_iter()
+            .take(limit)
+            .map(|(key, entry)| KeyValue {
+                key: text(key),
+                key_type: entry.value.type_name().to_string(),
+                ttl: entry.expire_at_ms.map(|at| (at - at_ms + 999) / 1000).unwrap_or(-1),
+                value: display_value(&entry.value),
+                size: None,
+            })
+            .collect();
+        (total, keys)
+    }
+}
+
+fn hash_entry(keys: &mut Db, key: Vec<u8>) -> &mut Entry {
+    keys.entry(key).or_insert(Entry { value: Value::Hash(HashMap::new()), expire_at_ms: None })
+}
+
+fn clone_value(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(s.clone()),
+        Value::List(l) => Value::List(l.clone()),
+        Value::Set(s) => Value::Set(s.clone()),
+        Value::ZSet(z) => Value::ZSet(z.clone()),
+        Value::Hash(h) => Value::Hash(h.clone()),
+        Value::Stream(s) => Value::Stream(s.clone()),
+        Value::Other(t) => Value::Other(t),
+    }
+}
+
+fn display_value(value: &Value) -> KeyValueData {
+    match value {
+        Value::String(s) => KeyValueData::String(text(s)),
+        Value::List(l) => KeyValueData::List(l.iter().take(MAX_COLLECTION_DISPLAY).map(|i| text(i)).collect()),
+        Value::Set(s) => {
+            let mut members: Vec<String> = s.iter().map(|m| text(m)).collect();
+            members.sort();
+            members.truncate(MAX_COLLECTION_DISPLAY);
+            KeyValueData::Set(members)
+        }
+        Value::ZSet(z) => {
+            let mut members: Vec<ZSetMember> = z.iter()
+                .map(|(m, s)| ZSetMember { member: text(m), score: *s })
+                .collect();
+            members.sort_by(|a, b| a.score.total_cmp(&b.score).then_with(|| a.member.cmp(&b.member)));
+            members.truncate(MAX_COLLECTION_DISPLAY);
+            KeyValueData::ZSet(members)
+        }
+        Value::Hash(h) => KeyValueData::Hash(
+            h.iter().take(MAX_COLLECTION_DISPLAY).map(|(f, v)| (text(f), text(v))).collect(),
+        ),
+        Value::Stream(entries) => KeyValueData::Stream(entries.iter().take(100).cloned().collect()),
+        Value::Other(t) => KeyValueData::Unknown(format!("Type '{}' not supported for viewing", t)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resp(args: &[&str]) -> String {
+        let mut out = format!("*{}\r\n", args.len());
+        for a in args {
+            out.push_str(&format!("${}\r\n{}\r\n", a.len(), a));
+        }
+        out
+    }
+
+    fn write_aof(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("redis-tics-{}-{}.aof", name, std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    fn sample_aof() -> String {
+        let mut aof = String::new();
+        aof.push_str("#TS:1700000000\r\n");
+        aof.push_str(&resp(&["SELECT", "0"]));
+        aof.push_str(&resp(&["SET", "user:1", "alice"]));
+        aof.push_str(&resp(&["RPUSH", "queue", "a", "b", "c"]));
+        aof.push_str("#TS:1700000120\r\n");
+        aof.push_str(&resp(&["SET", "user:1", "bob"]));
+        aof.push_str(&resp(&["LPOP", "queue"]));
+        aof.push_str(&resp(&["HSET", "h", "f", "v"]));
+        aof
+    }
+
+    #[test]
+    fn test_inspect_counts_and_timeline() {
+        let path = write_aof("inspect", &sample_aof());
+        let inspection = inspect_aof(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(inspection.total_commands, 6);
+        assert!(inspection.has_timestamps);
+        assert!(!inspection.truncated);
+        assert_eq!(inspection.timeline.len(), 2);
+        assert_eq!(inspection.command_counts[0].command, "SET");
+        assert_eq!(inspection.command_counts[0].count, 2);
+    }
+
+    #[test]
+    fn test_key_history_and_point_in_time() {
+        let path = write_aof("replay", &sample_aof());
+
+        let history = aof_key_history(&path, "user:1", 10).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].args, vec!["user:1", "bob"]);
+
+        let before = reconstruct_aof_state(&path, None, Some(1_700_000_060), 100).unwrap();
+        let after = reconstruct_aof_state(&path, None, None, 100).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(before.total_keys, 2);
+        match &before.keys[1].value {
+            KeyValueData::String(v) => assert_eq!(v, "alice"),
+            other => panic!("unexpected value {:?}", other),
+        }
+        assert_eq!(after.total_keys, 3);
+        match &after.keys[1].value {
+            KeyValueData::List(items) => assert_eq!(items, &vec!["b".to_string(), "c".to_string()]),
+            other => panic!("unexpected value {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_oversized_setrange_is_rejected() {
+        let mut aof = resp(&["SET", "k", "v"]);
+        aof.push_str(&resp(&["SETRANGE", "k", "4", "xy"]));
+        aof.push_str(&resp(&["SETRANGE", "k", &(MAX_STRING_LEN - 1).to_string(), "xy"]));
+        let path = write_aof("setrange", &aof);
+        let result = reconstruct_aof_state(&path, None, None, 100);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.unwrap_err().contains("512 MB"));
+    }
+
+    #[test]
+    fn test_truncated_tail_is_reported() {
+        let mut aof = resp(&["SET", "k", "v"]);
+        aof.push_str("*3\r\n$3\r\nSET\r\n$1\r\nk");
+        let path = write_aof("truncated", &aof);
+        let inspection = inspect_aof(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(inspection.total_commands, 1);
+        assert!(inspection.truncated);
+    }
+
+    #[test]
+    fn test_manifest_line_parsing() {
+        let tokens = split_manifest_line("file \"append only.aof.1.incr.aof\" seq 1 type i");
+        assert_eq!(tokens, vec!["file", "append only.aof.1.incr.aof", "seq", "1", "type", "i"]);
+    }
+}
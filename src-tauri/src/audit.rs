@@ -0,0 +1,290 @@
+use crate::commands::CommandSpec;
+use crate::config;
+use crate::crypto;
+use crate::error::AppError;
+use crate::types::{AuditEntry, AuditQuery, AuditVerification};
+use parking_lot::Mutex;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const REDACTED: &str = "***";
+
+/// Commands whose arguments are all key names, numbers or field names and
+/// can be logged verbatim.
+const KEY_ONLY_COMMANDS: &[&str] = &[
+    "DEL", "UNLINK", "EXISTS", "TOUCH", "PERSIST", "TYPE", "TTL", "PTTL", "DUMP",
+    "RENAME", "RENAMENX", "COPY", "EXPIRE", "PEXPIRE", "EXPIREAT", "PEXPIREAT",
+    "HDEL", "LTRIM", "ZREMRANGEBYRANK", "FLUSHDB", "FLUSHALL", "SELECT",
+];
+
+/// Commands that carry credentials anywhere in their arguments.
+const SECRET_COMMANDS: &[&str] = &["AUTH", "HELLO", "MIGRATE"];
+
+/// `<script or name> <numkeys> <keys...> <args...>` commands. Inline script
+/// bodies and arguments are redacted; keys stay readable.
+const SCRIPT_COMMANDS: &[&str] = &["EVAL", "EVAL_RO", "EVALSHA", "EVALSHA_RO", "FCALL", "FCALL_RO"];
+
+/// ACL SETUSER rules that set or remove a password or its hash.
+const PASSWORD_RULE_PREFIXES: &[char] = &['>', '<', '#', '!'];
+
+fn redacted(parts: &[&str], keep: impl Fn(usize, &str) -> bool) -> Vec<String> {
+    parts.iter()
+        .enumerate()
+        .map(|(i, p)| if keep(i, p) { p.to_string() } else { REDACTED.to_string() })
+        .collect()
+}
+
+/// Renders a command for the audit log: key names, config parameters and ACL
+/// users stay readable, while values, passwords and scripts are replaced with
+/// `***` so the log never holds user data or secrets. `spec` supplies the key
+/// positions; without it the first argument is taken as the key.
+pub fn redact_command(parts: &[&str], spec: Option<&CommandSpec>) -> String {
+    let Some(name) = parts.first().map(|n| n.to_uppercase()) else {
+        return String::new();
+    };
+    let sub = parts.get(1).map(|s| s.to_uppercase()).unwrap_or_default();
+    let args = &parts[1..];
+
+    let mut out = vec![name.clone()];
+    match name.as_str() {
+        n if SECRET_COMMANDS.contains(&n) => out.extend(redacted(args, |_, _| false)),
+        "CONFIG" | "ACL" if !args.is_empty() => {
+            let rest = &args[1..];
+            out.push(sub.clone());
+            out.extend(match (name.as_str(), sub.as_str()) {
+                // CONFIG SET name value [name value ...]
+                ("CONFIG", "SET") => redacted(rest, |i, _| i % 2 == 0 || !config::is_sensitive(rest[i - 1])),
+                ("CONFIG", _) => redacted(rest, |_, _| true),
+                // ACL SETUSER user [rule ...]
+                ("ACL", "SETUSER") => redacted(rest, |i, rule| i == 0 || !rule.starts_with(PASSWORD_RULE_PREFIXES)),
+                ("ACL", "DELUSER" | "GETUSER") => redacted(rest, |_, _| true),
+                _ => redacted(rest, |i, _| i == 0),
+            });
+        }
+        n if SCRIPT_COMMANDS.contains(&n) => {
+            let inline = n.starts_with("EVAL") && !n.starts_with("EVALSHA");
+            // A script split on whitespace has no numkeys where it's expected
+            let keep = match args.get(1).and_then(|n| n.parse::<usize>().ok()) {
+                Some(numkeys) => numkeys + 1,
+                None => 0,
+            };
+            out.extend(redacted(args, |i, _| (i == 0 && !inline) || (i >= 1 && i <= keep)));
+        }
+        n if KEY_ONLY_COMMANDS.contains(&n) => out.extend(args.iter().map(|p| p.to_string())),
+        _ => {
+            let keys = match spec {
+                Some(spec) => spec.key_positions(parts.len()),
+                None => vec![1],
+            };
+            out.extend(redacted(args, |i, _| keys.contains(&(i + 1))));
+        }
+    }
+    out.join(" ")
+}
+
+/// Summarises a reply without copying data into the log.
+pub fn summarize_reply(value: &redis::Value) -> String {
+    match value {
+        redis::Value::Nil => "(nil)".to_string(),
+        redis::Value::Int(n) => n.to_string(),
+        redis::Value::Okay => "OK".to_string(),
+        redis::Value::SimpleString(s) => s.clone(),
+        redis::Value::BulkString(b) => format!("({} bytes)", b.len()),
+        redis::Value::Array(items) | redis::Value::Set(items) => format!("({} items)", items.len()),
+        redis::Value::Map(items) => format!("({} entries)", items.len()),
+        redis::Value::ServerError(e) => format!("error: {}", e.code()),
+        _ => "(reply)".to_string(),
+    }
+}
+
+// Keyed so that rewriting the file and recomputing the chain isn't enough
+fn entry_hash(key: &[u8], entry: &AuditEntry) -> String {
+    let mut unhashed = entry.clone();
+    unhashed.hash = String::new();
+    let body = serde_json::to_string(&unhashed).unwrap_or_default();
+    let data = [entry.prev_hash.as_bytes(), body.as_bytes()].concat();
+    crypto::hmac_sha256(key, &data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+struct ChainHead {
+    seq: u64,
+    hash: String,
+}
+
+/// Append-only JSON-lines log where every entry carries the hash of the one
+/// before it, so edits or deletions inside the file are detectable.
+pub struct AuditLog {
+    path: PathBuf,
+    key: [u8; 32],
+    head: Mutex<ChainHead>,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf, key: [u8; 32]) -> Self {
+        let head = Self::read_entries(&path)
+            .last()
+            .map(|e| ChainHead { seq: e.seq, hash: e.hash.clone() })
+            .unwrap_or(ChainHead { seq: 0, hash: GENESIS_HASH.to_string() });
+        Self { path, key, head: Mutex::new(head) }
+    }
+
+    fn read_entries(path: &PathBuf) -> Vec<AuditEntry> {
+        match std::fs::File::open(path) {
+            Ok(file) => BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str(&line).ok())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    pub fn record(
+        &self,
+        server_id: &str,
+        db: u8,
+        command: String,
+        outcome: Result<String, String>,
+        duration_ms: u64,
+    ) -> Result<(), AppError> {
+        let mut head = self.head.lock();
+        let (success, result) = match outcome {
+            Ok(summary) => (true, summary),
+            Err(e) => (false, e),
+        };
+        let mut entry = AuditEntry {
+            seq: head.seq + 1,
+            timestamp: chrono::Utc::now().timestamp_millis() as u64,
+            server_id: server_id.to_string(),
+            db,
+            command,
+            success,
+            result,
+            duration_ms,
+            prev_hash: head.hash.clone(),
+            hash: String::new(),
+        };
+        entry.hash = entry_hash(&self.key, &entry);
+
+        let line = serde_json::to_string(&entry)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+
+        head.seq = entry.seq;
+        head.hash = entry.hash;
+        Ok(())
+    }
+
+    /// Newest first.
+    pub fn query(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        let needle = query.command_contains.as_deref().map(|c| c.to_uppercase());
+        let mut entries: Vec<AuditEntry> = Self::read_entries(&self.path)
+            .into_iter()
+            .filter(|e| query.server_id.as_deref().map_or(true, |id| e.server_id == id))
+            .filter(|e| query.from_ms.map_or(true, |from| e.timestamp >= from))
+            .filter(|e| query.to_ms.map_or(true, |to| e.timestamp <= to))
+            .filter(|e| needle.as_deref().map_or(true, |n| e.command.to_uppercase().contains(n)))
+            .filter(|e| !query.failures_only.unwrap_or(false) || !e.success)
+            .collect();
+        entries.reverse();
+        entries.truncate(query.limit.unwrap_or(500));
+        entries
+    }
+
+    /// Walks the whole chain and reports the first entry whose hash or link
+    /// does not check out.
+    pub fn verify(&self) -> AuditVerification {
+        let mut prev_hash = GENESIS_HASH.to_string();
+        let mut checked = 0u64;
+        for entry in Self::read_entries(&self.path) {
+            if entry.prev_hash != prev_hash || entry_hash(&self.key, &entry) != entry.hash {
+                return AuditVerification { valid: false, checked_entries: checked, first_invalid_seq: Some(entry.seq) };
+            }
+            prev_hash = entry.hash;
+            checked += 1;
+        }
+        AuditVerification { valid: true, checked_entries: checked, first_invalid_seq: None }
+    }
+
+    /// Writes matching entries to `dest` as JSON lines or CSV and returns how
+    /// many were exported (oldest first).
+    pub fn export(&self, query: &AuditQuery, dest: &str, format: &str) -> Result<usize, AppError> {
+        let mut entries = self.query(&AuditQuery { limit: Some(usize::MAX), ..query.clone() });
+        entries.reverse();
+
+        let content = match format {
+            "csv" => {
+                let escape = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+                let mut out = String::from("seq,timestamp,server_id,db,command,success,result,duration_ms,prev_hash,hash\n");
+                for e in &entries {
+                    out.push_str(&format!(
+                        "{},{},{},{},{},{},{},{},{},{}\n",
+                        e.seq, e.timestamp, escape(&e.server_id), e.db, escape(&e.command),
+                        e.success, escape(&e.result), e.duration_ms, e.prev_hash, e.hash
+                    ));
+                }
+                out
+            }
+            "jsonl" | "json" => entries.iter()
+                .filter_map(|e| serde_json::to_string(e).ok())
+                .map(|line| line + "\n")
+                .collect(),
+            other => return Err(format!("Unknown export format: {}", other).into()),
+        };
+
+        std::fs::write(dest, content)?;
+        Ok(entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_values_but_keeps_keys() {
+        assert_eq!(redact_command(&["set", "user:1", "secret"], None), "SET user:1 ***");
+        assert_eq!(redact_command(&["HSET", "h", "f", "v"], None), "HSET h *** ***");
+        assert_eq!(redact_command(&["del", "a", "b"], None), "DEL a b");
+        assert_eq!(redact_command(&["auth", "user", "pw"], None), "AUTH *** ***");
+        assert_eq!(redact_command(&["eval", "return 'pw'", "1", "k", "arg"], None), "EVAL *** 1 k ***");
+        assert_eq!(redact_command(&["fcall", "myfn", "0", "arg"], None), "FCALL myfn 0 ***");
+        assert_eq!(redact_command(&["eval", "return", "'pw'", "0"], None), "EVAL *** *** ***");
+
+        let mset = CommandSpec { flags: vec![], first_key: 1, last_key: -1, key_step: 2, acl_categories: vec![] };
+        assert_eq!(redact_command(&["mset", "a", "1", "b", "2"], Some(&mset)), "MSET a *** b ***");
+    }
+
+    #[test]
+    fn keeps_what_admin_commands_changed() {
+        assert_eq!(redact_command(&["config", "set", "maxmemory", "1gb"], None), "CONFIG SET maxmemory 1gb");
+        assert_eq!(
+            redact_command(&["config", "set", "requirepass", "pw", "maxmemory", "1gb"], None),
+            "CONFIG SET requirepass *** maxmemory 1gb"
+        );
+        assert_eq!(redact_command(&["acl", "setuser", "alice", "on", ">pw", "~app:*", "+get"], None), "ACL SETUSER alice on *** ~app:* +get");
+        assert_eq!(redact_command(&["acl", "deluser", "alice", "bob"], None), "ACL DELUSER alice bob");
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let path = std::env::temp_dir().join(format!("audit-{}.log", uuid::Uuid::new_v4()));
+        let log = AuditLog::new(path.clone(), [7u8; 32]);
+        log.record("s1", 0, "SET a ***".to_string(), Ok("OK".to_string()), 1).unwrap();
+        log.record("s1", 0, "DEL a".to_string(), Ok("1".to_string()), 1).unwrap();
+        assert!(log.verify().valid);
+        // The chain is bound to the key it was written with
+        assert!(!AuditLog::new(path.clone(), [8u8; 32]).verify().valid);
+
+        let content = std::fs::read_to_string(&path).unwrap().replace("DEL a", "DEL b");
+        std::fs::write(&path, content).unwrap();
+        let verification = log.verify();
+        assert!(!verification.valid);
+        assert_eq!(verification.first_invalid_seq, Some(2));
+        std::fs::remove_file(path).ok();
+    }
+}
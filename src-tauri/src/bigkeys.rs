@@ -0,0 +1,283 @@
+use crate::analysis::supports_memory_usage;
+use crate::error::AppError;
+use crate::jobs::{throttle, JobHandle};
+use crate::types::*;
+use redis::aio::MultiplexedConnection;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_TOP_N: usize = 10;
+const DEFAULT_BATCH_SIZE: u32 = 200;
+const PROGRESS_INTERVAL_MS: u128 = 1000;
+
+pub fn size_command(key_type: &str) -> Option<&'static str> {
+    match key_type {
+        "string" => Some("STRLEN"),
+        "hash" => Some("HLEN"),
+        "list" => Some("LLEN"),
+        "set" => Some("SCARD"),
+        "zset" => Some("ZCARD"),
+        "stream" => Some("XLEN"),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct TypeTally {
+    key_count: u64,
+    total_size: u64,
+    total_memory: u64,
+    biggest: Vec<BigKey>,
+}
+
+/// Keeps the N biggest keys per type, like `redis-cli --bigkeys/--memkeys`.
+struct BigKeyTracker {
+    top_n: usize,
+    by_memory: bool,
+    types: HashMap<String, TypeTally>,
+}
+
+impl BigKeyTracker {
+    /// Returns true when the key made it into its type's top list.
+    fn record(&mut self, key: &BigKey) -> bool {
+        let by_memory = self.by_memory;
+        let top_n = self.top_n;
+        let rank_of = |k: &BigKey| if by_memory { k.memory_bytes.unwrap_or(0) } else { k.size };
+        let rank = rank_of(key);
+        let tally = self.types.entry(key.key_type.clone()).or_default();
+        tally.key_count += 1;
+        tally.total_size += key.size;
+        tally.total_memory += key.memory_bytes.unwrap_or(0);
+
+        if tally.biggest.len() >= top_n && tally.biggest.last().is_some_and(|smallest| rank_of(smallest) >= rank) {
+            return false;
+        }
+        tally.biggest.push(key.clone());
+        tally.biggest.sort_by_key(|k| Reverse(rank_of(k)));
+        tally.biggest.truncate(top_n);
+        true
+    }
+
+    fn summaries(&self) -> Vec<BigKeyTypeSummary> {
+        let mut summaries: Vec<BigKeyTypeSummary> = self.types.iter()
+            .map(|(t, tally)| BigKeyTypeSummary {
+                key_type: t.clone(),
+                key_count: tally.key_count,
+                total_size: tally.total_size,
+                total_memory: tally.total_memory,
+                biggest: tally.biggest.clone(),
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.key_type.cmp(&b.key_type));
+        summaries
+    }
+}
+
+fn value_to_string(value: Option<&redis::Value>) -> Option<String> {
+    match value {
+        Some(redis::Value::SimpleString(s)) => Some(s.clone()),
+        Some(redis::Value::BulkString(b)) => Some(String::from_utf8_lossy(b).to_string()),
+        _ => None,
+    }
+}
+
+fn value_to_u64(value: Option<&redis::Value>) -> Option<u64> {
+    match value {
+        Some(redis::Value::Int(n)) => Some(*n as u64),
+        _ => None,
+    }
+}
+
+fn is_wrong_type(e: &redis::RedisError) -> bool {
+    e.code() == Some("WRONGTYPE")
+}
+
+/// Sizes a batch of keys. A key whose type changed between the two round
+/// trips fails the whole pipeline with WRONGTYPE, so the batch is then
+/// measured key by key, re-reading each type; only a key that keeps changing
+/// is left out.
+async fn measure_keys(conn: &mut MultiplexedConnection, keys: &[String], with_memory: bool) -> Result<Vec<BigKey>, AppError> {
+    match measure_batch(conn, keys, with_memory).await {
+        Ok(measured) => Ok(measured),
+        Err(e) if is_wrong_type(&e) => {
+            let mut measured = Vec::new();
+            for key in keys {
+                match measure_batch(conn, std::slice::from_ref(key), with_memory).await {
+                    Ok(found) => measured.extend(found),
+                    Err(e) if is_wrong_type(&e) => log::debug!("Skipping {}: type changed while measuring", key),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Ok(measured)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Two pipelined round trips: TYPE first, then the matching length command
+/// plus OBJECT ENCODING (and MEMORY USAGE when enabled).
+async fn measure_batch(conn: &mut MultiplexedConnection, keys: &[String], with_memory: bool) -> Result<Vec<BigKey>, redis::RedisError> {
+    if keys.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("TYPE").arg(key);
+    }
+    let types: Vec<redis::Value> = pipe.query_async(conn).await?;
+
+    // Keys deleted since SCAN report "none" and are dropped here
+    let typed: Vec<(&String, String)> = keys.iter()
+        .zip(types.iter())
+        .filter_map(|(key, t)| value_to_string(Some(t)).filter(|t| t != "none").map(|t| (key, t)))
+        .collect();
+
+    let mut pipe = redis::pipe();
+    for (key, key_type) in &typed {
+        match size_command(key_type) {
+            Some(cmd) => { pipe.cmd(cmd).arg(*key); }
+            None => { pipe.cmd("EXISTS").arg(*key); }
+        }
+        pipe.cmd("OBJECT").arg("ENCODING").arg(*key);
+        if with_memory {
+            pipe.cmd("MEMORY").arg("USAGE").arg(*key).arg("SAMPLES").arg(0);
+        }
+    }
+
+    let results: Vec<redis::Value> = pipe.query_async(conn).await?;
+
+    let stride = if with_memory { 3 } else { 2 };
+    Ok(typed.into_iter().enumerate().map(|(i, (key, key_type))| {
+        let size = if size_command(&key_type).is_some() { value_to_u64(results.get(i * stride)).unwrap_or(0) } else { 0 };
+        BigKey {
+            key: key.clone(),
+            key_type,
+            size,
+            encoding: value_to_string(results.get(i * stride + 1)).unwrap_or_default(),
+            memory_bytes: if with_memory { value_to_u64(results.get(i * stride + 2)) } else { None },
+        }
+    }).collect())
+}
+
+/// Walks the keyspace emitting `bigkey-found` whenever a key enters its type's
+/// top list and `bigkey-progress` roughly once a second.
+pub async fn run_bigkey_scan(
+    mut conn: MultiplexedConnection,
+    job: &JobHandle,
+    server_id: &str,
+    options: &BigKeyScanOptions,
+    app: &AppHandle,
+) -> BigKeyScanReport {
+    let started = std::time::Instant::now();
+    let total_keys: u64 = redis::cmd("DBSIZE").query_async(&mut conn).await.unwrap_or(0);
+    let with_memory = options.with_memory.unwrap_or(false) && supports_memory_usage(&mut conn).await;
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).clamp(10, 10_000);
+    let pattern = options.pattern.clone().filter(|p| !p.is_empty()).unwrap_or_else(|| "*".to_string());
+
+    let mut tracker = BigKeyTracker {
+        top_n: options.top_n.unwrap_or(DEFAULT_TOP_N).max(1),
+        by_memory: with_memory,
+        types: HashMap::new(),
+    };
+    let mut scanned_keys = 0u64;
+    let mut cursor = "0".to_string();
+    let mut last_progress = std::time::Instant::now();
+    let mut error = None;
+    let mut status = "completed";
+
+    let report = |status: &str, scanned_keys: u64, tracker: &BigKeyTracker, error: Option<String>| BigKeyScanReport {
+        job_id: job.id.clone(),
+        server_id: server_id.to_string(),
+        status: status.to_string(),
+        scanned_keys,
+        total_keys,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        ranked_by_memory: with_memory,
+        types: tracker.summaries(),
+        error,
+    };
+
+    loop {
+        if job.is_cancelled() {
+            status = "cancelled";
+            break;
+        }
+
+        let scan: Result<(String, Vec<String>), _> = redis::cmd("SCAN")
+            .arg(&cursor)
+            .arg("MATCH").arg(&pattern)
+            .arg("COUNT").arg(batch_size)
+            .query_async(&mut conn)
+            .await;
+        let (new_cursor, keys) = match scan {
+            Ok(r) => r,
+            Err(e) => {
+                error = Some(e.to_string());
+                status = "failed";
+                break;
+            }
+        };
+
+        match measure_keys(&mut conn, &keys, with_memory).await {
+            Ok(measured) => {
+                scanned_keys += measured.len() as u64;
+                for big_key in measured {
+                    if tracker.record(&big_key) {
+                        app.emit("bigkey-found", BigKeyFound {
+                            job_id: job.id.clone(),
+                            server_id: server_id.to_string(),
+                            big_key,
+                        }).ok();
+                    }
+                }
+            }
+            Err(e) => {
+                error = Some(e.to_string());
+                status = "failed";
+                break;
+            }
+        }
+
+        cursor = new_cursor;
+        if cursor == "0" {
+            break;
+        }
+
+        if last_progress.elapsed().as_millis() >= PROGRESS_INTERVAL_MS {
+            last_progress = std::time::Instant::now();
+            app.emit("bigkey-progress", report("running", scanned_keys, &tracker, None)).ok();
+        }
+
+        throttle(options.throttle_ms.unwrap_or(0)).await;
+    }
+
+    let final_report = report(status, scanned_keys, &tracker, error);
+    app.emit("bigkey-progress", &final_report).ok();
+    final_report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, size: u64) -> BigKey {
+        BigKey { key: name.to_string(), key_type: "hash".to_string(), size, encoding: "hashtable".to_string(), memory_bytes: None }
+    }
+
+    #[test]
+    fn keeps_top_n_per_type() {
+        let mut tracker = BigKeyTracker { top_n: 2, by_memory: false, types: HashMap::new() };
+        assert!(tracker.record(&key("a", 10)));
+        assert!(tracker.record(&key("b", 30)));
+        assert!(tracker.record(&key("c", 20)));
+        assert!(!tracker.record(&key("d", 5)));
+
+        let summary = &tracker.summaries()[0];
+        assert_eq!(summary.key_count, 4);
+        assert_eq!(summary.total_size, 65);
+        let names: Vec<&str> = summary.biggest.iter().map(|k| k.key.as_str()).collect();
+        assert_eq!(names, vec!["b", "c"]);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::error::AppError;
+use crate::jobs::JobHandle;
+use crate::rename::{ConflictMode, RenameRule};
+use crate::types::*;
+use rand::Rng;
+use redis::aio::MultiplexedConnection;
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_BATCH_SIZE: u32 = 500;
+const PREVIEW_SAMPLE_SIZE: usize = 20;
+/// Dry runs are a single command call, so they stop after this many
+/// scanned keys rather than walking a large keyspace uncancellably.
+pub const PREVIEW_SCAN_LIMIT: u64 = 100_000;
+const MAX_ERRORS: usize = 10;
+const PROGRESS_INTERVAL_MS: u128 = 500;
+
+pub enum TtlChange {
+    Set(i64),
+    Random(i64, i64),
+    Persist,
+    Extend(i64),
+}
+
+impl TtlChange {
+    pub fn from_operation(op: &BulkTtlOperation) -> Result<Self, String> {
+        let change = match op.mode.as_str() {
+            "set" => TtlChange::Set(op.seconds.ok_or("seconds is required")?),
+            "random" => {
+                let min = op.min_seconds.ok_or("minSeconds is required")?;
+                let max = op.max_seconds.ok_or("maxSeconds is required")?;
+                if min > max {
+                    return Err("minSeconds must not exceed maxSeconds".to_string());
+                }
+                TtlChange::Random(min, max)
+            }
+            "persist" => TtlChange::Persist,
+            "extend" => TtlChange::Extend(op.delta_seconds.ok_or("deltaSeconds is required")?),
+            other => return Err(format!("Unknown TTL mode: {}", other)),
+        };
+        match change {
+            TtlChange::Set(s) | TtlChange::Random(s, _) if s <= 0 => Err("TTL must be positive".to_string()),
+            _ => Ok(change),
+        }
+    }
+}
+
+/// What a bulk job does to each matching key.
+pub enum BulkAction {
+    Delete { command: &'static str },
+    Ttl(TtlChange),
+    Rename(RenameRule),
+}
+
+impl BulkAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BulkAction::Delete { .. } => "delete",
+            BulkAction::Ttl(_) => "ttl",
+            BulkAction::Rename(_) => "rename",
+        }
+    }
+
+    /// Errors that must halt the job rather than be counted and skipped.
+    fn stops_on_error(&self) -> bool {
+        matches!(self, BulkAction::Rename(rule) if rule.conflict == ConflictMode::Fail)
+    }
+
+    /// Applies the action to a batch in one pipelined round trip and returns
+    /// how many keys were affected.
+    pub async fn apply(&self, conn: &mut MultiplexedConnection, keys: &[String]) -> Result<u64, AppError> {
+        let mut pipe = redis::pipe();
+        match self {
+            BulkAction::Rename(rule) => return rule.apply(conn, keys).await,
+            // One command per key so batches never straddle cluster hash slots
+            BulkAction::Delete { command } => {
+                for key in keys {
+                    pipe.cmd(command).arg(key);
+                }
+            }
+            BulkAction::Ttl(TtlChange::Set(seconds)) => {
+                for key in keys {
+                    pipe.cmd("EXPIRE").arg(key).arg(*seconds);
+                }
+            }
+            BulkAction::Ttl(TtlChange::Random(min, max)) => {
+                let mut rng = rand::thread_rng();
+                for key in keys {
+                    pipe.cmd("EXPIRE").arg(key).arg(rng.gen_range(*min..=*max));
+                }
+            }
+            BulkAction::Ttl(TtlChange::Persist) => {
+                for key in keys {
+                    pipe.cmd("PERSIST").arg(key);
+                }
+            }
+            BulkAction::Ttl(TtlChange::Extend(delta)) => {
+                let mut ttl_pipe = redis::pipe();
+                for key in keys {
+                    ttl_pipe.cmd("PTTL").arg(key);
+                }
+                let ttls: Vec<i64> = ttl_pipe.query_async(conn).await?;
+                for (key, pttl) in keys.iter().zip(ttls) {
+                    // Persistent or vanished keys have nothing to extend
+                    if pttl > 0 {
+                        pipe.cmd("PEXPIRE").arg(key).arg((pttl + delta * 1000).max(1000));
+                    }
+                }
+            }
+        }
+        let results: Vec<i64> = pipe.query_async(conn).await?;
+        Ok(results.iter().filter(|n| **n > 0).count() as u64)
+    }
+}
+
+fn checks_ttl(filter: &BulkKeyFilter) -> bool {
+    filter.ttl_state.is_some() || filter.min_ttl_seconds.is_some() || filter.max_ttl_seconds.is_some()
+}
+
+fn ttl_matches(filter: &BulkKeyFilter, ttl: i64) -> bool {
+    let state_ok = match filter.ttl_state.as_deref() {
+        Some("persistent") => ttl == -1,
+        Some("expiring") => ttl >= 0,
+        _ => true,
+    };
+    state_ok
+        && filter.min_ttl_seconds.map_or(true, |min| ttl >= 0 && ttl >= min)
+        && filter.max_ttl_seconds.map_or(true, |max| ttl >= 0 && ttl <= max)
+}
+
+/// Applies the type/TTL/idle filters with a single pipelined round trip; plain
+/// glob matches skip it entirely.
+async fn filter_keys(conn: &mut MultiplexedConnection, keys: Vec<String>, filter: &BulkKeyFilter) -> Result<Vec<String>, AppError> {
+    let by_type = filter.key_type.as_deref().filter(|t| !t.is_empty());
+    let by_ttl = checks_ttl(filter);
+    let by_idle = filter.min_idle_seconds.is_some();
+    if keys.is_empty() || (by_type.is_none() && !by_ttl && !by_idle) {
+        return Ok(keys);
+    }
+
+    let mut pipe = redis::pipe();
+    for key in &keys {
+        if by_type.is_some() { pipe.cmd("TYPE").arg(key); }
+        if by_ttl { pipe.cmd("TTL").arg(key); }
+        if by_idle { pipe.cmd("OBJECT").arg("IDLETIME").arg(key); }
+    }
+    let results: Vec<redis::Value> = pipe.query_async(conn).await?;
+
+    let stride = by_type.is_some() as usize + by_ttl as usize + by_idle as usize;
+    Ok(keys.into_iter().enumerate().filter(|(i, _)| {
+        let mut values = results[i * stride..(i + 1) * stride].iter();
+        if let Some(wanted) = by_type {
+            let actual = match values.next() {
+                Some(redis::Value::SimpleString(s)) => s.as_str(),
+                _ => "",
+            };
+            if actual != wanted { return false; }
+        }
+        if by_ttl {
+            let ttl = match values.next() { Some(redis::Value::Int(n)) => *n, _ => -2 };
+            if !ttl_matches(filter, ttl) { return false; }
+        }
+        if let Some(min_idle) = filter.min_idle_seconds {
+            let idle = match values.next() { Some(redis::Value::Int(n)) => *n as u64, _ => 0 };
+            if idle < min_idle { return false; }
+        }
+        true
+    }).map(|(_, key)| key).collect())
+}
+
+/// One SCAN step; returns the next cursor, how many keys SCAN returned and
+/// the ones that passed the filters.
+pub async fn scan_step(
+    conn: &mut MultiplexedConnection,
+    cursor: &str,
+    filter: &BulkKeyFilter,
+    batch_size: u32,
+) -> Result<(String, u64, Vec<String>), AppError> {
+    let (next, keys): (String, Vec<String>) = redis::cmd("SCAN")
+        .arg(cursor)
+        .arg("MATCH").arg(&filter.pattern)
+        .arg("COUNT").arg(batch_size)
+        .query_async(conn)
+        .await?;
+    let scanned = keys.len() as u64;
+    Ok((next, scanned, filter_keys(conn, keys, filter).await?))
+}
+
+/// Dry run: counts matching keys and returns a sample without changing anything.
+pub async fn preview(conn: &mut MultiplexedConnection, filter: &BulkKeyFilter, options: &BulkJobOptions) -> Result<BulkPreview, AppError> {
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).clamp(10, 10_000);
+    let limit = options.max_keys.unwrap_or(u64::MAX);
+    let mut preview = BulkPreview { scanned_count: 0, matched_count: 0, sample_keys: Vec::new(), capped: false, partial: false };
+    let mut cursor = "0".to_string();
+
+    loop {
+        let (next, scanned, matched) = scan_step(conn, &cursor, filter, batch_size).await?;
+        preview.scanned_count += scanned;
+        preview.matched_count += matched.len() as u64;
+        let room = PREVIEW_SAMPLE_SIZE.saturating_sub(preview.sample_keys.len());
+        preview.sample_keys.extend(matched.into_iter().take(room));
+
+        // Keep scanning at the cap until a further match shows there are more
+        if preview.matched_count > limit {
+            preview.matched_count = limit;
+            preview.capped = true;
+            break;
+        }
+        cursor = next;
+        if cursor == "0" {
+            break;
+        }
+        if preview.scanned_count >= PREVIEW_SCAN_LIMIT {
+            preview.partial = true;
+            break;
+        }
+    }
+
+    Ok(preview)
+}
+
+/// Runs `action` over every key matching `filter`, batch by batch, honouring
+/// the ops/sec limit and max-keys cap and emitting `bulk-progress` events.
+pub async fn run_bulk_job(
+    mut conn: MultiplexedConnection,
+    job: &JobHandle,
+    server_id: &str,
+    filter: &BulkKeyFilter,
+    options: &BulkJobOptions,
+    action: BulkAction,
+    app: &AppHandle,
+) -> BulkJobProgress {
+    let started = std::time::Instant::now();
+    let ops_per_second = options.ops_per_second.filter(|n| *n > 0);
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE)
+        .min(ops_per_second.unwrap_or(u32::MAX))
+        .clamp(1, 10_000);
+    let limit = options.max_keys.unwrap_or(u64::MAX);
+
+    let mut progress = BulkJobProgress {
+        job_id: job.id.clone(),
+        server_id: server_id.to_string(),
+        operation: action.name().to_string(),
+        status: "running".to_string(),
+        scanned_count: 0,
+        processed_count: 0,
+        skipped_count: 0,
+        failed_count: 0,
+        elapsed_ms: 0,
+        capped: false,
+        errors: Vec::new(),
+    };
+    let mut cursor = "0".to_string();
+    let mut last_progress = std::time::Instant::now();
+
+    loop {
+        if job.is_cancelled() {
+            progress.status = "cancelled".to_string();
+            break;
+        }
+
+        let batch_started = std::time::Instant::now();
+        let (next, scanned, mut keys) = match scan_step(&mut conn, &cursor, filter, batch_size).await {
+            Ok(step) => step,
+            Err(e) => {
+                progress.status = "failed".to_string();
+                progress.errors.push(e.to_string());
+                break;
+            }
+        };
+        progress.scanned_count += scanned;
+
+        let attempted = progress.processed_count + progress.failed_count;
+        keys.truncate(limit.saturating_sub(attempted).min(keys.len() as u64) as usize);
+        if !keys.is_empty() {
+            match action.apply(&mut conn, &keys).await {
+                Ok(n) => {
+                    progress.processed_count += n;
+                    progress.skipped_count += keys.len() as u64 - n;
+                }
+                Err(e) if action.stops_on_error() => {
+                    progress.status = "failed".to_string();
+                    progress.errors.push(e.to_string());
+                    break;
+                }
+                Err(e) => {
+                    progress.failed_count += keys.len() as u64;
+                    if progress.errors.len() < MAX_ERRORS {
+                        progress.errors.push(e.to_string());
+                    }
+                }
+            }
+        }
+
+        cursor = next;
+        if progress.processed_count + progress.failed_count >= limit {
+            progress.capped = cursor != "0";
+            break;
+        }
+        if cursor == "0" {
+            break;
+        }
+
+        progress.elapsed_ms = started.elapsed().as_millis() as u64;
+        if last_progress.elapsed().as_millis() >= PROGRESS_INTERVAL_MS {
+            last_progress = std::time::Instant::now();
+            app.emit("bulk-progress", &progress).ok();
+        }
+
+        // Spread the work so the batch takes at least keys / ops_per_second
+        if let Some(ops) = ops_per_second {
+            let budget = std::time::Duration::from_millis(keys.len() as u64 * 1000 / ops as u64);
+            if let Some(wait) = budget.checked_sub(batch_started.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+
+    if progress.status == "running" {
+        progress.status = "completed".to_string();
+    }
+    progress.elapsed_ms = started.elapsed().as_millis() as u64;
+    app.emit("bulk-progress", &progress).ok();
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttl_filters() {
+        let expiring = BulkKeyFilter { ttl_state: Some("expiring".to_string()), max_ttl_seconds: Some(60), ..Default::default() };
+        assert!(ttl_matches(&expiring, 30));
+        assert!(!ttl_matches(&expiring, 120));
+        assert!(!ttl_matches(&expiring, -1));
+
+        let persistent = BulkKeyFilter { ttl_state: Some("persistent".to_string()), ..Default::default() };
+        assert!(ttl_matches(&persistent, -1));
+        assert!(!ttl_matches(&persistent, 10));
+    }
+
+    #[test]
+    fn validates_ttl_operations() {
+        let op = |mode: &str| BulkTtlOperation { mode: mode.to_string(), ..Default::default() };
+        assert!(TtlChange::from_operation(&op("set")).is_err());
+        assert!(matches!(TtlChange::from_operation(&op("persist")), Ok(TtlChange::Persist)));
+
+        let random = BulkTtlOperation { min_seconds: Some(600), max_seconds: Some(60), ..op("random") };
+        assert!(TtlChange::from_operation(&random).is_err());
+        let random = BulkTtlOperation { min_seconds: Some(60), max_seconds: Some(600), ..op("random") };
+        assert!(matches!(TtlChange::from_operation(&random), Ok(TtlChange::Random(60, 600))));
+    }
+}
@@ -0,0 +1,362 @@
+use crate::error::AppError;
+use parking_lot::Mutex;
+use redis::aio::MultiplexedConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Keyspaces at least this large make keyspace-wide scans (KEYS) risky.
+const LARGE_KEYSPACE: u64 = 10_000;
+const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+/// Used when the server can't describe its commands (pre-6.0 or COMMAND
+/// renamed away).
+const FALLBACK_RISKY_COMMANDS: &[&str] = &[
+    "FLUSHALL", "FLUSHDB", "SWAPDB", "KEYS", "DEBUG", "SHUTDOWN", "CONFIG",
+    "MIGRATE", "RESTORE", "MONITOR", "SCRIPT", "FUNCTION", "CLUSTER", "REPLICAOF", "SLAVEOF",
+];
+
+/// Commands that change the state of the connection they run on. The CLI
+/// shares its connection with the rest of the app, so these would leak into
+/// every other request. Entries are a command or command plus subcommand.
+const CONNECTION_STATE_COMMANDS: &[&str] = &[
+    "SELECT", "MULTI", "EXEC", "DISCARD", "WATCH", "UNWATCH", "SUBSCRIBE", "PSUBSCRIBE", "SSUBSCRIBE",
+    "UNSUBSCRIBE", "PUNSUBSCRIBE", "SUNSUBSCRIBE", "MONITOR", "RESET", "HELLO", "AUTH", "QUIT",
+    "READONLY", "READWRITE", "CLIENT REPLY", "CLIENT TRACKING", "CLIENT SETNAME", "CLIENT NO-EVICT", "CLIENT NO-TOUCH",
+];
+
+/// The parts of a `COMMAND INFO` reply the app uses to classify commands.
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    pub flags: Vec<String>,
+    /// Position of the first key argument; 0 when the command takes no keys.
+    pub first_key: i64,
+    /// Position of the last key; negative counts from the end.
+    pub last_key: i64,
+    pub key_step: i64,
+    /// `@dangerous`, `@slow`, ... (Redis 6+ only).
+    pub acl_categories: Vec<String>,
+}
+
+impl CommandSpec {
+    /// Writes data, or may (scripts, functions, PUBLISH) in ways the server
+    /// can't tell up front.
+    pub fn is_write(&self) -> bool {
+        self.has_flag("write") || self.has_flag("may_replicate")
+    }
+
+    /// Safe on a read-only server: flagged `readonly` and neither
+    /// administrative nor `@dangerous`.
+    pub fn is_read_only(&self) -> bool {
+        self.has_flag("readonly")
+            && !self.has_flag("admin")
+            && !self.has_category("@admin")
+            && !self.has_category("@dangerous")
+    }
+
+    /// Indexes of the key arguments in a command line of `len` parts.
+    pub fn key_positions(&self, len: usize) -> Vec<usize> {
+        if self.first_key <= 0 {
+            return Vec::new();
+        }
+        let last = match self.last_key {
+            n if n < 0 => len as i64 + n,
+            n => n,
+        };
+        (self.first_key..=last.min(len as i64 - 1))
+            .step_by(self.key_step.max(1) as usize)
+            .map(|i| i as usize)
+            .collect()
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f.eq_ignore_ascii_case(flag))
+    }
+
+    pub fn has_category(&self, category: &str) -> bool {
+        self.acl_categories.iter().any(|c| c.eq_ignore_ascii_case(category))
+    }
+}
+
+fn value_to_string(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::SimpleString(s) => Some(s.clone()),
+        redis::Value::BulkString(b) => Some(String::from_utf8_lossy(b).to_string()),
+        _ => None,
+    }
+}
+
+fn value_to_strings(value: Option<&redis::Value>) -> Vec<String> {
+    match value {
+        Some(redis::Value::Array(items)) | Some(redis::Value::Set(items)) => items.iter().filter_map(value_to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn int(value: Option<&redis::Value>) -> i64 {
+    match value {
+        Some(redis::Value::Int(n)) => *n,
+        _ => 0,
+    }
+}
+
+fn parse_command_info(value: &redis::Value) -> Option<CommandSpec> {
+    let redis::Value::Array(fields) = value else {
+        return None;
+    };
+    // Unknown commands come back as nil rather than an entry
+    value_to_string(fields.first()?)?;
+    Some(CommandSpec {
+        flags: value_to_strings(fields.get(2)),
+        first_key: int(fields.get(3)),
+        last_key: int(fields.get(4)),
+        key_step: int(fields.get(5)),
+        acl_categories: value_to_strings(fields.get(6)),
+    })
+}
+
+/// A `COMMAND INFO` entry and, for Redis 7 container commands, the
+/// `container|subcommand` entries nested in it, keyed by lowercase name.
+fn parse_command_entries(value: &redis::Value) -> Vec<(String, CommandSpec)> {
+    let redis::Value::Array(fields) = value else {
+        return Vec::new();
+    };
+    let Some((name, spec)) = fields.first().and_then(value_to_string).zip(parse_command_info(value)) else {
+        return Vec::new();
+    };
+    let mut entries = vec![(name.to_lowercase(), spec)];
+    if let Some(redis::Value::Array(subcommands)) = fields.get(9) {
+        entries.extend(subcommands.iter().flat_map(parse_command_entries));
+    }
+    entries
+}
+
+/// Per-connection cache of `COMMAND INFO` lookups, keyed by the lowercase
+/// command (or `container|subcommand`) name.
+#[derive(Default, Clone)]
+pub struct CommandCatalog {
+    specs: Arc<Mutex<HashMap<String, Option<CommandSpec>>>>,
+}
+
+impl CommandCatalog {
+    async fn fetch(conn: &mut MultiplexedConnection, name: &str) -> Result<Vec<(String, CommandSpec)>, AppError> {
+        let reply: Vec<redis::Value> = redis::cmd("COMMAND")
+            .arg("INFO")
+            .arg(name)
+            .query_async(conn)
+            .await
+            .map_err(|e| AppError::from(e).context("COMMAND INFO unavailable"))?;
+        Ok(reply.first().map(parse_command_entries).unwrap_or_default())
+    }
+
+    /// Looks up a command line's spec, preferring the subcommand entry
+    /// (`config|set`) that Redis 7 reports for container commands. `None`
+    /// means the server doesn't know the command.
+    pub async fn lookup(&self, conn: &mut MultiplexedConnection, parts: &[&str]) -> Result<Option<CommandSpec>, AppError> {
+        let Some(name) = parts.first().map(|p| p.to_lowercase()) else {
+            return Ok(None);
+        };
+        let cached = self.specs.lock().get(&name).cloned();
+        let spec = match cached {
+            Some(spec) => spec,
+            None => {
+                // Subcommand entries arrive with their container, so only
+                // command names are ever fetched or cached
+                let entries = Self::fetch(conn, &name).await?;
+                let mut specs = self.specs.lock();
+                specs.entry(name.clone()).or_insert(None);
+                for (entry_name, spec) in entries {
+                    specs.insert(entry_name, Some(spec));
+                }
+                specs.get(&name).cloned().flatten()
+            }
+        };
+        let Some(sub) = parts.get(1) else {
+            return Ok(spec);
+        };
+        let full = format!("{}|{}", name, sub.to_lowercase());
+        Ok(self.specs.lock().get(&full).cloned().flatten().or(spec))
+    }
+}
+
+/// Reasons a command line needs explicit confirmation before it runs; empty
+/// when it can run straight away.
+pub fn assess_risk(parts: &[&str], spec: Option<&CommandSpec>, total_keys: u64) -> Vec<String> {
+    let name = parts.first().map(|p| p.to_uppercase()).unwrap_or_default();
+    let Some(spec) = spec.filter(|s| !s.acl_categories.is_empty()) else {
+        if FALLBACK_RISKY_COMMANDS.contains(&name.as_str()) {
+            return vec![format!("{} is a risky command and the server could not classify it", name)];
+        }
+        return Vec::new();
+    };
+
+    let mut reasons = Vec::new();
+    let keyless = spec.first_key == 0;
+    if spec.has_category("@dangerous") && spec.is_write() {
+        if keyless && spec.has_category("@keyspace") {
+            reasons.push(format!("{} changes the whole keyspace ({} keys)", name, total_keys));
+        } else {
+            reasons.push(format!("{} is a dangerous write", name));
+        }
+    }
+    // CONFIG GET, CLIENT LIST and other admin reads run without asking
+    if (spec.has_category("@admin") || spec.has_flag("admin")) && !spec.has_flag("readonly") {
+        reasons.push(format!("{} is an administrative command", name));
+    }
+    // KEYS and friends walk every key and block the server while they do
+    if spec.has_category("@dangerous")
+        && spec.has_category("@slow")
+        && spec.has_category("@keyspace")
+        && !spec.is_write()
+        && keyless
+        && total_keys >= LARGE_KEYSPACE
+    {
+        reasons.push(format!("{} walks all {} keys and blocks the server", name, total_keys));
+    }
+    reasons
+}
+
+/// Matches a command line against a server's deny list. Entries are a
+/// command name (`FLUSHALL`) or a command plus subcommand (`CONFIG SET`).
+pub fn denied_by(parts: &[&str], denied: &[String]) -> Option<String> {
+    denied.iter()
+        .find(|entry| {
+            let words: Vec<&str> = entry.split_whitespace().collect();
+            !words.is_empty()
+                && words.len() <= parts.len()
+                && words.iter().zip(parts).all(|(w, p)| w.eq_ignore_ascii_case(p))
+        })
+        .map(|entry| entry.to_uppercase())
+}
+
+/// The connection-state command a command line starts with, if any.
+pub fn changes_connection_state(parts: &[&str]) -> Option<String> {
+    let entries: Vec<String> = CONNECTION_STATE_COMMANDS.iter().map(|c| c.to_string()).collect();
+    denied_by(parts, &entries)
+}
+
+struct PendingConfirmation {
+    server_id: String,
+    command: String,
+    expires_at: Instant,
+}
+
+/// Single-use tokens handed out for risky commands. A token only unlocks the
+/// exact command line it was issued for, on the same server, for a minute.
+#[derive(Default)]
+pub struct Confirmations {
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+}
+
+impl Confirmations {
+    pub fn issue(&self, server_id: &str, command: &str) -> (String, u64) {
+        let token = uuid::Uuid::new_v4().to_string();
+        let mut pending = self.pending.lock();
+        pending.retain(|_, p| p.expires_at > Instant::now());
+        pending.insert(token.clone(), PendingConfirmation {
+            server_id: server_id.to_string(),
+            command: command.to_string(),
+            expires_at: Instant::now() + CONFIRMATION_TTL,
+        });
+        (token, CONFIRMATION_TTL.as_secs())
+    }
+
+    pub fn redeem(&self, token: &str, server_id: &str, command: &str) -> bool {
+        match self.pending.lock().remove(token) {
+            Some(p) => p.server_id == server_id && p.command == command && p.expires_at > Instant::now(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> redis::Value {
+        redis::Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_command_info_entries() {
+        let reply = redis::Value::Array(vec![
+            bulk("set"),
+            redis::Value::Int(-3),
+            redis::Value::Set(vec![redis::Value::SimpleString("write".into()), redis::Value::SimpleString("denyoom".into())]),
+            redis::Value::Int(1),
+            redis::Value::Int(1),
+            redis::Value::Int(1),
+            redis::Value::Array(vec![bulk("@write"), bulk("@string"), bulk("@slow")]),
+        ]);
+        let spec = parse_command_info(&reply).unwrap();
+        assert!(spec.is_write());
+        assert!(!spec.has_flag("admin"));
+        assert!(spec.has_category("@slow"));
+        assert_eq!(spec.first_key, 1);
+        assert_eq!(spec.key_positions(3), vec![1]);
+
+        let mset = CommandSpec { first_key: 1, last_key: -1, key_step: 2, ..spec };
+        assert_eq!(mset.key_positions(5), vec![1, 3]);
+        assert!(parse_command_info(&redis::Value::Nil).is_none());
+
+        let config = redis::Value::Array(vec![
+            bulk("config"), redis::Value::Int(-2), redis::Value::Array(vec![]), redis::Value::Int(0), redis::Value::Int(0), redis::Value::Int(0),
+            redis::Value::Array(vec![bulk("@slow")]), redis::Value::Array(vec![]), redis::Value::Array(vec![]),
+            redis::Value::Array(vec![redis::Value::Array(vec![
+                bulk("config|get"), redis::Value::Int(-3), redis::Value::Array(vec![bulk("readonly")]), redis::Value::Int(0), redis::Value::Int(0), redis::Value::Int(0),
+                redis::Value::Array(vec![bulk("@admin")]),
+            ])]),
+        ]);
+        let entries = parse_command_entries(&config);
+        assert_eq!(entries.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), ["config", "config|get"]);
+        assert!(entries[1].1.has_flag("readonly"));
+    }
+
+    fn spec(flags: &[&str], first_key: i64, categories: &[&str]) -> CommandSpec {
+        CommandSpec {
+            flags: flags.iter().map(|s| s.to_string()).collect(),
+            first_key,
+            last_key: first_key,
+            key_step: 1,
+            acl_categories: categories.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn classifies_risky_commands() {
+        let flushall = spec(&["write"], 0, &["@keyspace", "@write", "@slow", "@dangerous"]);
+        assert_eq!(assess_risk(&["flushall"], Some(&flushall), 5).len(), 1);
+
+        let keys = spec(&["readonly"], 0, &["@keyspace", "@read", "@slow", "@dangerous"]);
+        assert!(assess_risk(&["keys", "*"], Some(&keys), 100).is_empty());
+        assert_eq!(assess_risk(&["keys", "*"], Some(&keys), 50_000).len(), 1);
+
+        let get = spec(&["readonly", "fast"], 1, &["@read", "@string", "@fast"]);
+        assert!(assess_risk(&["get", "k"], Some(&get), 50_000).is_empty());
+        assert!(get.is_read_only());
+        assert!(!keys.is_read_only());
+        assert!(!spec(&["fast"], 0, &["@connection"]).is_read_only());
+
+        let config_get = spec(&["admin", "noscript", "loading", "stale", "readonly"], 0, &["@admin", "@slow", "@dangerous"]);
+        assert!(assess_risk(&["config", "get", "maxmemory"], Some(&config_get), 0).is_empty());
+        let config_set = spec(&["admin", "noscript", "loading", "stale"], 0, &["@admin", "@slow", "@dangerous"]);
+        assert_eq!(assess_risk(&["config", "set", "maxmemory", "1"], Some(&config_set), 0).len(), 1);
+
+        assert_eq!(assess_risk(&["debug", "sleep", "1"], None, 0).len(), 1);
+        assert_eq!(denied_by(&["config", "set", "x", "y"], &["CONFIG SET".to_string()]).as_deref(), Some("CONFIG SET"));
+        assert!(denied_by(&["config", "get", "x"], &["CONFIG SET".to_string()]).is_none());
+        assert_eq!(changes_connection_state(&["select", "2"]).as_deref(), Some("SELECT"));
+        assert_eq!(changes_connection_state(&["client", "reply", "off"]).as_deref(), Some("CLIENT REPLY"));
+        assert!(changes_connection_state(&["client", "list"]).is_none());
+    }
+
+    #[test]
+    fn confirmation_tokens_are_single_use_and_bound() {
+        let confirmations = Confirmations::default();
+        let (token, _) = confirmations.issue("s1", "FLUSHDB");
+        assert!(!confirmations.redeem(&token, "s1", "FLUSHALL"));
+        let (token, _) = confirmations.issue("s1", "FLUSHDB");
+        assert!(confirmations.redeem(&token, "s1", "FLUSHDB"));
+        assert!(!confirmations.redeem(&token, "s1", "FLUSHDB"));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::types::*;
+use std::collections::{BTreeSet, HashMap};
+
+const MASK: &str = "********";
+
+/// Parameters whose values are secrets; shown masked but still compared.
+const SENSITIVE: &[&str] = &["requirepass", "masterauth", "tls-key-file-pass", "tls-client-key-file-pass"];
+
+/// Parameters that differ between healthy instances of the same deployment,
+/// left out of server comparisons unless asked for.
+pub const INSTANCE_SPECIFIC: &[&str] = &[
+    "port", "tls-port", "bind", "pidfile", "logfile", "dir", "unixsocket", "replicaof", "slaveof",
+    "cluster-config-file", "cluster-announce-ip", "cluster-announce-port", "cluster-announce-bus-port",
+    "replica-announce-ip", "replica-announce-port", "replica-priority", "masterauth", "masteruser",
+];
+
+enum Kind {
+    Bool,
+    Int(i64, i64),
+    Memory,
+    Enum(&'static [&'static str]),
+    Text,
+}
+
+struct ParamSpec {
+    name: &'static str,
+    default: &'static str,
+    kind: Kind,
+    /// False for parameters that can only be set in redis.conf.
+    mutable: bool,
+    description: &'static str,
+}
+
+const fn spec(name: &'static str, default: &'static str, kind: Kind, mutable: bool, description: &'static str) -> ParamSpec {
+    ParamSpec { name, default, kind, mutable, description }
+}
+
+const MAX: i64 = i64::MAX;
+
+/// Defaults as Redis 7.2 reports them from `CONFIG GET` (sizes in bytes).
+/// Older servers differ on a few (`save`, `*-listpack-*`), so a non-default
+/// flag there is a hint rather than a verdict.
+const KNOWN_PARAMS: &[ParamSpec] = &[
+    spec("maxmemory", "0", Kind::Memory, true, "Memory limit for the dataset; 0 means no limit"),
+    spec("maxmemory-policy", "noeviction", Kind::Enum(&["noeviction", "allkeys-lru", "allkeys-lfu", "allkeys-random", "volatile-lru", "volatile-lfu", "volatile-random", "volatile-ttl"]), true, "What to evict when maxmemory is reached"),
+    spec("maxmemory-samples", "5", Kind::Int(1, 64), true, "Keys sampled per eviction; higher is more accurate and slower"),
+    spec("maxmemory-clients", "0", Kind::Text, true, "Memory limit for all client buffers combined, in bytes or percent of maxmemory"),
+    spec("maxclients", "10000", Kind::Int(1, MAX), true, "Maximum number of connected clients"),
+    spec("timeout", "0", Kind::Int(0, MAX), true, "Close clients idle for this many seconds; 0 disables"),
+    spec("tcp-keepalive", "300", Kind::Int(0, MAX), true, "Seconds between TCP keepalive probes; 0 disables"),
+    spec("tcp-backlog", "511", Kind::Int(0, MAX), false, "Listen backlog size"),
+    spec("databases", "16", Kind::Int(1, MAX), false, "Number of logical databases"),
+    spec("port", "6379", Kind::Int(0, 65535), true, "TCP port; 0 disables TCP"),
+    spec("bind", "* -::*", Kind::Text, true, "Interfaces to listen on"),
+    spec("protected-mode", "yes", Kind::Bool, true, "Refuse remote clients when no password or bind address is set"),
+    spec("daemonize", "no", Kind::Bool, false, "Run as a background daemon"),
+    spec("supervised", "no", Kind::Enum(&["no", "upstart", "systemd", "auto"]), false, "Supervision tree integration"),
+    spec("loglevel", "notice", Kind::Enum(&["debug", "verbose", "notice", "warning", "nothing"]), true, "Server log verbosity"),
+    spec("save", "3600 1 300 100 60 10000", Kind::Text, true, "RDB snapshot points as `seconds changes` pairs; empty disables"),
+    spec("stop-writes-on-bgsave-error", "yes", Kind::Bool, true, "Refuse writes while RDB snapshots are failing"),
+    spec("rdbcompression", "yes", Kind::Bool, true, "Compress strings in RDB files with LZF"),
+    spec("rdbchecksum", "yes", Kind::Bool, true, "Append a CRC64 checksum to RDB files"),
+    spec("dbfilename", "dump.rdb", Kind::Text, true, "RDB file name"),
+    spec("appendonly", "no", Kind::Bool, true, "Enable the append-only file"),
+    spec("appendfsync", "everysec", Kind::Enum(&["always", "everysec", "no"]), true, "How often the AOF is fsynced"),
+    spec("no-appendfsync-on-rewrite", "no", Kind::Bool, true, "Skip fsync while a rewrite is running"),
+    spec("auto-aof-rewrite-percentage", "100", Kind::Int(0, MAX), true, "Rewrite the AOF once it grows by this percentage; 0 disables"),
+    spec("auto-aof-rewrite-min-size", "67108864", Kind::Memory, true, "Minimum AOF size before automatic rewrites"),
+    spec("slowlog-log-slower-than", "10000", Kind::Int(-1, MAX), true, "Log commands slower than this many microseconds; -1 disables"),
+    spec("slowlog-max-len", "128", Kind::Int(0, MAX), true, "Slow log entries kept"),
+    spec("latency-monitor-threshold", "0", Kind::Int(0, MAX), true, "Record latency events above this many milliseconds; 0 disables"),
+    spec("latency-tracking", "yes", Kind::Bool, true, "Track per-command latency percentiles"),
+    spec("notify-keyspace-events", "", Kind::Text, true, "Keyspace notification classes; empty disables"),
+    spec("hz", "10", Kind::Int(1, 500), true, "Background task frequency"),
+    spec("dynamic-hz", "yes", Kind::Bool, true, "Scale hz with the number of clients"),
+    spec("active-expire-effort", "1", Kind::Int(1, 10), true, "Effort spent reclaiming expired keys"),
+    spec("activedefrag", "no", Kind::Bool, true, "Defragment memory in the background"),
+    spec("lazyfree-lazy-eviction", "no", Kind::Bool, true, "Free evicted keys in a background thread"),
+    spec("lazyfree-lazy-expire", "no", Kind::Bool, true, "Free expired keys in a background thread"),
+    spec("lazyfree-lazy-server-del", "no", Kind::Bool, true, "Free keys deleted implicitly (RENAME, SET) in a background thread"),
+    spec("lazyfree-lazy-user-del", "no", Kind::Bool, true, "Make DEL behave like UNLINK"),
+    spec("lazyfree-lazy-user-flush", "no", Kind::Bool, true, "Make FLUSHALL/FLUSHDB asynchronous by default"),
+    spec("replica-read-only", "yes", Kind::Bool, true, "Refuse writes on replicas"),
+    spec("replica-serve-stale-data", "yes", Kind::Bool, true, "Answer reads while the link to the primary is down"),
+    spec("repl-backlog-size", "1048576", Kind::Memory, true, "Replication backlog used for partial resyncs"),
+    spec("repl-backlog-ttl", "3600", Kind::Int(0, MAX), true, "Seconds to keep the backlog without replicas; 0 keeps it forever"),
+    spec("repl-timeout", "60", Kind::Int(1, MAX), true, "Replication timeout in seconds"),
+    spec("repl-diskless-sync", "yes", Kind::Bool, true, "Stream RDB to replicas without touching disk"),
+    spec("min-replicas-to-write", "0", Kind::Int(0, MAX), true, "Refuse writes with fewer good replicas than this; 0 disables"),
+    spec("min-replicas-max-lag", "10", Kind::Int(0, MAX), true, "Lag in seconds for a replica to count as good"),
+    spec("client-output-buffer-limit", "normal 0 0 0 slave 268435456 67108864 60 pubsub 33554432 8388608 60", Kind::Text, true, "Output buffer limits per client class"),
+    spec("client-query-buffer-limit", "1073741824", Kind::Memory, true, "Maximum query buffer per client"),
+    spec("proto-max-bulk-len", "536870912", Kind::Memory, true, "Maximum size of a single bulk string"),
+    spec("busy-reply-threshold", "5000", Kind::Int(0, MAX), true, "Milliseconds a script may run before BUSY replies"),
+    spec("hash-max-listpack-entries", "128", Kind::Int(0, MAX), true, "Hashes with more fields use a hash table"),
+    spec("hash-max-listpack-value", "64", Kind::Int(0, MAX), true, "Hashes with longer values use a hash table"),
+    spec("list-max-listpack-size", "-2", Kind::Int(-5, MAX), true, "List node size; negative values are size classes"),
+    spec("set-max-intset-entries", "512", Kind::Int(0, MAX), true, "Integer sets with more members use a hash table"),
+    spec("zset-max-listpack-entries", "128", Kind::Int(0, MAX), true, "Sorted sets with more members use a skiplist"),
+    spec("zset-max-listpack-value", "64", Kind::Int(0, MAX), true, "Sorted sets with longer members use a skiplist"),
+    spec("io-threads", "1", Kind::Int(1, 128), false, "I/O threads, including the main thread"),
+    spec("cluster-enabled", "no", Kind::Bool, false, "Run in cluster mode"),
+    spec("cluster-node-timeout", "15000", Kind::Int(0, MAX), true, "Milliseconds before a node is considered failing"),
+    spec("enable-debug-command", "no", Kind::Enum(&["yes", "no", "local"]), false, "Allow the DEBUG command"),
+    spec("requirepass", "", Kind::Text, true, "Password of the default user"),
+    spec("masterauth", "", Kind::Text, true, "Password used to authenticate with the primary"),
+];
+
+fn known(name: &str) -> Option<&'static ParamSpec> {
+    KNOWN_PARAMS.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+}
+
+pub fn is_sensitive(name: &str) -> bool {
+    SENSITIVE.iter().any(|s| s.eq_ignore_ascii_case(name))
+}
+
+pub fn display_value(name: &str, value: &str) -> String {
+    if is_sensitive(name) && !value.is_empty() { MASK.to_string() } else { value.to_string() }
+}
+
+/// Parses a redis.conf size: `1gb` is 1024^3 bytes, `1g` is 10^9.
+pub fn parse_memory(value: &str) -> Option<u64> {
+    let value = value.trim().to_lowercase();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let multiplier: u64 = match unit {
+        "" | "b" => 1,
+        "k" => 1_000,
+        "kb" => 1 << 10,
+        "m" => 1_000_000,
+        "mb" => 1 << 20,
+        "g" => 1_000_000_000,
+        "gb" => 1 << 30,
+        _ => return None,
+    };
+    digits.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+fn normalize(spec: &ParamSpec, value: &str) -> String {
+    match spec.kind {
+        Kind::Memory => parse_memory(value).map(|n| n.to_string()).unwrap_or_else(|| value.to_string()),
+        _ => value.trim().to_lowercase(),
+    }
+}
+
+/// Whether `value` is the Redis default; `None` for parameters this table
+/// doesn't know.
+pub fn is_default(name: &str, value: &str) -> Option<bool> {
+    let spec = known(name)?;
+    Some(normalize(spec, value) == normalize(spec, spec.default))
+}
+
+/// Checks a value before it's sent to `CONFIG SET`. Unknown parameters are
+/// left to the server.
+pub fn validate(name: &str, value: &str) -> Result<(), String> {
+    let Some(spec) = known(name) else {
+        return Ok(());
+    };
+    if !spec.mutable {
+        return Err(format!("{} can only be changed in redis.conf and needs a restart", spec.name));
+    }
+    match spec.kind {
+        Kind::Bool if !matches!(value.to_lowercase().as_str(), "yes" | "no") => {
+            Err(format!("{} must be yes or no", spec.name))
+        }
+        Kind::Int(min, max) => match value.trim().parse::<i64>() {
+            Ok(n) if n >= min && n <= max => Ok(()),
+            Ok(_) if max == MAX => Err(format!("{} must be at least {}", spec.name, min)),
+            Ok(_) => Err(format!("{} must be between {} and {}", spec.name, min, max)),
+            Err(_) => Err(format!("{} must be an integer", spec.name)),
+        },
+        Kind::Memory if parse_memory(value).is_none() => {
+            Err(format!("{} must be a size such as 100mb or 1gb", spec.name))
+        }
+        Kind::Enum(allowed) if !allowed.iter().any(|a| a.eq_ignore_ascii_case(value)) => {
+            Err(format!("{} must be one of: {}", spec.name, allowed.join(", ")))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// `CONFIG GET *` with defaults, descriptions and types, sorted by name.
+pub fn annotate(config: &HashMap<String, String>) -> Vec<ConfigParameter> {
+    let mut params: Vec<ConfigParameter> = config.iter()
+        .map(|(name, value)| {
+            let spec = known(name);
+            ConfigParameter {
+                name: name.clone(),
+                value: display_value(name, value),
+                default_value: spec.map(|s| s.default.to_string()),
+                description: spec.map(|s| s.description.to_string()),
+                value_type: match spec.map(|s| &s.kind) {
+                    Some(Kind::Bool) => "bool",
+                    Some(Kind::Int(..)) => "integer",
+                    Some(Kind::Memory) => "memory",
+                    Some(Kind::Enum(_)) => "enum",
+                    _ => "string",
+                }
+                .to_string(),
+                allowed_values: match spec.map(|s| &s.kind) {
+                    Some(Kind::Enum(allowed)) => allowed.iter().map(|a| a.to_string()).collect(),
+                    _ => Vec::new(),
+                },
+                is_default: is_default(name, value),
+                mutable: spec.map_or(true, |s| s.mutable),
+                sensitive: is_sensitive(name),
+            }
+        })
+        .collect();
+    params.sort_by(|a, b| a.name.cmp(&b.name));
+    params
+}
+
+/// Differences between two servers' configs, skipping `ignored` parameters.
+pub fn compare(left: &HashMap<String, String>, right: &HashMap<String, String>, ignored: &[String]) -> (Vec<ConfigDifference>, u64) {
+    let params: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    let mut differences = Vec::new();
+    let mut matching = 0;
+    for param in params.into_iter().filter(|p| !ignored.iter().any(|i| i.eq_ignore_ascii_case(p))) {
+        let (l, r) = (left.get(param), right.get(param));
+        let same = match (l, r, known(param)) {
+            (Some(l), Some(r), Some(spec)) => normalize(spec, l) == normalize(spec, r),
+            _ => l == r,
+        };
+        if same {
+            matching += 1;
+            continue;
+        }
+        differences.push(ConfigDifference {
+            parameter: param.clone(),
+            left: l.map(|v| display_value(param, v)),
+            right: r.map(|v| display_value(param, v)),
+            left_is_default: l.and_then(|v| is_default(param, v)),
+            right_is_default: r.and_then(|v| is_default(param, v)),
+        });
+    }
+    (differences, matching)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotates_and_validates_parameters() {
+        let config: HashMap<String, String> = [
+            ("maxmemory", "1073741824"),
+            ("maxmemory-policy", "noeviction"),
+            ("requirepass", "hunter2"),
+            ("some-module-option", "x"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let params = annotate(&config);
+        let get = |name: &str| params.iter().find(|p| p.name == name).unwrap();
+        assert_eq!(get("maxmemory").is_default, Some(false));
+        assert_eq!(get("maxmemory-policy").is_default, Some(true));
+        assert_eq!(get("requirepass").value, MASK);
+        assert_eq!(get("some-module-option").is_default, None);
+
+        assert_eq!(parse_memory("1gb"), Some(1 << 30));
+        assert!(validate("maxmemory", "2gb").is_ok());
+        assert!(validate("maxmemory", "lots").is_err());
+        assert!(validate("hz", "1000").is_err());
+        assert!(validate("appendonly", "maybe").is_err());
+        assert!(validate("databases", "32").is_err());
+        assert!(validate("some-module-option", "anything").is_ok());
+    }
+
+    #[test]
+    fn compares_configs_by_normalized_value() {
+        let left: HashMap<String, String> = [("maxmemory", "1073741824"), ("port", "6379"), ("appendonly", "yes")]
+            .iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let right: HashMap<String, String> = [("maxmemory", "1gb"), ("port", "6380"), ("appendonly", "no")]
+            .iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let (differences, matching) = compare(&left, &right, &["port".to_string()]);
+        assert_eq!(matching, 1);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].parameter, "appendonly");
+        assert_eq!(differences[0].right_is_default, Some(true));
+    }
+}
@@ -3,6 +3,7 @@ use aes_gcm::{
     Aes256Gcm, Nonce,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use hmac::{Hmac, Mac};
 use rand::Rng;
 use sha2::{Digest, Sha256};
 use std::fs;
@@ -67,11 +68,38 @@ fn derive_key(master_key: &[u8; 32]) -> [u8; 32] {
     key
 }
 
+/// Key for signing the audit log, kept next to (not in) the log.
+pub fn audit_key() -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(get_or_create_key());
+    hasher.update(b"redis-tics-audit-v1");
+    hasher.finalize().into()
+}
+
+/// HMAC-SHA256 (RFC 2104).
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    // HMAC accepts keys of any length, so this can't fail
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC key");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
 pub fn encrypt_password(password: &str) -> Result<String, String> {
     if password.is_empty() {
         return Ok(String::new());
     }
+    encrypt_bytes(password.as_bytes())
+}
 
+pub fn decrypt_password(encrypted: &str) -> Result<String, String> {
+    if encrypted.is_empty() {
+        return Ok(String::new());
+    }
+    String::from_utf8(decrypt_bytes(encrypted)?).map_err(|e| e.to_string())
+}
+
+/// Encrypts local data with the app key; returns base64 of nonce + ciphertext.
+pub fn encrypt_bytes(data: &[u8]) -> Result<String, String> {
     let master_key = get_or_create_key();
     let key = derive_key(&master_key);
     let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())?;
@@ -81,7 +109,7 @@ pub fn encrypt_password(password: &str) -> Result<String, String> {
     let nonce = Nonce::from_slice(&nonce_bytes);
 
     let ciphertext = cipher
-        .encrypt(nonce, password.as_bytes())
+        .encrypt(nonce, data)
         .map_err(|e| e.to_string())?;
 
     let mut combined = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
@@ -91,11 +119,7 @@ pub fn encrypt_password(password: &str) -> Result<String, String> {
     Ok(BASE64.encode(&combined))
 }
 
-pub fn decrypt_password(encrypted: &str) -> Result<String, String> {
-    if encrypted.is_empty() {
-        return Ok(String::new());
-    }
-
+pub fn decrypt_bytes(encrypted: &str) -> Result<Vec<u8>, String> {
     let combined = BASE64.decode(encrypted).map_err(|e| e.to_string())?;
     
     if combined.len() < NONCE_SIZE {
@@ -109,11 +133,9 @@ pub fn decrypt_password(encrypted: &str) -> Result<String, String> {
     let nonce = Nonce::from_slice(&combined[..NONCE_SIZE]);
     let ciphertext = &combined[NONCE_SIZE..];
 
-    let plaintext = cipher
+    cipher
         .decrypt(nonce, ciphertext)
-        .map_err(|_| "Decryption failed - invalid key or corrupted data")?;
-
-    String::from_utf8(plaintext).map_err(|e| e.to_string())
+        .map_err(|_| "Decryption failed - invalid key or corrupted data".to_string())
 }
 
 #[cfg(test)]
@@ -128,6 +150,14 @@ mod tests {
         assert_eq!(password, decrypted);
     }
 
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = mac.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(hex, "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+
     #[test]
     fn test_empty_password() {
         let encrypted = encrypt_password("").unwrap();
@@ -0,0 +1,204 @@
+use crate::bigkeys::size_command;
+use crate::error::AppError;
+use crate::jobs::{throttle, JobHandle};
+use crate::types::*;
+use redis::aio::MultiplexedConnection;
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_CHUNK_SIZE: u32 = 1000;
+const DEFAULT_PAUSE_MS: u64 = 10;
+const PROGRESS_INTERVAL_MS: u128 = 500;
+
+/// How `delete_chunk` trims one chunk off each collection type.
+#[derive(Debug, PartialEq)]
+enum ChunkPlan {
+    /// Cursor over members with `scan`, then remove them with `remove`.
+    /// `pairs` when the scan returns field/value pairs.
+    Scan { scan: &'static str, remove: &'static str, pairs: bool },
+    /// ZREMRANGEBYRANK from the lowest rank.
+    ByRank,
+    /// LTRIM the head off.
+    ListHead,
+    /// XTRIM MAXLEN down by one chunk.
+    StreamHead,
+}
+
+/// `None` for types that aren't trimmed and go straight to the final delete.
+fn chunk_plan(key_type: &str) -> Option<ChunkPlan> {
+    match key_type {
+        "hash" => Some(ChunkPlan::Scan { scan: "HSCAN", remove: "HDEL", pairs: true }),
+        "set" => Some(ChunkPlan::Scan { scan: "SSCAN", remove: "SREM", pairs: false }),
+        "zset" => Some(ChunkPlan::ByRank),
+        "list" => Some(ChunkPlan::ListHead),
+        "stream" => Some(ChunkPlan::StreamHead),
+        _ => None,
+    }
+}
+
+/// UNLINK frees memory in a background thread (Redis 4.0+); servers that
+/// don't expose it fall back to DEL.
+pub async fn supports_unlink(conn: &mut MultiplexedConnection) -> bool {
+    let info: Result<Vec<redis::Value>, _> = redis::cmd("COMMAND")
+        .arg("INFO")
+        .arg("UNLINK")
+        .query_async(conn)
+        .await;
+    matches!(info.as_deref(), Ok([first, ..]) if *first != redis::Value::Nil)
+}
+
+pub fn delete_command(supports_unlink: bool) -> &'static str {
+    if supports_unlink { "UNLINK" } else { "DEL" }
+}
+
+async fn remaining(conn: &mut MultiplexedConnection, key: &str, key_type: &str) -> Result<u64, AppError> {
+    if chunk_plan(key_type).is_none() {
+        return Ok(0);
+    }
+    match size_command(key_type) {
+        Some(cmd) => redis::cmd(cmd).arg(key).query_async(conn).await.map_err(AppError::from),
+        None => Ok(0),
+    }
+}
+
+/// Removes one chunk of members and returns how many were removed.
+async fn delete_chunk(
+    conn: &mut MultiplexedConnection,
+    key: &str,
+    key_type: &str,
+    chunk_size: u32,
+    cursor: &mut String,
+    left: u64,
+) -> Result<u64, AppError> {
+    let Some(plan) = chunk_plan(key_type) else {
+        return Ok(0);
+    };
+    match plan {
+        ChunkPlan::Scan { scan, remove, pairs } => {
+            let (next, items): (String, Vec<Vec<u8>>) = redis::cmd(scan)
+                .arg(key)
+                .arg(&*cursor)
+                .arg("COUNT").arg(chunk_size)
+                .query_async(conn)
+                .await?;
+            *cursor = next;
+
+            let members: Vec<&Vec<u8>> = if pairs { items.iter().step_by(2).collect() } else { items.iter().collect() };
+            if members.is_empty() {
+                return Ok(0);
+            }
+            redis::cmd(remove).arg(key).arg(members).query_async(conn).await.map_err(AppError::from)
+        }
+        ChunkPlan::ByRank => redis::cmd("ZREMRANGEBYRANK")
+            .arg(key)
+            .arg(0)
+            .arg(chunk_size as i64 - 1)
+            .query_async(conn)
+            .await
+            .map_err(AppError::from),
+        ChunkPlan::ListHead => {
+            let _: () = redis::cmd("LTRIM")
+                .arg(key)
+                .arg(chunk_size)
+                .arg(-1)
+                .query_async(conn)
+                .await?;
+            Ok(left.min(chunk_size as u64))
+        }
+        ChunkPlan::StreamHead => redis::cmd("XTRIM")
+            .arg(key)
+            .arg("MAXLEN")
+            .arg(left.saturating_sub(chunk_size as u64))
+            .query_async(conn)
+            .await
+            .map_err(AppError::from),
+    }
+}
+
+/// Empties a large collection a chunk at a time with a pause in between, so
+/// freeing millions of members never shows up as a single latency spike.
+/// Keys of other types are removed in one go.
+pub async fn run_incremental_delete(
+    mut conn: MultiplexedConnection,
+    job: &JobHandle,
+    server_id: &str,
+    key: &str,
+    options: &IncrementalDeleteOptions,
+    delete_cmd: &str,
+    app: &AppHandle,
+) -> IncrementalDeleteProgress {
+    let chunk_size = options.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).clamp(1, 100_000);
+    let pause_ms = options.pause_ms.unwrap_or(DEFAULT_PAUSE_MS);
+
+    let mut progress = IncrementalDeleteProgress {
+        job_id: job.id.clone(),
+        server_id: server_id.to_string(),
+        key: key.to_string(),
+        key_type: String::new(),
+        removed: 0,
+        remaining: 0,
+        status: "running".to_string(),
+        error: None,
+    };
+
+    let result: Result<(), AppError> = async {
+        progress.key_type = redis::cmd("TYPE").arg(key).query_async(&mut conn).await?;
+        progress.remaining = remaining(&mut conn, key, &progress.key_type).await?;
+
+        let mut cursor = "0".to_string();
+        let mut last_progress = std::time::Instant::now();
+        while progress.remaining > 0 {
+            if job.is_cancelled() {
+                progress.status = "cancelled".to_string();
+                return Ok(());
+            }
+
+            progress.removed += delete_chunk(&mut conn, key, &progress.key_type, chunk_size, &mut cursor, progress.remaining).await?;
+            progress.remaining = remaining(&mut conn, key, &progress.key_type).await?;
+
+            if last_progress.elapsed().as_millis() >= PROGRESS_INTERVAL_MS {
+                last_progress = std::time::Instant::now();
+                app.emit("incremental-delete-progress", &progress).ok();
+            }
+            throttle(pause_ms).await;
+        }
+
+        // Drops whatever is left: non-collection values, or an emptied stream
+        let _: i64 = redis::cmd(delete_cmd).arg(key).query_async(&mut conn).await?;
+        progress.status = "completed".to_string();
+        Ok(())
+    }.await;
+
+    if let Err(e) = result {
+        progress.status = "failed".to_string();
+        progress.error = Some(e.to_string());
+    }
+    app.emit("incremental-delete-progress", &progress).ok();
+    progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_delete_command() {
+        assert_eq!(delete_command(true), "UNLINK");
+        assert_eq!(delete_command(false), "DEL");
+    }
+
+    #[test]
+    fn plans_a_chunk_per_collection_type() {
+        assert_eq!(chunk_plan("hash"), Some(ChunkPlan::Scan { scan: "HSCAN", remove: "HDEL", pairs: true }));
+        assert_eq!(chunk_plan("set"), Some(ChunkPlan::Scan { scan: "SSCAN", remove: "SREM", pairs: false }));
+        assert_eq!(chunk_plan("zset"), Some(ChunkPlan::ByRank));
+        assert_eq!(chunk_plan("list"), Some(ChunkPlan::ListHead));
+        assert_eq!(chunk_plan("stream"), Some(ChunkPlan::StreamHead));
+        for other in ["string", "none", "ReJSON-RL"] {
+            assert_eq!(chunk_plan(other), None);
+        }
+        // `remaining` needs a length for every type that's trimmed
+        for key_type in ["hash", "set", "zset", "list", "stream"] {
+            assert!(size_command(key_type).is_some());
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorKind {
+    NotConnected,
+    ConnectionLost,
+    Timeout,
+    Io,
+    Auth,
+    NoPermission,
+    /// MOVED / ASK: the key lives on another cluster node.
+    Redirect,
+    ClusterDown,
+    CrossSlot,
+    Busy,
+    ReadOnly,
+    WrongType,
+    ResponseError,
+    Client,
+    /// Refused by the server's deny list or a read-only profile.
+    Denied,
+    InvalidInput,
+    Other,
+}
+
+/// Error returned by every Tauri command, so the UI can tell a timeout from
+/// an auth failure or a MOVED redirect without parsing messages.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Redis error code such as `NOPERM`, `WRONGTYPE` or `MOVED`.
+    pub code: Option<String>,
+    pub server_id: Option<String>,
+    /// Worth retrying as is, e.g. after a reconnect or once loading finishes.
+    pub retryable: bool,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        let retryable = matches!(
+            kind,
+            ErrorKind::ConnectionLost | ErrorKind::Timeout | ErrorKind::Io | ErrorKind::Busy | ErrorKind::ClusterDown
+        );
+        Self { kind, message: message.into(), code: None, server_id: None, retryable }
+    }
+
+    pub fn not_connected(server_id: &str) -> Self {
+        Self::new(ErrorKind::NotConnected, "Server not connected").on_server(server_id)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidInput, message)
+    }
+
+    /// Prefixes the message with what was being attempted, keeping the kind.
+    pub fn context(mut self, context: &str) -> Self {
+        self.message = format!("{}: {}", context, self.message);
+        self
+    }
+
+    /// Tags the error with the server it came from, keeping an existing tag.
+    pub fn on_server(mut self, server_id: &str) -> Self {
+        if self.server_id.is_none() {
+            self.server_id = Some(server_id.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<redis::RedisError> for AppError {
+    fn from(e: redis::RedisError) -> Self {
+        use redis::ErrorKind as R;
+
+        let code = e.code().map(|c| c.to_string());
+        let kind = if e.is_timeout() {
+            ErrorKind::Timeout
+        } else if e.is_connection_dropped() || e.is_connection_refusal() {
+            ErrorKind::ConnectionLost
+        } else {
+            match (e.kind(), code.as_deref()) {
+                (_, Some("NOPERM")) => ErrorKind::NoPermission,
+                (_, Some("WRONGPASS" | "NOAUTH")) | (R::AuthenticationFailed, _) => ErrorKind::Auth,
+                (R::Moved | R::Ask, _) => ErrorKind::Redirect,
+                (R::ClusterDown | R::MasterDown | R::TryAgain, _) => ErrorKind::ClusterDown,
+                (R::CrossSlot, _) => ErrorKind::CrossSlot,
+                (R::BusyLoadingError, _) | (_, Some("BUSY")) => ErrorKind::Busy,
+                (R::ReadOnly, _) => ErrorKind::ReadOnly,
+                (_, Some("WRONGTYPE")) => ErrorKind::WrongType,
+                (R::IoError, _) => ErrorKind::Io,
+                (R::ClientError | R::InvalidClientConfig | R::TypeError | R::ParseError, _) => ErrorKind::Client,
+                _ => ErrorKind::ResponseError,
+            }
+        };
+        Self { code, ..Self::new(kind, e.to_string()) }
+    }
+}
+
+/// Messages from the helper modules, which still report plain strings.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        Self::new(ErrorKind::Io, e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::new(ErrorKind::Other, e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_redis_error_codes() {
+        let noperm = redis::parse_redis_value(b"-NOPERM no permissions\r\n").unwrap().extract_error().unwrap_err();
+        let err = AppError::from(noperm);
+        assert_eq!(err.kind, ErrorKind::NoPermission);
+        assert_eq!(err.code.as_deref(), Some("NOPERM"));
+        assert!(!err.retryable);
+
+        let timeout = redis::RedisError::from(std::io::Error::from(std::io::ErrorKind::TimedOut));
+        let err = AppError::from(timeout).on_server("s1");
+        assert_eq!(err.kind, ErrorKind::Timeout);
+        assert!(err.retryable);
+        assert_eq!(err.server_id.as_deref(), Some("s1"));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::types::{ConnectionHealth, ConnectionState};
+use parking_lot::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+pub const PING_INTERVAL: Duration = Duration::from_secs(5);
+pub const PING_TIMEOUT: Duration = Duration::from_secs(3);
+/// Consecutive failed pings before a connection is treated as lost.
+pub const LOST_AFTER_FAILURES: u32 = 2;
+const DEGRADED_LATENCY_MS: u64 = 250;
+const BACKOFF_BASE_MS: u64 = 500;
+const BACKOFF_MAX_MS: u64 = 30_000;
+
+/// Delay before reconnect attempt `attempt` (0-based): doubles from 500ms up
+/// to 30s.
+pub fn backoff_delay(attempt: u32) -> Duration {
+    let ms = BACKOFF_BASE_MS.saturating_mul(1u64 << attempt.min(16));
+    Duration::from_millis(ms.min(BACKOFF_MAX_MS))
+}
+
+pub fn ping_state(latency_ms: u64) -> ConnectionState {
+    if latency_ms > DEGRADED_LATENCY_MS {
+        ConnectionState::Degraded
+    } else {
+        ConnectionState::Ready
+    }
+}
+
+/// Current health of one connection. Every state change is emitted to the UI
+/// as a `connection-state` event.
+pub struct HealthTracker {
+    health: Mutex<ConnectionHealth>,
+    app: AppHandle,
+}
+
+impl HealthTracker {
+    pub fn new(server_id: &str, app: AppHandle) -> Self {
+        let health = ConnectionHealth {
+            server_id: server_id.to_string(),
+            state: ConnectionState::Connecting,
+            latency_ms: None,
+            last_error: None,
+            reconnect_attempt: 0,
+            changed_at: chrono::Utc::now().timestamp_millis() as u64,
+        };
+        Self { health: Mutex::new(health), app }
+    }
+
+    pub fn snapshot(&self) -> ConnectionHealth {
+        self.health.lock().clone()
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.health.lock().state
+    }
+
+    pub fn update(&self, state: ConnectionState, latency_ms: Option<u64>, error: Option<String>, reconnect_attempt: u32) {
+        let changed = {
+            let mut health = self.health.lock();
+            health.latency_ms = latency_ms;
+            health.reconnect_attempt = reconnect_attempt;
+            if error.is_some() {
+                health.last_error = error;
+            }
+            if health.state == state {
+                None
+            } else {
+                health.state = state;
+                health.changed_at = chrono::Utc::now().timestamp_millis() as u64;
+                Some(health.clone())
+            }
+        };
+        if let Some(health) = changed {
+            self.app.emit("connection-state", health).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff_delay(0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(3), Duration::from_millis(4000));
+        assert_eq!(backoff_delay(10), Duration::from_millis(BACKOFF_MAX_MS));
+        assert_eq!(backoff_delay(u32::MAX), Duration::from_millis(BACKOFF_MAX_MS));
+        assert_eq!(ping_state(5), ConnectionState::Ready);
+        assert_eq!(ping_state(800), ConnectionState::Degraded);
+    }
+}
@@ -0,0 +1,154 @@
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Finished job results kept per server; older ones are dropped.
+const MAX_RESULTS_PER_SERVER: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    pub id: String,
+    pub server_id: String,
+    pub kind: String,
+    pub started_at: u64,
+    pub cancelled: bool,
+}
+
+struct JobEntry {
+    info: JobInfo,
+    cancel: Arc<AtomicBool>,
+}
+
+/// Tracks long-running background jobs (analysis, scans, bulk operations) so
+/// they can be listed and cancelled from the UI.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, JobEntry>>,
+}
+
+impl JobRegistry {
+    pub fn start(self: &Arc<Self>, server_id: &str, kind: &str) -> JobHandle {
+        let id = uuid::Uuid::new_v4().to_string();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let info = JobInfo {
+            id: id.clone(),
+            server_id: server_id.to_string(),
+            kind: kind.to_string(),
+            started_at: chrono::Utc::now().timestamp_millis() as u64,
+            cancelled: false,
+        };
+        self.jobs.lock().insert(id.clone(), JobEntry { info, cancel: cancel.clone() });
+
+        JobHandle { id, cancel, registry: self.clone() }
+    }
+
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.jobs.lock().get_mut(job_id) {
+            Some(entry) => {
+                entry.cancel.store(true, Ordering::SeqCst);
+                entry.info.cancelled = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancels every job running against a server, e.g. when it disconnects.
+    pub fn cancel_server(&self, server_id: &str) {
+        for entry in self.jobs.lock().values_mut().filter(|e| e.info.server_id == server_id) {
+            entry.cancel.store(true, Ordering::SeqCst);
+            entry.info.cancelled = true;
+        }
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        let mut jobs: Vec<JobInfo> = self.jobs.lock().values().map(|e| e.info.clone()).collect();
+        jobs.sort_by_key(|j| j.started_at);
+        jobs
+    }
+}
+
+/// Owned by the running task; the job is unregistered when it is dropped.
+pub struct JobHandle {
+    pub id: String,
+    cancel: Arc<AtomicBool>,
+    registry: Arc<JobRegistry>,
+}
+
+impl JobHandle {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        self.registry.jobs.lock().remove(&self.id);
+    }
+}
+
+/// Results of finished jobs by job id, keeping only the most recent few per
+/// server so reports don't accumulate for the life of the app.
+pub struct JobResults<T> {
+    order: VecDeque<(String, String)>,
+    results: HashMap<String, T>,
+}
+
+impl<T> Default for JobResults<T> {
+    fn default() -> Self {
+        Self { order: VecDeque::new(), results: HashMap::new() }
+    }
+}
+
+impl<T> JobResults<T> {
+    pub fn insert(&mut self, server_id: &str, job_id: &str, result: T) {
+        self.order.push_back((server_id.to_string(), job_id.to_string()));
+        self.results.insert(job_id.to_string(), result);
+        if self.order.iter().filter(|(s, _)| s == server_id).count() > MAX_RESULTS_PER_SERVER {
+            if let Some(oldest) = self.order.iter().position(|(s, _)| s == server_id) {
+                if let Some((_, job_id)) = self.order.remove(oldest) {
+                    self.results.remove(&job_id);
+                }
+            }
+        }
+    }
+
+    pub fn get(&self, job_id: &str) -> Option<&T> {
+        self.results.get(job_id)
+    }
+
+    pub fn for_server<'a>(&'a self, server_id: &'a str) -> impl Iterator<Item = &'a T> + 'a {
+        self.order.iter()
+            .filter(move |(s, _)| s == server_id)
+            .filter_map(|(_, job_id)| self.results.get(job_id))
+    }
+}
+
+/// Sleeps between batches so background jobs leave headroom for real traffic.
+pub async fn throttle(pause_ms: u64) {
+    if pause_ms > 0 {
+        tokio::time::sleep(tokio::time::Duration::from_millis(pause_ms)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_recent_results_per_server() {
+        let mut results = JobResults::default();
+        for i in 0..MAX_RESULTS_PER_SERVER + 5 {
+            results.insert("s1", &format!("a{}", i), i);
+        }
+        results.insert("s2", "b0", 0);
+
+        assert!(results.get("a0").is_none());
+        assert_eq!(results.get("a5"), Some(&5));
+        assert_eq!(results.for_server("s1").count(), MAX_RESULTS_PER_SERVER);
+        assert_eq!(results.for_server("s2").count(), 1);
+    }
+}
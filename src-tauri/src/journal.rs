@@ -0,0 +1,401 @@
+use crate::crypto::{decrypt_bytes, encrypt_bytes};
+use crate::error::AppError;
+use crate::types::{UndoEntry, UndoKeyState};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use parking_lot::Mutex;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+
+const MAX_ENTRIES: usize = 200;
+const MAX_TOTAL_BYTES: u64 = 32 * 1024 * 1024;
+/// Values above this are noted in the journal but not kept.
+const MAX_DUMP_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize)]
+struct JournalRecord {
+    entry: UndoEntry,
+    /// DUMP payloads, parallel to `entry.keys`: encrypted with the app key,
+    /// or plain base64 in journals written before encryption.
+    dumps: Vec<Option<String>>,
+    #[serde(default)]
+    encrypted: bool,
+}
+
+/// One line of the journal file. Captures and restores are appended; the
+/// file is compacted once evicted records make up most of it.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum JournalLine {
+    Record(JournalRecord),
+    Restored { id: String, at: u64 },
+}
+
+/// Writing side of `JournalLine`, so records needn't be cloned to be saved.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum JournalLineRef<'a> {
+    Record(&'a JournalRecord),
+    Restored { id: &'a str, at: u64 },
+}
+
+struct JournalState {
+    records: VecDeque<JournalRecord>,
+    /// Lines currently in the file.
+    lines: usize,
+}
+
+/// Bounded, locally persisted journal of key versions captured right before
+/// the app changes them, so edits can be rolled back with RESTORE. The file
+/// holds raw values, so the DUMP payloads are encrypted at rest.
+pub struct UndoJournal {
+    path: PathBuf,
+    state: Mutex<JournalState>,
+}
+
+fn evict(records: &mut VecDeque<JournalRecord>) {
+    let mut total: u64 = records.iter().flat_map(|r| r.entry.keys.iter()).map(|k| k.size_bytes).sum();
+    while records.len() > MAX_ENTRIES || (total > MAX_TOTAL_BYTES && records.len() > 1) {
+        if let Some(evicted) = records.pop_front() {
+            total -= evicted.entry.keys.iter().map(|k| k.size_bytes).sum::<u64>();
+        }
+    }
+}
+
+/// What a capture knows about one key from its DUMP and PTTL replies, plus
+/// the payload to keep when it isn't too large.
+fn key_state<'a>(key: &str, dump: Option<&'a redis::Value>, pttl: Option<i64>, now_ms: i64) -> (UndoKeyState, Option<&'a [u8]>) {
+    // existed: None when it can't be told
+    let (existed, payload) = match dump {
+        Some(redis::Value::BulkString(bytes)) => (Some(true), Some(bytes.as_slice())),
+        Some(redis::Value::Nil) => (Some(false), None),
+        // DUMP unavailable; fall back to PTTL to tell whether the key existed
+        _ => (pttl.map(|ttl| ttl != -2), None),
+    };
+    let kept = payload.filter(|p| p.len() <= MAX_DUMP_BYTES);
+    let state = UndoKeyState {
+        key: key.to_string(),
+        existed: existed.unwrap_or(false),
+        expire_at_ms: pttl.filter(|ttl| *ttl > 0).map(|ttl| now_ms + ttl),
+        size_bytes: payload.map(|p| p.len() as u64).unwrap_or(0),
+        restorable: match existed {
+            Some(true) => kept.is_some(),
+            Some(false) => true,
+            None => false,
+        },
+    };
+    (state, kept)
+}
+
+impl UndoJournal {
+    pub fn new(path: PathBuf) -> Self {
+        let mut records = VecDeque::new();
+        let mut lines = 0;
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            for line in content.lines() {
+                lines += 1;
+                match serde_json::from_str(line) {
+                    Ok(JournalLine::Record(record)) => records.push_back(record),
+                    Ok(JournalLine::Restored { id, at }) => {
+                        if let Some(record) = records.iter_mut().find(|r| r.entry.id == id) {
+                            record.entry.restored_at = Some(at);
+                        }
+                    }
+                    Err(_) => {}
+                }
+                evict(&mut records);
+            }
+        }
+        let journal = Self { path, state: Mutex::new(JournalState { records, lines }) };
+
+        // Journals used to be one JSON array in undo_journal.json
+        let legacy = journal.path.with_extension("json");
+        if lines == 0 && legacy != journal.path {
+            let old: Option<VecDeque<JournalRecord>> = std::fs::read_to_string(&legacy)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok());
+            if let Some(old) = old {
+                let mut state = journal.state.lock();
+                state.records = old;
+                evict(&mut state.records);
+                if journal.compact(&mut state).is_ok() {
+                    std::fs::remove_file(&legacy).ok();
+                }
+            }
+        }
+        journal
+    }
+
+    fn compact(&self, state: &mut JournalState) -> Result<(), AppError> {
+        let mut content = String::new();
+        for record in &state.records {
+            let line = serde_json::to_string(&JournalLineRef::Record(record))?;
+            content.push_str(&line);
+            content.push('\n');
+        }
+        std::fs::write(&self.path, content)?;
+        state.lines = state.records.len();
+        Ok(())
+    }
+
+    // Call after updating `state.records`, since it may rewrite the file from them
+    fn append(&self, state: &mut JournalState, line: String) -> Result<(), AppError> {
+        if state.lines >= state.records.len() + MAX_ENTRIES {
+            return self.compact(state);
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        state.lines += 1;
+        Ok(())
+    }
+
+    /// Captures DUMP and PTTL of `keys` and appends them as one entry.
+    /// Capture failures never block the edit itself; the entry is simply
+    /// marked as not restorable.
+    pub async fn capture(
+        &self,
+        conn: &mut MultiplexedConnection,
+        server_id: &str,
+        db: Option<u8>,
+        operation: &str,
+        keys: &[&str],
+    ) -> Result<(), AppError> {
+        let mut pipe = redis::pipe();
+        for key in keys {
+            pipe.cmd("DUMP").arg(*key);
+            pipe.cmd("PTTL").arg(*key);
+        }
+        // When the pipeline fails nothing is known about the keys, and an
+        // entry that guessed "did not exist" would DEL live data on restore
+        let results: Option<Vec<redis::Value>> = pipe.query_async(conn).await.ok();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+
+        let mut states = Vec::new();
+        let mut dumps = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            let dump = results.as_ref().and_then(|r| r.get(i * 2));
+            let pttl = match results.as_ref().and_then(|r| r.get(i * 2 + 1)) {
+                Some(redis::Value::Int(n)) => Some(*n),
+                _ => None,
+            };
+            let (state, kept) = key_state(key, dump, pttl, now_ms);
+            dumps.push(kept.map(encrypt_bytes).transpose()?);
+            states.push(state);
+        }
+
+        let record = JournalRecord {
+            entry: UndoEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                server_id: server_id.to_string(),
+                db,
+                operation: operation.to_string(),
+                created_at: now_ms as u64,
+                keys: states,
+                restored_at: None,
+            },
+            dumps,
+            encrypted: true,
+        };
+
+        let line = serde_json::to_string(&JournalLineRef::Record(&record))?;
+        let mut state = self.state.lock();
+        state.records.push_back(record);
+        evict(&mut state.records);
+        self.append(&mut state, line)
+    }
+
+    /// Newest first.
+    pub fn list(&self, server_id: Option<&str>, limit: usize) -> Vec<UndoEntry> {
+        self.state.lock().records.iter().rev()
+            .filter(|r| server_id.map_or(true, |id| r.entry.server_id == id))
+            .take(limit)
+            .map(|r| r.entry.clone())
+            .collect()
+    }
+
+    pub fn get(&self, entry_id: &str) -> Option<UndoEntry> {
+        self.state.lock().records.iter().find(|r| r.entry.id == entry_id).map(|r| r.entry.clone())
+    }
+
+    /// Puts every key of an entry back the way it was: RESTORE ... REPLACE for
+    /// keys that existed, DEL for keys the change created. Returns the keys
+    /// that were restored.
+    pub async fn restore(&self, conn: &mut MultiplexedConnection, entry_id: &str) -> Result<Vec<String>, AppError> {
+        let (entry, dumps, encrypted) = {
+            let state = self.state.lock();
+            let record = state.records.iter().find(|r| r.entry.id == entry_id).ok_or("Undo entry not found")?;
+            (record.entry.clone(), record.dumps.clone(), record.encrypted)
+        };
+        if let Some(state) = entry.keys.iter().find(|k| !k.restorable) {
+            return Err(format!("{} was not captured and cannot be restored", state.key).into());
+        }
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut restored = Vec::new();
+        for (state, dump) in entry.keys.iter().zip(dumps) {
+            match dump {
+                Some(payload) if state.existed => {
+                    let ttl = match state.expire_at_ms {
+                        Some(at) if at <= now_ms => {
+                            // That version would have expired by now anyway
+                            let _: i64 = redis::cmd("DEL").arg(&state.key).query_async(conn).await?;
+                            continue;
+                        }
+                        Some(at) => at - now_ms,
+                        None => 0,
+                    };
+                    let bytes = if encrypted {
+                        decrypt_bytes(&payload)?
+                    } else {
+                        STANDARD.decode(payload).map_err(|e| e.to_string())?
+                    };
+                    let _: () = redis::cmd("RESTORE")
+                        .arg(&state.key)
+                        .arg(ttl)
+                        .arg(bytes)
+                        .arg("REPLACE")
+                        .query_async(conn)
+                        .await
+                        .map_err(|e| format!("Failed to restore {}: {}", state.key, e))?;
+                }
+                _ => {
+                    let _: i64 = redis::cmd("DEL").arg(&state.key).query_async(conn).await?;
+                }
+            }
+            restored.push(state.key.clone());
+        }
+
+        let mut state = self.state.lock();
+        if let Some(record) = state.records.iter_mut().find(|r| r.entry.id == entry_id) {
+            record.entry.restored_at = Some(now_ms as u64);
+        }
+        let line = serde_json::to_string(&JournalLineRef::Restored { id: entry_id, at: now_ms as u64 })?;
+        self.append(&mut state, line)?;
+        Ok(restored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, size_bytes: u64) -> JournalRecord {
+        JournalRecord {
+            entry: UndoEntry {
+                id: id.to_string(),
+                server_id: "s1".to_string(),
+                db: None,
+                operation: "SET".to_string(),
+                created_at: 0,
+                keys: vec![UndoKeyState { key: "k".to_string(), existed: true, expire_at_ms: None, size_bytes, restorable: true }],
+                restored_at: None,
+            },
+            dumps: vec![None],
+            encrypted: true,
+        }
+    }
+
+    fn line(record: &JournalRecord) -> String {
+        serde_json::to_string(&JournalLineRef::Record(record)).unwrap()
+    }
+
+    fn temp_path() -> PathBuf {
+        std::env::temp_dir().join(format!("undo-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn evicts_oldest_by_count_and_size() {
+        let mut records: VecDeque<_> = (0..MAX_ENTRIES + 5).map(|i| record(&i.to_string(), 0)).collect();
+        evict(&mut records);
+        assert_eq!(records.len(), MAX_ENTRIES);
+        assert_eq!(records[0].entry.id, "5");
+
+        let big = MAX_TOTAL_BYTES / 2 + 1;
+        let mut records: VecDeque<_> = ["a", "b", "c"].iter().map(|id| record(id, big)).collect();
+        evict(&mut records);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].entry.id, "c");
+
+        // The newest entry is kept however large it is
+        let mut records = VecDeque::from([record("huge", MAX_TOTAL_BYTES * 2)]);
+        evict(&mut records);
+        assert_eq!(records.len(), 1);
+    }
+
+    #[test]
+    fn reloads_records_and_restores() {
+        let path = temp_path();
+        let restored = serde_json::to_string(&JournalLineRef::Restored { id: "a", at: 42 }).unwrap();
+        let content = [line(&record("a", 1)), line(&record("b", 1)), restored, "not json".to_string()].join("\n");
+        std::fs::write(&path, content).unwrap();
+
+        let journal = UndoJournal::new(path.clone());
+        assert_eq!(journal.get("a").unwrap().restored_at, Some(42));
+        assert_eq!(journal.get("b").unwrap().restored_at, None);
+        assert_eq!(journal.list(None, 10).iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["b", "a"]);
+        assert_eq!(journal.state.lock().lines, 4);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn compacts_once_stale_lines_pile_up() {
+        let path = temp_path();
+        let journal = UndoJournal::new(path.clone());
+        let mut state = journal.state.lock();
+        let first = record("a", 1);
+        let first_line = line(&first);
+        state.records.push_back(first);
+        journal.append(&mut state, first_line).unwrap();
+
+        for _ in 0..MAX_ENTRIES {
+            let restored = serde_json::to_string(&JournalLineRef::Restored { id: "a", at: 1 }).unwrap();
+            journal.append(&mut state, restored).unwrap();
+        }
+        assert_eq!(state.lines, MAX_ENTRIES + 1);
+
+        let restored = serde_json::to_string(&JournalLineRef::Restored { id: "a", at: 1 }).unwrap();
+        journal.append(&mut state, restored).unwrap();
+        assert_eq!(state.lines, 1);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn migrates_legacy_json_journal() {
+        let path = temp_path();
+        let legacy = path.with_extension("json");
+        let old = VecDeque::from([record("a", 1), record("b", 1)]);
+        std::fs::write(&legacy, serde_json::to_string(&old).unwrap()).unwrap();
+
+        let journal = UndoJournal::new(path.clone());
+        assert!(journal.get("a").is_some() && journal.get("b").is_some());
+        assert!(!legacy.exists());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn marks_what_can_be_restored() {
+        let small = redis::Value::BulkString(vec![1; 8]);
+        let (state, kept) = key_state("k", Some(&small), Some(5000), 1000);
+        assert!(state.existed && state.restorable && kept.is_some());
+        assert_eq!(state.expire_at_ms, Some(6000));
+
+        let large = redis::Value::BulkString(vec![0; MAX_DUMP_BYTES + 1]);
+        let (state, kept) = key_state("k", Some(&large), Some(-1), 0);
+        assert!(state.existed && !state.restorable && kept.is_none());
+        assert_eq!(state.size_bytes, MAX_DUMP_BYTES as u64 + 1);
+
+        // Restoring a key that didn't exist is just a DEL
+        let (state, _) = key_state("k", Some(&redis::Value::Nil), Some(-2), 0);
+        assert!(!state.existed && state.restorable);
+
+        // No usable DUMP reply: PTTL still tells whether there was a key
+        let (state, _) = key_state("k", Some(&redis::Value::Okay), Some(-1), 0);
+        assert!(state.existed && !state.restorable);
+        let (state, _) = key_state("k", None, None, 0);
+        assert!(!state.existed && !state.restorable);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::error::AppError;
+use crate::types::*;
+use redis::aio::MultiplexedConnection;
+
+/// Offered when `latency-monitor-threshold` is 0.
+pub const SUGGESTED_THRESHOLD_MS: u64 = 100;
+
+fn text(value: &redis::Value) -> Option<String> {
+    match value {
+        redis::Value::SimpleString(s) => Some(s.clone()),
+        redis::Value::BulkString(b) => Some(String::from_utf8_lossy(b).to_string()),
+        _ => None,
+    }
+}
+
+fn number(value: Option<&redis::Value>) -> Option<u64> {
+    match value? {
+        redis::Value::Int(n) => u64::try_from(*n).ok(),
+        other => text(other)?.parse().ok(),
+    }
+}
+
+/// Pairs of a RESP2 flat array or a RESP3 map.
+fn pairs(value: &redis::Value) -> Vec<(&redis::Value, &redis::Value)> {
+    match value {
+        redis::Value::Map(entries) => entries.iter().map(|(k, v)| (k, v)).collect(),
+        redis::Value::Array(items) => items.chunks(2).filter_map(|p| Some((p.first()?, p.get(1)?))).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn latency_error(e: redis::RedisError) -> AppError {
+    AppError::from(e).context("LATENCY command failed")
+}
+
+/// `LATENCY LATEST`: one `[event, timestamp, latest ms, max ms]` per event.
+pub fn parse_latest(value: &redis::Value) -> Vec<LatencyEvent> {
+    let redis::Value::Array(events) = value else {
+        return Vec::new();
+    };
+    let mut events: Vec<LatencyEvent> = events.iter()
+        .filter_map(|event| {
+            let redis::Value::Array(fields) = event else {
+                return None;
+            };
+            Some(LatencyEvent {
+                event: text(fields.first()?)?,
+                timestamp: number(fields.get(1))?,
+                latest_ms: number(fields.get(2))?,
+                max_ms: number(fields.get(3)).unwrap_or(0),
+            })
+        })
+        .collect();
+    events.sort_by_key(|e| std::cmp::Reverse(e.max_ms));
+    events
+}
+
+/// `LATENCY HISTORY <event>`: `[timestamp, ms]` samples, oldest first.
+pub fn parse_history(value: &redis::Value) -> Vec<LatencyHistoryEntry> {
+    let redis::Value::Array(samples) = value else {
+        return Vec::new();
+    };
+    samples.iter()
+        .filter_map(|sample| {
+            let redis::Value::Array(fields) = sample else {
+                return None;
+            };
+            Some(LatencyHistoryEntry { timestamp: number(fields.first())?, latency_ms: number(fields.get(1))? })
+        })
+        .collect()
+}
+
+// Upper bound of the first bucket holding the given share of calls
+fn bucket_percentile(buckets: &[LatencyBucket], calls: u64, p: f64) -> Option<u64> {
+    let target = (calls as f64 * p).ceil() as u64;
+    buckets.iter().find(|b| b.cumulative_count >= target).map(|b| b.le_usec)
+}
+
+/// `LATENCY HISTOGRAM`: per command, call count and power-of-two buckets
+/// of cumulative counts (`le_usec` is the bucket's upper bound).
+pub fn parse_histograms(value: &redis::Value) -> Vec<CommandLatencyHistogram> {
+    let mut histograms: Vec<CommandLatencyHistogram> = pairs(value).into_iter()
+        .filter_map(|(name, details)| {
+            let details = pairs(details);
+            let field = |key: &str| details.iter().find(|(k, _)| text(k).as_deref() == Some(key)).map(|(_, v)| *v);
+            let calls = number(field("calls")).unwrap_or(0);
+            let mut buckets: Vec<LatencyBucket> = field("histogram_usec")
+                .map(pairs)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|(bound, count)| Some(LatencyBucket { le_usec: number(Some(bound))?, cumulative_count: number(Some(count))? }))
+                .collect();
+            buckets.sort_by_key(|b| b.le_usec);
+            Some(CommandLatencyHistogram {
+                command: text(name)?,
+                calls,
+                p50_usec: bucket_percentile(&buckets, calls, 0.5),
+                p99_usec: bucket_percentile(&buckets, calls, 0.99),
+                buckets,
+            })
+        })
+        .collect();
+    histograms.sort_by_key(|h| std::cmp::Reverse(h.calls));
+    histograms
+}
+
+pub async fn latest(conn: &mut MultiplexedConnection) -> Result<Vec<LatencyEvent>, AppError> {
+    let reply: redis::Value = redis::cmd("LATENCY").arg("LATEST").query_async(conn).await.map_err(latency_error)?;
+    Ok(parse_latest(&reply))
+}
+
+pub async fn history(conn: &mut MultiplexedConnection, event: &str) -> Result<Vec<LatencyHistoryEntry>, AppError> {
+    let reply: redis::Value = redis::cmd("LATENCY").arg("HISTORY").arg(event).query_async(conn).await.map_err(latency_error)?;
+    Ok(parse_history(&reply))
+}
+
+/// Needs Redis 7; with no commands, covers every command called so far.
+pub async fn histograms(conn: &mut MultiplexedConnection, commands: &[String]) -> Result<Vec<CommandLatencyHistogram>, AppError> {
+    let reply: redis::Value = redis::cmd("LATENCY")
+        .arg("HISTOGRAM")
+        .arg(commands)
+        .query_async(conn)
+        .await
+        .map_err(|e| AppError::from(e).context("LATENCY HISTOGRAM failed (requires Redis 7+)"))?;
+    Ok(parse_histograms(&reply))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> redis::Value {
+        redis::Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parses_latest_and_histograms() {
+        let latest = redis::Value::Array(vec![
+            redis::Value::Array(vec![bulk("command"), redis::Value::Int(1700000000), redis::Value::Int(120), redis::Value::Int(450)]),
+            redis::Value::Array(vec![bulk("fork"), redis::Value::Int(1700000100), redis::Value::Int(30), redis::Value::Int(30)]),
+        ]);
+        let events = parse_latest(&latest);
+        assert_eq!(events[0].event, "command");
+        assert_eq!(events[0].max_ms, 450);
+
+        let histogram = redis::Value::Array(vec![
+            bulk("set"),
+            redis::Value::Array(vec![
+                bulk("calls"), redis::Value::Int(100),
+                bulk("histogram_usec"), redis::Value::Array(vec![
+                    redis::Value::Int(4), redis::Value::Int(98),
+                    redis::Value::Int(1), redis::Value::Int(40),
+                    redis::Value::Int(16), redis::Value::Int(100),
+                ]),
+            ]),
+        ]);
+        let histograms = parse_histograms(&histogram);
+        assert_eq!(histograms[0].command, "set");
+        assert_eq!(histograms[0].buckets[0].le_usec, 1);
+        assert_eq!(histograms[0].p50_usec, Some(4));
+        assert_eq!(histograms[0].p99_usec, Some(16));
+    }
+}
@@ -1,14 +1,37 @@
+mod acl;
+mod acl_monitor;
+mod analysis;
+mod aof;
+mod audit;
+mod bigkeys;
+mod bulk;
+mod commands;
+mod config;
 mod crypto;
+mod deletion;
+mod error;
+mod health;
+mod jobs;
+mod journal;
+mod latency;
+mod lint;
+mod namespace;
+mod rdb;
 mod redis_client;
+mod rename;
+mod slowlog;
+mod snapshots;
 mod types;
 
 use crypto::{encrypt_password, decrypt_password};
+use error::AppError;
+use jobs::JobInfo;
 use redis_client::RedisManager;
 use std::sync::Arc;
 use types::*;
 
 #[tauri::command]
-async fn get_servers(state: tauri::State<'_, Arc<RedisManager>>) -> Result<Vec<RedisServer>, String> {
+async fn get_servers(state: tauri::State<'_, Arc<RedisManager>>) -> Result<Vec<RedisServer>, AppError> {
     state.get_servers().await
 }
 
@@ -16,7 +39,7 @@ async fn get_servers(state: tauri::State<'_, Arc<RedisManager>>) -> Result<Vec<R
 async fn save_servers(
     state: tauri::State<'_, Arc<RedisManager>>,
     servers: Vec<RedisServer>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     state.save_servers(servers).await
 }
 
@@ -24,32 +47,48 @@ async fn save_servers(
 async fn connect_redis(
     state: tauri::State<'_, Arc<RedisManager>>,
     server: RedisServer,
-) -> Result<(), String> {
-    state.connect(&server).await
+    app: tauri::AppHandle,
+) -> Result<(), AppError> {
+    state.connect(&server, app).await
+}
+
+#[tauri::command]
+async fn list_connection_health(
+    state: tauri::State<'_, Arc<RedisManager>>,
+) -> Result<Vec<ConnectionHealth>, AppError> {
+    Ok(state.list_connection_health().await)
 }
 
 #[tauri::command]
 async fn disconnect_redis(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<(), String> {
-    state.disconnect(&server_id).await
+) -> Result<(), AppError> {
+    state.disconnect(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_redis_info(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<RedisInfo, String> {
-    state.get_info(&server_id).await
+) -> Result<RedisInfo, AppError> {
+    state.get_info(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn list_databases(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<Vec<DatabaseSummary>, AppError> {
+    state.list_databases(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_client_list(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<Vec<ClientInfo>, String> {
-    state.get_clients(&server_id).await
+) -> Result<Vec<ClientInfo>, AppError> {
+    state.get_clients(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
@@ -57,40 +96,84 @@ async fn start_monitor(
     app: tauri::AppHandle,
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<(), String> {
-    state.start_monitor(&server_id, app).await
+) -> Result<(), AppError> {
+    state.start_monitor(&server_id, app).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn stop_monitor(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<(), String> {
-    state.stop_monitor(&server_id).await
+) -> Result<(), AppError> {
+    state.stop_monitor(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_advanced_analytics(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<AdvancedAnalytics, String> {
-    state.get_advanced_analytics(&server_id).await
+) -> Result<AdvancedAnalytics, AppError> {
+    state.get_advanced_analytics(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_memory_analytics(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<(Option<MemoryStats>, Option<String>), String> {
-    state.get_memory_analytics(&server_id).await
+) -> Result<(Option<MemoryStats>, Option<String>), AppError> {
+    state.get_memory_analytics(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_latency_analytics(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<Option<String>, String> {
-    state.get_latency_analytics(&server_id).await
+) -> Result<Option<String>, AppError> {
+    state.get_latency_analytics(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_latency_overview(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<LatencyOverview, AppError> {
+    state.get_latency_overview(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_latency_history(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    event: String,
+) -> Result<Vec<LatencyHistoryEntry>, AppError> {
+    state.get_latency_history(&server_id, &event).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_latency_histograms(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    commands: Option<Vec<String>>,
+) -> Result<Vec<CommandLatencyHistogram>, AppError> {
+    state.get_latency_histograms(&server_id, &commands.unwrap_or_default()).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn reset_latency(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    events: Option<Vec<String>>,
+) -> Result<u64, AppError> {
+    state.reset_latency(&server_id, &events.unwrap_or_default()).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn enable_latency_monitor(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    threshold_ms: Option<u64>,
+) -> Result<ConfigApplyResult, AppError> {
+    state.enable_latency_monitor(&server_id, threshold_ms).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
@@ -98,73 +181,118 @@ async fn get_slow_log(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
     count: u32,
-) -> Result<Vec<SlowLogEntry>, String> {
-    state.get_slow_log(&server_id, count).await
+) -> Result<Vec<SlowLogEntry>, AppError> {
+    state.get_slow_log(&server_id, count).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn start_slowlog_collector(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    interval_secs: Option<u64>,
+    batch_size: Option<u32>,
+) -> Result<String, AppError> {
+    state.start_slowlog_collector(&server_id, interval_secs, batch_size).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn stop_slowlog_collector(state: tauri::State<'_, Arc<RedisManager>>, server_id: String) -> bool {
+    state.stop_slowlog_collector(&server_id)
+}
+
+#[tauri::command]
+fn get_slowlog_history(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    from: Option<u64>,
+    to: Option<u64>,
+    limit: Option<usize>,
+) -> Vec<SlowLogEntry> {
+    state.get_slowlog_history(&server_id, from, to, limit)
+}
+
+#[tauri::command]
+fn aggregate_slowlog(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    from: Option<u64>,
+    to: Option<u64>,
+) -> SlowLogAggregate {
+    state.aggregate_slowlog(&server_id, from, to)
+}
+
+#[tauri::command]
+fn clear_slowlog_history(state: tauri::State<'_, Arc<RedisManager>>, server_id: String) -> Result<(), AppError> {
+    state.clear_slowlog_history(&server_id).map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_command_stats(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<Vec<CommandStat>, String> {
-    state.get_command_stats(&server_id).await
+) -> Result<Vec<CommandStat>, AppError> {
+    state.get_command_stats(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
-fn encrypt_server_password(password: String) -> Result<String, String> {
-    encrypt_password(&password)
+fn encrypt_server_password(password: String) -> Result<String, AppError> {
+    encrypt_password(&password).map_err(AppError::from)
 }
 
 #[tauri::command]
-fn decrypt_server_password(encrypted: String) -> Result<String, String> {
-    decrypt_password(&encrypted)
+fn decrypt_server_password(encrypted: String) -> Result<String, AppError> {
+    decrypt_password(&encrypted).map_err(AppError::from)
 }
 
 #[tauri::command]
 async fn scan_keys(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     pattern: String,
     cursor: String,
     count: u32,
-) -> Result<KeyScanResult, String> {
-    state.scan_keys(&server_id, &pattern, &cursor, count).await
+) -> Result<KeyScanResult, AppError> {
+    state.scan_keys(&server_id, db, &pattern, &cursor, count).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_key_value(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
-) -> Result<KeyValue, String> {
-    state.get_key_value(&server_id, &key).await
+) -> Result<KeyValue, AppError> {
+    state.get_key_value(&server_id, db, &key).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn delete_key(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
-) -> Result<bool, String> {
-    state.delete_key(&server_id, &key).await
+) -> Result<bool, AppError> {
+    state.delete_key(&server_id, db, &key).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn set_key_ttl(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     ttl: i64,
-) -> Result<bool, String> {
-    state.set_key_ttl(&server_id, &key, ttl).await
+) -> Result<bool, AppError> {
+    state.set_key_ttl(&server_id, db, &key, ttl).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn get_server_capabilities(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<ServerCapabilities, String> {
-    state.get_server_capabilities(&server_id).await
+) -> Result<ServerCapabilities, AppError> {
+    state.get_server_capabilities(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
@@ -173,120 +301,197 @@ async fn check_operation_impact(
     server_id: String,
     operation: String,
     pattern: String,
-) -> Result<PerformanceWarning, String> {
-    state.check_operation_impact(&server_id, &operation, &pattern).await
+) -> Result<PerformanceWarning, AppError> {
+    state.check_operation_impact(&server_id, &operation, &pattern).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn execute_command(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     command: String,
-) -> Result<CommandResult, String> {
-    state.execute_command(&server_id, &command).await
+    confirmation_token: Option<String>,
+    timeout_ms: Option<u64>,
+) -> Result<CommandResult, AppError> {
+    state.execute_command(&server_id, db, &command, confirmation_token.as_deref(), timeout_ms).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn set_string(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     value: String,
     ttl: Option<i64>,
-) -> Result<bool, String> {
-    state.set_string(&server_id, &key, &value, ttl).await
+) -> Result<bool, AppError> {
+    state.set_string(&server_id, db, &key, &value, ttl).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn hash_set(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     field: String,
     value: String,
-) -> Result<bool, String> {
-    state.hash_set(&server_id, &key, &field, &value).await
+) -> Result<bool, AppError> {
+    state.hash_set(&server_id, db, &key, &field, &value).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn hash_delete(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     field: String,
-) -> Result<bool, String> {
-    state.hash_delete(&server_id, &key, &field).await
+) -> Result<bool, AppError> {
+    state.hash_delete(&server_id, db, &key, &field).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn list_push(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     value: String,
     position: String,
-) -> Result<i64, String> {
-    state.list_push(&server_id, &key, &value, &position).await
+) -> Result<i64, AppError> {
+    state.list_push(&server_id, db, &key, &value, &position).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn list_remove(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     index: i64,
-) -> Result<bool, String> {
-    state.list_remove(&server_id, &key, index).await
+) -> Result<bool, AppError> {
+    state.list_remove(&server_id, db, &key, index).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn set_add(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     member: String,
-) -> Result<bool, String> {
-    state.set_add(&server_id, &key, &member).await
+) -> Result<bool, AppError> {
+    state.set_add(&server_id, db, &key, &member).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn set_remove(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     member: String,
-) -> Result<bool, String> {
-    state.set_remove(&server_id, &key, &member).await
+) -> Result<bool, AppError> {
+    state.set_remove(&server_id, db, &key, &member).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn zset_add(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     score: f64,
     member: String,
-) -> Result<bool, String> {
-    state.zset_add(&server_id, &key, score, &member).await
+) -> Result<bool, AppError> {
+    state.zset_add(&server_id, db, &key, score, &member).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn zset_remove(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     key: String,
     member: String,
-) -> Result<bool, String> {
-    state.zset_remove(&server_id, &key, &member).await
+) -> Result<bool, AppError> {
+    state.zset_remove(&server_id, db, &key, &member).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
-async fn bulk_delete(
+async fn start_incremental_delete(
+    app: tauri::AppHandle,
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-    pattern: String,
-) -> Result<BulkDeleteResult, String> {
-    state.bulk_delete(&server_id, &pattern).await
+    db: Option<u8>,
+    key: String,
+    options: IncrementalDeleteOptions,
+) -> Result<String, AppError> {
+    state.start_incremental_delete(&server_id, db, &key, options, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn preview_bulk_keys(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    filter: BulkKeyFilter,
+    options: BulkJobOptions,
+) -> Result<BulkPreview, AppError> {
+    state.preview_bulk_job(&server_id, &filter, &options).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn start_bulk_delete(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    filter: BulkKeyFilter,
+    options: BulkJobOptions,
+) -> Result<String, AppError> {
+    state.start_bulk_delete(&server_id, filter, options, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn start_bulk_ttl(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    filter: BulkKeyFilter,
+    operation: BulkTtlOperation,
+    options: BulkJobOptions,
+) -> Result<String, AppError> {
+    state.start_bulk_ttl(&server_id, filter, operation, options, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn preview_bulk_rename(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    spec: BulkRenameSpec,
+    options: BulkJobOptions,
+) -> Result<BulkRenamePreview, AppError> {
+    state.preview_bulk_rename(&server_id, &spec, &options).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn start_bulk_rename(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    spec: BulkRenameSpec,
+    options: BulkJobOptions,
+) -> Result<String, AppError> {
+    state.start_bulk_rename(&server_id, spec, options, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_bulk_job_result(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    job_id: String,
+) -> Result<Option<BulkJobProgress>, AppError> {
+    state.get_bulk_job_result(&job_id).await
 }
 
 #[tauri::command]
@@ -294,36 +499,351 @@ async fn analyze_database(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
     sample_size: u32,
-) -> Result<DatabaseAnalysis, String> {
-    state.analyze_database(&server_id, sample_size).await
+    namespace_options: Option<NamespaceOptions>,
+) -> Result<DatabaseAnalysis, AppError> {
+    state.analyze_database(&server_id, sample_size, &namespace_options.unwrap_or_default()).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn start_database_analysis(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    options: AnalysisOptions,
+) -> Result<String, AppError> {
+    state.start_analysis(&server_id, options, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_analysis_report(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    job_id: String,
+) -> Result<Option<AnalysisReport>, AppError> {
+    state.get_analysis_report(&job_id).await
+}
+
+#[tauri::command]
+async fn list_analysis_reports(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<Vec<AnalysisReport>, AppError> {
+    state.list_analysis_reports(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn list_jobs(state: tauri::State<'_, Arc<RedisManager>>) -> Vec<JobInfo> {
+    state.list_jobs()
+}
+
+#[tauri::command]
+fn cancel_job(state: tauri::State<'_, Arc<RedisManager>>, job_id: String) -> bool {
+    state.cancel_job(&job_id)
+}
+
+#[tauri::command]
+async fn start_bigkey_scan(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    options: BigKeyScanOptions,
+) -> Result<String, AppError> {
+    state.start_bigkey_scan(&server_id, options, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_bigkey_report(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    job_id: String,
+) -> Result<Option<BigKeyScanReport>, AppError> {
+    state.get_bigkey_report(&job_id).await
+}
+
+#[tauri::command]
+async fn get_server_config(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<Vec<ConfigParameter>, AppError> {
+    state.get_config(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn apply_server_config(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    updates: Vec<ConfigUpdate>,
+    rewrite: Option<bool>,
+) -> Result<ConfigApplyResult, AppError> {
+    state.apply_config(&server_id, &updates, rewrite.unwrap_or(false)).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn compare_server_configs(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    left_server_id: String,
+    right_server_id: String,
+    include_instance_specific: Option<bool>,
+) -> Result<ConfigComparison, AppError> {
+    state.compare_server_configs(&left_server_id, &right_server_id, include_instance_specific.unwrap_or(false)).await
+}
+
+#[tauri::command]
+async fn lint_server_config(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<LintReport, AppError> {
+    state.lint_config(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn list_lint_rules(state: tauri::State<'_, Arc<RedisManager>>) -> Vec<LintRuleInfo> {
+    state.list_lint_rules()
+}
+
+#[tauri::command]
+fn get_lint_settings(state: tauri::State<'_, Arc<RedisManager>>) -> LintSettings {
+    state.get_lint_settings()
+}
+
+#[tauri::command]
+fn save_lint_settings(state: tauri::State<'_, Arc<RedisManager>>, settings: LintSettings) -> Result<(), AppError> {
+    state.save_lint_settings(&settings)
+}
+
+#[tauri::command]
+async fn save_analysis_snapshot(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    label: Option<String>,
+    analysis: Option<DatabaseAnalysis>,
+    analytics: Option<AdvancedAnalytics>,
+) -> Result<SnapshotSummary, AppError> {
+    state.save_snapshot(&server_id, label, analysis, analytics).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn list_analysis_snapshots(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<Vec<SnapshotSummary>, AppError> {
+    state.list_snapshots(&server_id).map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn get_analysis_snapshot(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    snapshot_id: String,
+) -> Result<AnalysisSnapshot, AppError> {
+    state.get_snapshot(&server_id, &snapshot_id).map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn delete_analysis_snapshot(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    snapshot_id: String,
+) -> Result<(), AppError> {
+    state.delete_snapshot(&server_id, &snapshot_id).map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn compare_analysis_snapshots(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    from_id: String,
+    to_id: String,
+) -> Result<SnapshotDiff, AppError> {
+    state.compare_snapshots(&server_id, &from_id, &to_id).map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn list_undo_entries(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: Option<String>,
+    limit: Option<usize>,
+) -> Vec<UndoEntry> {
+    state.list_undo_entries(server_id.as_deref(), limit.unwrap_or(50))
+}
+
+#[tauri::command]
+async fn restore_undo_entry(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    entry_id: String,
+) -> Result<Vec<String>, AppError> {
+    state.restore_undo_entry(&entry_id).await
+}
+
+#[tauri::command]
+fn query_audit_log(state: tauri::State<'_, Arc<RedisManager>>, query: AuditQuery) -> Vec<AuditEntry> {
+    state.query_audit_log(&query)
+}
+
+#[tauri::command]
+fn verify_audit_log(state: tauri::State<'_, Arc<RedisManager>>) -> AuditVerification {
+    state.verify_audit_log()
+}
+
+#[tauri::command]
+fn export_audit_log(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    query: AuditQuery,
+    path: String,
+    format: String,
+) -> Result<usize, AppError> {
+    state.export_audit_log(&query, &path, &format)
 }
 
 #[tauri::command]
 async fn analyze_clients(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
-) -> Result<ClientAnalysis, String> {
-    state.analyze_clients(&server_id).await
+) -> Result<ClientAnalysis, AppError> {
+    state.analyze_clients(&server_id).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn rename_key(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     old_key: String,
     new_key: String,
-) -> Result<bool, String> {
-    state.rename_key(&server_id, &old_key, &new_key).await
+) -> Result<bool, AppError> {
+    state.rename_key(&server_id, db, &old_key, &new_key).await.map_err(|e| e.on_server(&server_id))
 }
 
 #[tauri::command]
 async fn copy_key(
     state: tauri::State<'_, Arc<RedisManager>>,
     server_id: String,
+    db: Option<u8>,
     source: String,
     dest: String,
-) -> Result<bool, String> {
-    state.copy_key(&server_id, &source, &dest).await
+) -> Result<bool, AppError> {
+    state.copy_key(&server_id, db, &source, &dest).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn list_acl_users(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+) -> Result<Vec<AclUser>, AppError> {
+    state.list_acl_users(&server_id).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_acl_user(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    username: String,
+) -> Result<Option<AclUser>, AppError> {
+    state.get_acl_user(&server_id, &username).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn set_acl_user(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    username: String,
+    rules: Vec<String>,
+) -> Result<(), AppError> {
+    state.set_acl_user(&server_id, &username, &rules).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn delete_acl_users(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    usernames: Vec<String>,
+) -> Result<u64, AppError> {
+    state.delete_acl_users(&server_id, &usernames).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_acl_categories(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    category: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    state.get_acl_categories(&server_id, category.as_deref()).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn get_acl_log(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    count: Option<u32>,
+) -> Result<Vec<AclLogEntry>, AppError> {
+    state.get_acl_log(&server_id, count).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn acl_dry_run(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    username: String,
+    command: Vec<String>,
+) -> Result<AclDryRunResult, AppError> {
+    state.acl_dry_run(&server_id, &username, &command).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+async fn start_acl_monitor(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    interval_secs: Option<u64>,
+    alerts: Option<Vec<CustomAlert>>,
+) -> Result<String, AppError> {
+    state.start_acl_monitor(&server_id, interval_secs, alerts, app).await.map_err(|e| e.on_server(&server_id))
+}
+
+#[tauri::command]
+fn stop_acl_monitor(state: tauri::State<'_, Arc<RedisManager>>, server_id: String) -> bool {
+    state.stop_acl_monitor(&server_id)
+}
+
+#[tauri::command]
+fn get_acl_events(
+    state: tauri::State<'_, Arc<RedisManager>>,
+    server_id: String,
+    since: Option<u64>,
+    limit: Option<usize>,
+) -> Vec<AclSecurityEvent> {
+    state.get_acl_events(&server_id, since, limit)
+}
+
+#[tauri::command]
+async fn analyze_rdb_file(path: String, namespace_options: Option<NamespaceOptions>) -> Result<DatabaseAnalysis, AppError> {
+    Ok(tokio::task::spawn_blocking(move || rdb::analyze_rdb_file(&path, &namespace_options.unwrap_or_default()))
+        .await
+        .map_err(|e| e.to_string())??)
+}
+
+#[tauri::command]
+async fn inspect_aof_file(path: String) -> Result<AofInspection, AppError> {
+    Ok(tokio::task::spawn_blocking(move || aof::inspect_aof(&path))
+        .await
+        .map_err(|e| e.to_string())??)
+}
+
+#[tauri::command]
+async fn get_aof_key_history(path: String, key: String, limit: usize) -> Result<Vec<AofCommandRecord>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || aof::aof_key_history(&path, &key, limit))
+        .await
+        .map_err(|e| e.to_string())??)
+}
+
+#[tauri::command]
+async fn reconstruct_aof_state(
+    path: String,
+    until_offset: Option<u64>,
+    until_timestamp: Option<u64>,
+    limit: usize,
+) -> Result<AofStateSnapshot, AppError> {
+    Ok(tokio::task::spawn_blocking(move || aof::reconstruct_aof_state(&path, until_offset, until_timestamp, limit))
+        .await
+        .map_err(|e| e.to_string())??)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -337,14 +857,26 @@ pub fn run() {
             save_servers,
             connect_redis,
             disconnect_redis,
+            list_connection_health,
             get_redis_info,
             get_client_list,
+            list_databases,
             start_monitor,
             stop_monitor,
             get_advanced_analytics,
             get_memory_analytics,
             get_latency_analytics,
+            get_latency_overview,
+            get_latency_history,
+            get_latency_histograms,
+            reset_latency,
+            enable_latency_monitor,
             get_slow_log,
+            start_slowlog_collector,
+            stop_slowlog_collector,
+            get_slowlog_history,
+            aggregate_slowlog,
+            clear_slowlog_history,
             get_command_stats,
             encrypt_server_password,
             decrypt_server_password,
@@ -364,11 +896,55 @@ pub fn run() {
             set_remove,
             zset_add,
             zset_remove,
-            bulk_delete,
+            start_incremental_delete,
+            preview_bulk_keys,
+            start_bulk_delete,
+            start_bulk_ttl,
+            preview_bulk_rename,
+            start_bulk_rename,
+            get_bulk_job_result,
             analyze_database,
+            start_database_analysis,
+            get_analysis_report,
+            list_analysis_reports,
+            list_jobs,
+            cancel_job,
+            start_bigkey_scan,
+            get_bigkey_report,
+            get_server_config,
+            apply_server_config,
+            compare_server_configs,
+            lint_server_config,
+            list_lint_rules,
+            get_lint_settings,
+            save_lint_settings,
+            save_analysis_snapshot,
+            list_analysis_snapshots,
+            get_analysis_snapshot,
+            delete_analysis_snapshot,
+            compare_analysis_snapshots,
             analyze_clients,
             rename_key,
             copy_key,
+            list_undo_entries,
+            restore_undo_entry,
+            query_audit_log,
+            verify_audit_log,
+            export_audit_log,
+            list_acl_users,
+            get_acl_user,
+            set_acl_user,
+            delete_acl_users,
+            get_acl_categories,
+            get_acl_log,
+            acl_dry_run,
+            start_acl_monitor,
+            stop_acl_monitor,
+            get_acl_events,
+            analyze_rdb_file,
+            inspect_aof_file,
+            get_aof_key_history,
+            reconstruct_aof_state,
         ])
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
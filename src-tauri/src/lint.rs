@@ -0,0 +1,397 @@
+use crate::config::parse_memory;
+use crate::types::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const OPERATORS: &[&str] = &["eq", "ne", "lt", "lte", "gt", "gte", "contains", "missing"];
+const SEVERITIES: &[&str] = &["critical", "warning", "info"];
+
+/// Seconds of writes the replication backlog should hold so a replica that
+/// drops off briefly can resync partially.
+const BACKLOG_SECONDS: f64 = 60.0;
+
+/// `INFO` as a flat field map; section headers are dropped.
+pub fn info_fields(info: &str) -> HashMap<String, String> {
+    info.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.trim().split_once(':'))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+struct LintInput<'a> {
+    config: &'a HashMap<String, String>,
+    info: &'a HashMap<String, String>,
+}
+
+impl LintInput<'_> {
+    fn config(&self, name: &str) -> Option<&str> {
+        self.config.get(name).map(|v| v.as_str())
+    }
+
+    fn bytes(&self, name: &str) -> Option<u64> {
+        parse_memory(self.config(name)?)
+    }
+
+    fn info_number(&self, name: &str) -> Option<f64> {
+        self.info.get(name)?.trim().parse().ok()
+    }
+
+    /// Keys and keys with a TTL across all databases.
+    fn keyspace(&self) -> (u64, u64) {
+        self.info.iter()
+            .filter(|(k, _)| k.starts_with("db") && k[2..].parse::<u32>().is_ok())
+            .fold((0, 0), |(keys, expires), (_, v)| {
+                let field = |name: &str| v.split(',').find_map(|p| p.strip_prefix(name)?.strip_prefix('=')?.parse::<u64>().ok()).unwrap_or(0);
+                (keys + field("keys"), expires + field("expires"))
+            })
+    }
+}
+
+struct Hit {
+    detail: String,
+    /// Overrides the rule's severity when the same problem can be mild or acute.
+    severity: Option<&'static str>,
+}
+
+fn hit(detail: String) -> Option<Hit> {
+    Some(Hit { detail, severity: None })
+}
+
+struct BuiltinRule {
+    id: &'static str,
+    title: &'static str,
+    severity: &'static str,
+    parameter: &'static str,
+    rationale: &'static str,
+    recommendation: &'static str,
+    check: fn(&LintInput) -> Option<Hit>,
+}
+
+const BUILTIN_RULES: &[BuiltinRule] = &[
+    BuiltinRule {
+        id: "maxmemory-unset-on-cache",
+        title: "Cache without a memory limit",
+        severity: "warning",
+        parameter: "maxmemory",
+        rationale: "With maxmemory 0 the eviction policy never runs, so a cache keeps growing until the OS swaps or kills Redis.",
+        recommendation: "Set maxmemory below the host's available memory and pick an allkeys-* or volatile-* policy.",
+        check: |input| {
+            if input.bytes("maxmemory")? != 0 {
+                return None;
+            }
+            let (keys, expires) = input.keyspace();
+            let evicting = input.config("maxmemory-policy").is_some_and(|p| p != "noeviction");
+            // Mostly expiring keys or a configured eviction policy mark it as a cache
+            if evicting || (keys > 0 && expires * 2 >= keys) {
+                hit(format!("maxmemory is 0 while {} of {} keys have a TTL", expires, keys))
+            } else {
+                None
+            }
+        },
+    },
+    BuiltinRule {
+        id: "noeviction-near-limit",
+        title: "No eviction close to the memory limit",
+        severity: "warning",
+        parameter: "maxmemory-policy",
+        rationale: "With noeviction, writes fail with OOM errors once used memory reaches maxmemory.",
+        recommendation: "Raise maxmemory, free memory, or switch to an eviction policy if the data can be evicted.",
+        check: |input| {
+            if input.config("maxmemory-policy")? != "noeviction" {
+                return None;
+            }
+            let limit = input.bytes("maxmemory").filter(|m| *m > 0)? as f64;
+            let ratio = input.info_number("used_memory")? / limit;
+            if ratio < 0.8 {
+                return None;
+            }
+            Some(Hit {
+                detail: format!("Used memory is at {:.0}% of maxmemory", ratio * 100.0),
+                severity: Some(if ratio >= 0.9 { "critical" } else { "warning" }),
+            })
+        },
+    },
+    BuiltinRule {
+        id: "no-persistence",
+        title: "Persistence disabled",
+        severity: "warning",
+        parameter: "save",
+        rationale: "With no RDB save points and AOF off, a restart or crash loses the whole dataset.",
+        recommendation: "Enable AOF or add save points, unless this instance is a disposable cache.",
+        check: |input| {
+            let no_snapshots = input.config("save")?.trim().is_empty();
+            let no_aof = input.config("appendonly")? == "no";
+            if no_snapshots && no_aof {
+                hit("save is empty and appendonly is no".to_string())
+            } else {
+                None
+            }
+        },
+    },
+    BuiltinRule {
+        id: "unprotected-without-password",
+        title: "Protected mode off without a password",
+        severity: "critical",
+        parameter: "protected-mode",
+        rationale: "Protected mode is what stops a passwordless server from accepting remote clients; with it off anyone who can reach the port has full access.",
+        recommendation: "Set requirepass (or ACL users) or turn protected-mode back on.",
+        check: |input| {
+            let unprotected = input.config("protected-mode")? == "no";
+            if unprotected && input.config("requirepass").unwrap_or_default().is_empty() {
+                hit("protected-mode is no and requirepass is empty".to_string())
+            } else {
+                None
+            }
+        },
+    },
+    BuiltinRule {
+        id: "small-repl-backlog",
+        title: "Replication backlog too small for the write rate",
+        severity: "warning",
+        parameter: "repl-backlog-size",
+        rationale: "A replica that reconnects after its offset has left the backlog needs a full resync, which forks and copies the whole dataset.",
+        recommendation: "Size the backlog for at least a minute of writes.",
+        check: |input| {
+            let backlog = input.bytes("repl-backlog-size")? as f64;
+            let offset = input.info_number("master_repl_offset")?;
+            let uptime = input.info_number("uptime_in_seconds").filter(|u| *u > 0.0)?;
+            // Average replication stream rate since start; bursts will be higher
+            let rate = offset / uptime;
+            if rate <= 0.0 || backlog >= rate * BACKLOG_SECONDS {
+                return None;
+            }
+            hit(format!(
+                "The backlog holds {:.0}s of writes at the average rate of {:.0} bytes/s",
+                backlog / rate,
+                rate
+            ))
+        },
+    },
+    BuiltinRule {
+        id: "latency-monitor-off",
+        title: "Latency monitor disabled",
+        severity: "info",
+        parameter: "latency-monitor-threshold",
+        rationale: "Without a threshold LATENCY LATEST and LATENCY HISTORY stay empty, so latency spikes leave no trace.",
+        recommendation: "Set latency-monitor-threshold, e.g. to 100 milliseconds.",
+        check: |input| {
+            if input.config("latency-monitor-threshold")?.trim() == "0" {
+                hit("latency-monitor-threshold is 0".to_string())
+            } else {
+                None
+            }
+        },
+    },
+    BuiltinRule {
+        id: "slowlog-disabled",
+        title: "Slow log disabled",
+        severity: "info",
+        parameter: "slowlog-log-slower-than",
+        rationale: "A negative slowlog-log-slower-than turns the slow log off, hiding expensive commands.",
+        recommendation: "Set slowlog-log-slower-than to 10000 microseconds or lower.",
+        check: |input| {
+            let threshold: i64 = input.config("slowlog-log-slower-than")?.trim().parse().ok()?;
+            if threshold < 0 {
+                hit(format!("slowlog-log-slower-than is {}", threshold))
+            } else {
+                None
+            }
+        },
+    },
+];
+
+fn compare(actual: Option<&str>, operator: &str, expected: &str) -> bool {
+    let Some(actual) = actual else {
+        return operator == "missing";
+    };
+    let number = |v: &str| v.trim().parse::<f64>().ok().or_else(|| parse_memory(v).map(|n| n as f64));
+    let ordered = |test: fn(f64, f64) -> bool| match (number(actual), number(expected)) {
+        (Some(a), Some(e)) => test(a, e),
+        _ => false,
+    };
+    match operator {
+        "eq" => actual.eq_ignore_ascii_case(expected) || number(actual).is_some_and(|a| number(expected) == Some(a)),
+        "ne" => !(actual.eq_ignore_ascii_case(expected) || number(actual).is_some_and(|a| number(expected) == Some(a))),
+        "lt" => ordered(|a, e| a < e),
+        "lte" => ordered(|a, e| a <= e),
+        "gt" => ordered(|a, e| a > e),
+        "gte" => ordered(|a, e| a >= e),
+        "contains" => actual.to_lowercase().contains(&expected.to_lowercase()),
+        _ => false,
+    }
+}
+
+/// Checks user rules before they're saved.
+pub fn validate_settings(settings: &LintSettings) -> Result<(), String> {
+    let mut ids = std::collections::HashSet::new();
+    for rule in &settings.custom_rules {
+        if rule.id.trim().is_empty() || rule.title.trim().is_empty() {
+            return Err("Lint rules need an id and a title".to_string());
+        }
+        if BUILTIN_RULES.iter().any(|b| b.id == rule.id) || !ids.insert(&rule.id) {
+            return Err(format!("Lint rule id {} is already in use", rule.id));
+        }
+        if !SEVERITIES.contains(&rule.severity.as_str()) {
+            return Err(format!("Rule {}: severity must be one of {}", rule.id, SEVERITIES.join(", ")));
+        }
+        if rule.conditions.is_empty() {
+            return Err(format!("Rule {} has no conditions", rule.id));
+        }
+        for condition in &rule.conditions {
+            if condition.source != "config" && condition.source != "info" {
+                return Err(format!("Rule {}: condition source must be config or info", rule.id));
+            }
+            if !OPERATORS.contains(&condition.operator.as_str()) {
+                return Err(format!("Rule {}: operator must be one of {}", rule.id, OPERATORS.join(", ")));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn list_rules(settings: &LintSettings) -> Vec<LintRuleInfo> {
+    let enabled = |id: &str| !settings.disabled_rules.iter().any(|d| d == id);
+    BUILTIN_RULES.iter()
+        .map(|rule| LintRuleInfo {
+            id: rule.id.to_string(),
+            title: rule.title.to_string(),
+            severity: rule.severity.to_string(),
+            rationale: rule.rationale.to_string(),
+            builtin: true,
+            enabled: enabled(rule.id),
+        })
+        .chain(settings.custom_rules.iter().map(|rule| LintRuleInfo {
+            id: rule.id.clone(),
+            title: rule.title.clone(),
+            severity: rule.severity.clone(),
+            rationale: rule.rationale.clone(),
+            builtin: false,
+            enabled: rule.enabled && enabled(&rule.id),
+        }))
+        .collect()
+}
+
+/// Runs the enabled built-in and user rules against `CONFIG GET *` and
+/// `INFO`. Findings come most severe first.
+pub fn lint(config: &HashMap<String, String>, info: &HashMap<String, String>, settings: &LintSettings) -> (Vec<LintFinding>, u64) {
+    let input = LintInput { config, info };
+    let enabled = |id: &str| !settings.disabled_rules.iter().any(|d| d == id);
+    let mut findings = Vec::new();
+    let mut evaluated = 0;
+
+    for rule in BUILTIN_RULES.iter().filter(|r| enabled(r.id)) {
+        evaluated += 1;
+        if let Some(hit) = (rule.check)(&input) {
+            findings.push(LintFinding {
+                rule_id: rule.id.to_string(),
+                title: rule.title.to_string(),
+                severity: hit.severity.unwrap_or(rule.severity).to_string(),
+                rationale: rule.rationale.to_string(),
+                detail: hit.detail,
+                parameter: Some(rule.parameter.to_string()),
+                recommendation: Some(rule.recommendation.to_string()),
+                builtin: true,
+            });
+        }
+    }
+
+    for rule in settings.custom_rules.iter().filter(|r| r.enabled && enabled(&r.id)) {
+        evaluated += 1;
+        let source = |c: &LintCondition| if c.source == "info" { info } else { config };
+        let matched = rule.conditions.iter()
+            .all(|c| compare(source(c).get(&c.field).map(|v| v.as_str()), &c.operator, &c.value));
+        if !matched {
+            continue;
+        }
+        let detail = rule.conditions.iter()
+            .map(|c| format!("{} is {}", c.field, source(c).get(&c.field).map_or("unset", |v| v.as_str())))
+            .collect::<Vec<_>>()
+            .join(", ");
+        findings.push(LintFinding {
+            rule_id: rule.id.clone(),
+            title: rule.title.clone(),
+            severity: rule.severity.clone(),
+            rationale: rule.rationale.clone(),
+            detail,
+            parameter: rule.conditions.iter().find(|c| c.source == "config").map(|c| c.field.clone()),
+            recommendation: rule.recommendation.clone(),
+            builtin: false,
+        });
+    }
+
+    let rank = |s: &str| SEVERITIES.iter().position(|x| *x == s).unwrap_or(SEVERITIES.len());
+    findings.sort_by_key(|f| rank(&f.severity));
+    (findings, evaluated)
+}
+
+/// User rules and disabled rule ids, kept in one JSON file.
+pub struct LintRuleStore {
+    path: PathBuf,
+}
+
+impl LintRuleStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> LintSettings {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, settings: &LintSettings) -> Result<(), String> {
+        validate_settings(settings)?;
+        let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, content).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn flags_builtin_and_custom_rules() {
+        let config = map(&[
+            ("maxmemory", "1000"),
+            ("maxmemory-policy", "noeviction"),
+            ("save", ""),
+            ("appendonly", "no"),
+            ("protected-mode", "no"),
+            ("requirepass", ""),
+            ("latency-monitor-threshold", "0"),
+            ("repl-backlog-size", "1048576"),
+            ("hz", "10"),
+        ]);
+        let info = info_fields("# Memory\r\nused_memory:950\r\n# Replication\r\nmaster_repl_offset:100000000\r\nuptime_in_seconds:1000\r\n");
+        let settings = LintSettings {
+            custom_rules: vec![LintRule {
+                id: "low-hz".to_string(),
+                title: "hz at the default".to_string(),
+                severity: "info".to_string(),
+                rationale: "We run hz 20 everywhere".to_string(),
+                recommendation: None,
+                enabled: true,
+                conditions: vec![LintCondition { source: "config".to_string(), field: "hz".to_string(), operator: "lt".to_string(), value: "20".to_string() }],
+            }],
+            disabled_rules: vec!["no-persistence".to_string()],
+        };
+        assert!(validate_settings(&settings).is_ok());
+
+        let (findings, evaluated) = lint(&config, &info, &settings);
+        let ids: Vec<&str> = findings.iter().map(|f| f.rule_id.as_str()).collect();
+        assert_eq!(evaluated, BUILTIN_RULES.len() as u64);
+        assert_eq!(ids[..2], ["noeviction-near-limit", "unprotected-without-password"]);
+        assert!(ids.contains(&"small-repl-backlog"));
+        assert!(ids.contains(&"latency-monitor-off"));
+        assert!(ids.contains(&"low-hz"));
+        assert!(!ids.contains(&"no-persistence"));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::types::{NamespaceInfo, NamespaceNode, NamespaceOptions};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+const DEFAULT_DELIMITERS: &str = ":";
+const DEFAULT_MAX_DEPTH: usize = 4;
+/// Past this many distinct children a node's segment is treated as an ID and
+/// further values are folded into `*`, keeping the tree bounded.
+const MAX_CHILDREN: usize = 100;
+const WILDCARD: &str = "*";
+
+#[derive(Default, Clone)]
+struct TreeNode {
+    key_count: u64,
+    memory_bytes: u64,
+    children: HashMap<String, TreeNode>,
+}
+
+/// Groups keys into a multi-level namespace tree, e.g. `user:123:session` and
+/// `user:456:session` both land under `user` → `user:*` → `user:*:session`.
+#[derive(Clone)]
+pub struct NamespaceTree {
+    delimiters: Vec<char>,
+    max_depth: usize,
+    collapse_ids: bool,
+    root: TreeNode,
+}
+
+impl Default for NamespaceTree {
+    fn default() -> Self {
+        Self::new(&NamespaceOptions::default())
+    }
+}
+
+impl NamespaceTree {
+    pub fn new(options: &NamespaceOptions) -> Self {
+        let delimiters: Vec<char> = options.delimiters.as_deref()
+            .filter(|d| !d.is_empty())
+            .unwrap_or(DEFAULT_DELIMITERS)
+            .chars()
+            .collect();
+
+        Self {
+            delimiters,
+            max_depth: options.max_depth.map(|d| d.max(1) as usize).unwrap_or(DEFAULT_MAX_DEPTH),
+            collapse_ids: options.collapse_ids.unwrap_or(true),
+            root: TreeNode::default(),
+        }
+    }
+
+    pub fn insert(&mut self, key: &str, memory_bytes: u64) {
+        let mut node = &mut self.root;
+        let mut depth = 0;
+        let mut start = 0;
+
+        while depth < self.max_depth {
+            let end = key[start..].find(|c| self.delimiters.contains(&c))
+                .map(|i| start + i)
+                .unwrap_or(key.len());
+            let raw = &key[start..end];
+            // Keep the delimiter that followed the segment so paths can be rebuilt verbatim
+            let delimiter = key[end..].chars().next();
+
+            let mut segment = if self.collapse_ids && is_id_segment(raw) { WILDCARD.to_string() } else { raw.to_string() };
+            if let Some(d) = delimiter {
+                segment.push(d);
+            }
+            if !node.children.contains_key(&segment) && node.children.len() >= MAX_CHILDREN {
+                segment = match delimiter {
+                    Some(d) => format!("{}{}", WILDCARD, d),
+                    None => WILDCARD.to_string(),
+                };
+            }
+
+            node = node.children.entry(segment).or_default();
+            node.key_count += 1;
+            node.memory_bytes += memory_bytes;
+
+            depth += 1;
+            match delimiter {
+                Some(d) if end + d.len_utf8() < key.len() => start = end + d.len_utf8(),
+                _ => break,
+            }
+        }
+    }
+
+    /// First-level namespaces, largest first. A bare `user` key counts
+    /// towards the `user` namespace along with `user:...` keys.
+    pub fn top_level(&self, limit: usize) -> Vec<NamespaceInfo> {
+        let mut merged: HashMap<&str, NamespaceInfo> = HashMap::new();
+        for (segment, node) in &self.root.children {
+            let name = trim_delimiter(segment, &self.delimiters);
+            let info = merged.entry(name).or_insert_with(|| NamespaceInfo {
+                namespace: name.to_string(),
+                key_count: 0,
+                memory_bytes: 0,
+            });
+            info.key_count += node.key_count;
+            info.memory_bytes += node.memory_bytes;
+        }
+        let mut namespaces: Vec<NamespaceInfo> = merged.into_values().collect();
+        namespaces.sort_by_key(|n| Reverse(n.memory_bytes));
+        namespaces.truncate(limit);
+        namespaces
+    }
+
+    pub fn nodes(&self) -> Vec<NamespaceNode> {
+        build_nodes(&self.root, "", &self.delimiters)
+    }
+}
+
+fn build_nodes(node: &TreeNode, prefix: &str, delimiters: &[char]) -> Vec<NamespaceNode> {
+    let mut nodes: Vec<NamespaceNode> = node.children.iter()
+        .map(|(segment, child)| {
+            let full = format!("{}{}", prefix, segment);
+            NamespaceNode {
+                segment: trim_delimiter(segment, delimiters).to_string(),
+                path: trim_delimiter(&full, delimiters).to_string(),
+                key_count: child.key_count,
+                memory_bytes: child.memory_bytes,
+                children: build_nodes(child, &full, delimiters),
+            }
+        })
+        .collect();
+    nodes.sort_by_key(|n| Reverse(n.memory_bytes));
+    nodes
+}
+
+fn trim_delimiter<'a>(s: &'a str, delimiters: &[char]) -> &'a str {
+    match s.chars().last() {
+        Some(c) if delimiters.contains(&c) => &s[..s.len() - c.len_utf8()],
+        _ => s,
+    }
+}
+
+/// Numeric IDs, UUIDs and long hex strings (hashes, object ids).
+fn is_id_segment(segment: &str) -> bool {
+    if segment.is_empty() {
+        return false;
+    }
+    if segment.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    if is_uuid(segment) {
+        return true;
+    }
+    segment.len() >= 8
+        && segment.chars().all(|c| c.is_ascii_hexdigit())
+        && segment.chars().any(|c| c.is_ascii_digit())
+}
+
+fn is_uuid(segment: &str) -> bool {
+    let groups: Vec<&str> = segment.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collapses_id_segments_into_patterns() {
+        let mut tree = NamespaceTree::default();
+        tree.insert("user:123:session", 10);
+        tree.insert("user:456:session", 20);
+        tree.insert("user:550e8400-e29b-41d4-a716-446655440000:cart", 5);
+
+        let nodes = tree.nodes();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].path, "user");
+        assert_eq!(nodes[0].key_count, 3);
+
+        let ids = &nodes[0].children[0];
+        assert_eq!(ids.path, "user:*");
+        assert_eq!(ids.memory_bytes, 35);
+        assert_eq!(ids.children[0].path, "user:*:session");
+        assert_eq!(ids.children[0].key_count, 2);
+    }
+
+    #[test]
+    fn honours_custom_delimiters_and_depth() {
+        let mut tree = NamespaceTree::new(&NamespaceOptions {
+            delimiters: Some("./".to_string()),
+            max_depth: Some(2),
+            collapse_ids: Some(false),
+        });
+        tree.insert("cache.page/home/v2", 1);
+        tree.insert("cache.page/about", 1);
+        tree.insert("plain", 1);
+
+        let top = tree.top_level(10);
+        assert!(top.iter().any(|n| n.namespace == "cache" && n.key_count == 2));
+        assert!(top.iter().any(|n| n.namespace == "plain"));
+
+        let cache = tree.nodes().into_iter().find(|n| n.segment == "cache").unwrap();
+        assert_eq!(cache.children[0].path, "cache.page");
+        assert!(cache.children[0].children.is_empty());
+    }
+
+    #[test]
+    fn merges_leaf_keys_and_caps_children() {
+        let mut tree = NamespaceTree::new(&NamespaceOptions { collapse_ids: Some(false), ..Default::default() });
+        tree.insert("user", 1);
+        tree.insert("user:alice", 2);
+        let top = tree.top_level(10);
+        assert_eq!(top.len(), 1);
+        assert_eq!((top[0].key_count, top[0].memory_bytes), (2, 3));
+
+        for i in 0..MAX_CHILDREN + 10 {
+            tree.insert(&format!("tenant:t{}:config", i), 1);
+        }
+        let tenant = tree.nodes().into_iter().find(|n| n.segment == "tenant").unwrap();
+        assert_eq!(tenant.children.len(), MAX_CHILDREN + 1);
+        assert!(tenant.children.iter().any(|n| n.segment == "*" && n.key_count == 10));
+    }
+}
@@ -0,0 +1,862 @@
+use crate::analysis::AnalysisAccumulator;
+use crate::types::{DatabaseAnalysis, NamespaceOptions};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+const MAX_SUPPORTED_VERSION: u32 = 12;
+
+// Opcodes
+const OPCODE_SLOT_INFO: u8 = 244;
+const OPCODE_FUNCTION2: u8 = 245;
+const OPCODE_FUNCTION_PRE_GA: u8 = 246;
+const OPCODE_MODULE_AUX: u8 = 247;
+const OPCODE_IDLE: u8 = 248;
+const OPCODE_FREQ: u8 = 249;
+const OPCODE_AUX: u8 = 250;
+const OPCODE_RESIZEDB: u8 = 251;
+const OPCODE_EXPIRETIME_MS: u8 = 252;
+const OPCODE_EXPIRETIME: u8 = 253;
+const OPCODE_SELECTDB: u8 = 254;
+const OPCODE_EOF: u8 = 255;
+
+// Object types
+const TYPE_STRING: u8 = 0;
+const TYPE_LIST: u8 = 1;
+const TYPE_SET: u8 = 2;
+const TYPE_ZSET: u8 = 3;
+const TYPE_HASH: u8 = 4;
+const TYPE_ZSET_2: u8 = 5;
+const TYPE_MODULE_PRE_GA: u8 = 6;
+const TYPE_MODULE_2: u8 = 7;
+const TYPE_HASH_ZIPMAP: u8 = 9;
+const TYPE_LIST_ZIPLIST: u8 = 10;
+const TYPE_SET_INTSET: u8 = 11;
+const TYPE_ZSET_ZIPLIST: u8 = 12;
+const TYPE_HASH_ZIPLIST: u8 = 13;
+const TYPE_LIST_QUICKLIST: u8 = 14;
+const TYPE_STREAM_LISTPACKS: u8 = 15;
+const TYPE_HASH_LISTPACK: u8 = 16;
+const TYPE_ZSET_LISTPACK: u8 = 17;
+const TYPE_LIST_QUICKLIST_2: u8 = 18;
+const TYPE_STREAM_LISTPACKS_2: u8 = 19;
+const TYPE_SET_LISTPACK: u8 = 20;
+const TYPE_STREAM_LISTPACKS_3: u8 = 21;
+const TYPE_HASH_METADATA_PRE_GA: u8 = 22;
+const TYPE_HASH_LISTPACK_EX_PRE_GA: u8 = 23;
+const TYPE_HASH_METADATA: u8 = 24;
+const TYPE_HASH_LISTPACK_EX: u8 = 25;
+
+// Special string encodings
+const ENC_INT8: u64 = 0;
+const ENC_INT16: u64 = 1;
+const ENC_INT32: u64 = 2;
+const ENC_LZF: u64 = 3;
+
+const QUICKLIST_NODE_PLAIN: u64 = 1;
+
+/// A decoded value, independent of the on-disk encoding it was stored with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RdbValue {
+    String(Vec<u8>),
+    List(Vec<Vec<u8>>),
+    Set(Vec<Vec<u8>>),
+    ZSet(Vec<(Vec<u8>, f64)>),
+    Hash(Vec<(Vec<u8>, Vec<u8>)>),
+    Stream { length: u64 },
+    Module { module_id: u64 },
+}
+
+impl RdbValue {
+    /// Type name as reported by the Redis TYPE command.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            RdbValue::String(_) => "string",
+            RdbValue::List(_) => "list",
+            RdbValue::Set(_) => "set",
+            RdbValue::ZSet(_) => "zset",
+            RdbValue::Hash(_) => "hash",
+            RdbValue::Stream { .. } => "stream",
+            RdbValue::Module { .. } => "module",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RdbEntry {
+    pub db: u64,
+    pub key: Vec<u8>,
+    pub value: RdbValue,
+    pub expire_at_ms: Option<i64>,
+    pub serialized_size: u64,
+}
+
+struct CountingReader<R: Read> {
+    inner: R,
+    pos: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+/// Streaming RDB reader yielding one key at a time, so multi-gigabyte dumps
+/// never need to be held in memory.
+pub struct RdbParser<R: Read> {
+    reader: CountingReader<R>,
+    version: u32,
+    db: u64,
+    aux: HashMap<String, String>,
+    finished: bool,
+}
+
+impl<R: Read> RdbParser<R> {
+    pub fn new(inner: R) -> Result<Self, String> {
+        let mut reader = CountingReader { inner, pos: 0 };
+        let mut header = [0u8; 9];
+        reader.read_exact(&mut header).map_err(|e| format!("Failed to read RDB header: {}", e))?;
+        if &header[..5] != b"REDIS" {
+            return Err("Not an RDB file (missing REDIS magic)".to_string());
+        }
+        let version: u32 = std::str::from_utf8(&header[5..])
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .ok_or("Invalid RDB version")?;
+        if version == 0 || version > MAX_SUPPORTED_VERSION {
+            return Err(format!("Unsupported RDB version {}", version));
+        }
+
+        Ok(Self { reader, version, db: 0, aux: HashMap::new(), finished: false })
+    }
+
+    /// Snapshot creation time from the `ctime` aux field, in milliseconds.
+    pub fn created_at_ms(&self) -> Option<i64> {
+        self.aux.get("ctime").and_then(|v| v.parse::<i64>().ok()).map(|s| s * 1000)
+    }
+
+    /// Bytes consumed so far, including the header.
+    pub fn position(&self) -> u64 {
+        self.reader.pos
+    }
+
+    /// Returns the underlying reader positioned right after the EOF checksum.
+    /// Used for AOF files that carry an RDB preamble.
+    pub fn into_inner(self) -> R {
+        self.reader.inner
+    }
+
+    pub fn next_entry(&mut self) -> Result<Option<RdbEntry>, String> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let mut expire_at_ms: Option<i64> = None;
+        loop {
+            let opcode = self.read_u8()?;
+            match opcode {
+                OPCODE_AUX => {
+                    let key = self.read_string()?;
+                    let value = self.read_string()?;
+                    self.aux.insert(
+                        String::from_utf8_lossy(&key).to_string(),
+                        String::from_utf8_lossy(&value).to_string(),
+                    );
+                }
+                OPCODE_RESIZEDB => {
+                    self.read_len()?;
+                    self.read_len()?;
+                }
+                OPCODE_SELECTDB => {
+                    self.db = self.read_len()?;
+                }
+                OPCODE_EXPIRETIME => {
+                    let mut buf = [0u8; 4];
+                    self.read_exact(&mut buf)?;
+                    expire_at_ms = Some(u32::from_le_bytes(buf) as i64 * 1000);
+                }
+                OPCODE_EXPIRETIME_MS => {
+                    expire_at_ms = Some(self.read_millis()?);
+                }
+                OPCODE_IDLE => {
+                    self.read_len()?;
+                }
+                OPCODE_FREQ => {
+                    self.read_u8()?;
+                }
+                OPCODE_SLOT_INFO => {
+                    self.read_len()?;
+                    self.read_len()?;
+                    self.read_len()?;
+                }
+                OPCODE_FUNCTION2 => {
+                    self.read_string()?;
+                }
+                OPCODE_FUNCTION_PRE_GA => {
+                    return Err("RDB contains pre-GA function data (Redis 7.0 RC), which is not supported".to_string());
+                }
+                OPCODE_MODULE_AUX => {
+                    self.read_len()?; // module id
+                    self.read_len()?; // when opcode
+                    self.read_len()?; // when
+                    self.skip_module_value()?;
+                }
+                OPCODE_EOF => {
+                    if self.version >= 5 {
+                        let mut checksum = [0u8; 8];
+                        self.read_exact(&mut checksum)?;
+                    }
+                    self.finished = true;
+                    return Ok(None);
+                }
+                value_type => {
+                    let key = self.read_string()?;
+                    let start = self.reader.pos;
+                    let value = self.read_value(value_type)?;
+                    return Ok(Some(RdbEntry {
+                        db: self.db,
+                        key,
+                        value,
+                        expire_at_ms,
+                        serialized_size: self.reader.pos - start,
+                    }));
+                }
+            }
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), String> {
+        self.reader.read_exact(buf).map_err(|e| format!("Unexpected end of RDB data: {}", e))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, String> {
+        let mut buf = [0u8; 1];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn read_bytes(&mut self, len: u64) -> Result<Vec<u8>, String> {
+        // Grow the buffer as data arrives instead of trusting a possibly corrupt length
+        let mut buf = Vec::with_capacity(len.min(64 * 1024) as usize);
+        (&mut self.reader).take(len).read_to_end(&mut buf).map_err(|e| e.to_string())?;
+        if buf.len() as u64 != len {
+            return Err("Unexpected end of RDB data".to_string());
+        }
+        Ok(buf)
+    }
+
+    fn read_millis(&mut self) -> Result<i64, String> {
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)?;
+        Ok(i64::from_le_bytes(buf))
+    }
+
+    fn read_length_with_encoding(&mut self) -> Result<(u64, bool), String> {
+        let first = self.read_u8()?;
+        match first >> 6 {
+            0 => Ok(((first & 0x3F) as u64, false)),
+            1 => {
+                let next = self.read_u8()?;
+                Ok(((((first & 0x3F) as u64) << 8) | next as u64, false))
+            }
+            2 => match first {
+                0x80 => {
+                    let mut buf = [0u8; 4];
+                    self.read_exact(&mut buf)?;
+                    Ok((u32::from_be_bytes(buf) as u64, false))
+                }
+                0x81 => {
+                    let mut buf = [0u8; 8];
+                    self.read_exact(&mut buf)?;
+                    Ok((u64::from_be_bytes(buf), false))
+                }
+                _ => Err(format!("Invalid length encoding 0x{:02x}", first)),
+            },
+            _ => Ok(((first & 0x3F) as u64, true)),
+        }
+    }
+
+    fn read_len(&mut self) -> Result<u64, String> {
+        match self.read_length_with_encoding()? {
+            (len, false) => Ok(len),
+            (_, true) => Err("Unexpected encoded length".to_string()),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<Vec<u8>, String> {
+        let (len, encoded) = self.read_length_with_encoding()?;
+        if !encoded {
+            return self.read_bytes(len);
+        }
+
+        match len {
+            ENC_INT8 => Ok((self.read_u8()? as i8).to_string().into_bytes()),
+            ENC_INT16 => {
+                let mut buf = [0u8; 2];
+                self.read_exact(&mut buf)?;
+                Ok(i16::from_le_bytes(buf).to_string().into_bytes())
+            }
+            ENC_INT32 => {
+                let mut buf = [0u8; 4];
+                self.read_exact(&mut buf)?;
+                Ok(i32::from_le_bytes(buf).to_string().into_bytes())
+            }
+            ENC_LZF => {
+                let compressed_len = self.read_len()?;
+                let uncompressed_len = self.read_len()?;
+                let compressed = self.read_bytes(compressed_len)?;
+                lzf_decompress(&compressed, uncompressed_len as usize)
+            }
+            other => Err(format!("Unknown string encoding {}", other)),
+        }
+    }
+
+    fn read_string_double(&mut self) -> Result<f64, String> {
+        match self.read_u8()? {
+            253 => Ok(f64::NAN),
+            254 => Ok(f64::INFINITY),
+            255 => Ok(f64::NEG_INFINITY),
+            len => {
+                let bytes = self.read_bytes(len as u64)?;
+                parse_score(&bytes)
+            }
+        }
+    }
+
+    fn read_binary_double(&mut self) -> Result<f64, String> {
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)?;
+        Ok(f64::from_le_bytes(buf))
+    }
+
+    fn read_strings(&mut self) -> Result<Vec<Vec<u8>>, String> {
+        let len = self.read_len()?;
+        let mut items = Vec::new();
+        for _ in 0..len {
+            items.push(self.read_string()?);
+        }
+        Ok(items)
+    }
+
+    fn read_value(&mut self, value_type: u8) -> Result<RdbValue, String> {
+        match value_type {
+            TYPE_STRING => Ok(RdbValue::String(self.read_string()?)),
+            TYPE_LIST => Ok(RdbValue::List(self.read_strings()?)),
+            TYPE_SET => Ok(RdbValue::Set(self.read_strings()?)),
+            TYPE_ZSET | TYPE_ZSET_2 => {
+                let len = self.read_len()?;
+                let mut members = Vec::new();
+                for _ in 0..len {
+                    let member = self.read_string()?;
+                    let score = if value_type == TYPE_ZSET_2 {
+                        self.read_binary_double()?
+                    } else {
+                        self.read_string_double()?
+                    };
+                    members.push((member, score));
+                }
+                Ok(RdbValue::ZSet(members))
+            }
+            TYPE_HASH => {
+                let len = self.read_len()?;
+                let mut fields = Vec::new();
+                for _ in 0..len {
+                    let field = self.read_string()?;
+                    let value = self.read_string()?;
+                    fields.push((field, value));
+                }
+                Ok(RdbValue::Hash(fields))
+            }
+            TYPE_HASH_METADATA | TYPE_HASH_METADATA_PRE_GA => {
+                if value_type == TYPE_HASH_METADATA {
+                    self.read_millis()?; // min field expiry
+                }
+                let len = self.read_len()?;
+                let mut fields = Vec::new();
+                for _ in 0..len {
+                    // Per-field TTL: absolute ms before GA, relative to the minimum after
+                    if value_type == TYPE_HASH_METADATA {
+                        self.read_len()?;
+                    } else {
+                        self.read_millis()?;
+                    }
+                    let field = self.read_string()?;
+                    let value = self.read_string()?;
+                    fields.push((field, value));
+                }
+                Ok(RdbValue::Hash(fields))
+            }
+            TYPE_MODULE_2 => {
+                let module_id = self.read_len()?;
+                self.skip_module_value()?;
+                Ok(RdbValue::Module { module_id })
+            }
+            TYPE_MODULE_PRE_GA => Err("Pre-GA module values cannot be parsed without the module".to_string()),
+            TYPE_HASH_ZIPMAP => Ok(RdbValue::Hash(pairs(decode_zipmap(&self.read_string()?)?))),
+            TYPE_LIST_ZIPLIST => Ok(RdbValue::List(decode_ziplist(&self.read_string()?)?)),
+            TYPE_SET_INTSET => Ok(RdbValue::Set(decode_intset(&self.read_string()?)?)),
+            TYPE_SET_LISTPACK => Ok(RdbValue::Set(decode_listpack(&self.read_string()?)?)),
+            TYPE_ZSET_ZIPLIST => Ok(RdbValue::ZSet(scored(decode_ziplist(&self.read_string()?)?)?)),
+            TYPE_ZSET_LISTPACK => Ok(RdbValue::ZSet(scored(decode_listpack(&self.read_string()?)?)?)),
+            TYPE_HASH_ZIPLIST => Ok(RdbValue::Hash(pairs(decode_ziplist(&self.read_string()?)?))),
+            TYPE_HASH_LISTPACK => Ok(RdbValue::Hash(pairs(decode_listpack(&self.read_string()?)?))),
+            TYPE_HASH_LISTPACK_EX | TYPE_HASH_LISTPACK_EX_PRE_GA => {
+                if value_type == TYPE_HASH_LISTPACK_EX {
+                    self.read_millis()?; // min field expiry
+                }
+                // Entries are stored as field, value, ttl triplets
+                let items = decode_listpack(&self.read_string()?)?;
+                let fields = items
+                    .chunks(3)
+                    .filter(|c| c.len() >= 2)
+                    .map(|c| (c[0].clone(), c[1].clone()))
+                    .collect();
+                Ok(RdbValue::Hash(fields))
+            }
+            TYPE_LIST_QUICKLIST | TYPE_LIST_QUICKLIST_2 => {
+                let nodes = self.read_len()?;
+                let mut items = Vec::new();
+                for _ in 0..nodes {
+                    if value_type == TYPE_LIST_QUICKLIST {
+                        items.extend(decode_ziplist(&self.read_string()?)?);
+                        continue;
+                    }
+                    let container = self.read_len()?;
+                    let data = self.read_string()?;
+                    if container == QUICKLIST_NODE_PLAIN {
+                        items.push(data);
+                    } else {
+                        items.extend(decode_listpack(&data)?);
+                    }
+                }
+                Ok(RdbValue::List(items))
+            }
+            TYPE_STREAM_LISTPACKS | TYPE_STREAM_LISTPACKS_2 | TYPE_STREAM_LISTPACKS_3 => {
+                let length = self.skip_stream(value_type)?;
+                Ok(RdbValue::Stream { length })
+            }
+            other => Err(format!("Unknown RDB object type {}", other)),
+        }
+    }
+
+    /// Walks a stream value without decoding entries and returns its length.
+    fn skip_stream(&mut self, value_type: u8) -> Result<u64, String> {
+        let listpacks = self.read_len()?;
+        for _ in 0..listpacks {
+            self.read_string()?; // master ID
+            self.read_string()?; // listpack
+        }
+        let length = self.read_len()?;
+        self.read_len()?; // last id ms
+        self.read_len()?; // last id seq
+        if value_type >= TYPE_STREAM_LISTPACKS_2 {
+            for _ in 0..5 {
+                // first id, max deleted id, entries added
+                self.read_len()?;
+            }
+        }
+
+        let groups = self.read_len()?;
+        for _ in 0..groups {
+            self.read_string()?; // group name
+            self.read_len()?; // last id ms
+            self.read_len()?; // last id seq
+            if value_type >= TYPE_STREAM_LISTPACKS_2 {
+                self.read_len()?; // entries read
+            }
+            let pending = self.read_len()?;
+            for _ in 0..pending {
+                let mut raw_id = [0u8; 16];
+                self.read_exact(&mut raw_id)?;
+                self.read_millis()?; // delivery time
+                self.read_len()?; // delivery count
+            }
+            let consumers = self.read_len()?;
+            for _ in 0..consumers {
+                self.read_string()?; // consumer name
+                self.read_millis()?; // seen time
+                if value_type >= TYPE_STREAM_LISTPACKS_3 {
+                    self.read_millis()?; // active time
+                }
+                let consumer_pending = self.read_len()?;
+                for _ in 0..consumer_pending {
+                    let mut raw_id = [0u8; 16];
+                    self.read_exact(&mut raw_id)?;
+                }
+            }
+        }
+
+        Ok(length)
+    }
+
+    fn skip_module_value(&mut self) -> Result<(), String> {
+        loop {
+            match self.read_len()? {
+                0 => return Ok(()),
+                1 | 2 => { self.read_len()?; }
+                3 => { let mut buf = [0u8; 4]; self.read_exact(&mut buf)?; }
+                4 => { self.read_binary_double()?; }
+                5 => { self.read_string()?; }
+                other => return Err(format!("Unknown module opcode {}", other)),
+            }
+        }
+    }
+}
+
+fn pairs(items: Vec<Vec<u8>>) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut iter = items.into_iter();
+    let mut out = Vec::new();
+    while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
+        out.push((k, v));
+    }
+    out
+}
+
+fn scored(items: Vec<Vec<u8>>) -> Result<Vec<(Vec<u8>, f64)>, String> {
+    pairs(items)
+        .into_iter()
+        .map(|(member, score)| Ok((member, parse_score(&score)?)))
+        .collect()
+}
+
+fn parse_score(bytes: &[u8]) -> Result<f64, String> {
+    let text = String::from_utf8_lossy(bytes);
+    match text.as_ref() {
+        "inf" | "+inf" => Ok(f64::INFINITY),
+        "-inf" => Ok(f64::NEG_INFINITY),
+        _ => text.parse().map_err(|_| format!("Invalid score '{}'", text)),
+    }
+}
+
+fn slice(data: &[u8], pos: usize, len: usize) -> Result<&[u8], String> {
+    data.get(pos..pos + len).ok_or_else(|| "Truncated encoded value".to_string())
+}
+
+fn int_le(bytes: &[u8]) -> i64 {
+    // Sign-extend little endian integers of 1 to 8 bytes
+    let mut buf = if bytes.last().map(|b| b & 0x80 != 0).unwrap_or(false) { [0xFFu8; 8] } else { [0u8; 8] };
+    buf[..bytes.len()].copy_from_slice(bytes);
+    i64::from_le_bytes(buf)
+}
+
+/// A 3-byte LZF back reference expands to at most 264 bytes.
+const LZF_MAX_RATIO: usize = 88;
+
+pub fn lzf_decompress(input: &[u8], expected_len: usize) -> Result<Vec<u8>, String> {
+    if expected_len > input.len().saturating_mul(LZF_MAX_RATIO) {
+        return Err("LZF length exceeds what the compressed data can hold".to_string());
+    }
+    let mut out: Vec<u8> = Vec::with_capacity(expected_len.min(64 * 1024));
+    let mut i = 0;
+    while i < input.len() {
+        let ctrl = input[i] as usize;
+        i += 1;
+        if ctrl < 32 {
+            let run = ctrl + 1;
+            out.extend_from_slice(slice(input, i, run)?);
+            i += run;
+        } else {
+            let mut len = ctrl >> 5;
+            if len == 7 {
+                len += *input.get(i).ok_or("Truncated LZF data")? as usize;
+                i += 1;
+            }
+            let back = ((ctrl & 0x1F) << 8) + *input.get(i).ok_or("Truncated LZF data")? as usize + 1;
+            i += 1;
+            if back > out.len() {
+                return Err("Invalid LZF back reference".to_string());
+            }
+            let start = out.len() - back;
+            for k in 0..len + 2 {
+                out.push(out[start + k]);
+            }
+        }
+    }
+    if out.len() != expected_len {
+        return Err("LZF length mismatch".to_string());
+    }
+    Ok(out)
+}
+
+pub fn decode_intset(data: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let encoding = u32::from_le_bytes(slice(data, 0, 4)?.try_into().unwrap()) as usize;
+    let length = u32::from_le_bytes(slice(data, 4, 4)?.try_into().unwrap()) as usize;
+    if !matches!(encoding, 2 | 4 | 8) {
+        return Err(format!("Invalid intset encoding {}", encoding));
+    }
+    (0..length)
+        .map(|i| Ok(int_le(slice(data, 8 + i * encoding, encoding)?).to_string().into_bytes()))
+        .collect()
+}
+
+pub fn decode_ziplist(data: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let mut items = Vec::new();
+    let mut pos = 10; // zlbytes, zltail, zllen
+    loop {
+        let prev_len_byte = *data.get(pos).ok_or("Truncated ziplist")?;
+        if prev_len_byte == 0xFF {
+            break;
+        }
+        pos += if prev_len_byte == 0xFE { 5 } else { 1 };
+
+        let enc = *data.get(pos).ok_or("Truncated ziplist")?;
+        let item = match enc >> 6 {
+            0 => {
+                let len = (enc & 0x3F) as usize;
+                pos += 1;
+                let s = slice(data, pos, len)?.to_vec();
+                pos += len;
+                s
+            }
+            1 => {
+                let len = (((enc & 0x3F) as usize) << 8) | *data.get(pos + 1).ok_or("Truncated ziplist")? as usize;
+                pos += 2;
+                let s = slice(data, pos, len)?.to_vec();
+                pos += len;
+                s
+            }
+            2 => {
+                let len = u32::from_be_bytes(slice(data, pos + 1, 4)?.try_into().unwrap()) as usize;
+                pos += 5;
+                let s = slice(data, pos, len)?.to_vec();
+                pos += len;
+                s
+            }
+            _ => {
+                pos += 1;
+                let (value, width) = match enc {
+                    0xC0 => (int_le(slice(data, pos, 2)?), 2),
+                    0xD0 => (int_le(slice(data, pos, 4)?), 4),
+                    0xE0 => (int_le(slice(data, pos, 8)?), 8),
+                    0xF0 => (int_le(slice(data, pos, 3)?), 3),
+                    0xFE => (int_le(slice(data, pos, 1)?), 1),
+                    0xF1..=0xFD => ((enc & 0x0F) as i64 - 1, 0),
+                    _ => return Err(format!("Invalid ziplist encoding 0x{:02x}", enc)),
+                };
+                pos += width;
+                value.to_string().into_bytes()
+            }
+        };
+        items.push(item);
+    }
+    Ok(items)
+}
+
+pub fn decode_listpack(data: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let mut items = Vec::new();
+    let mut pos = 6; // total bytes, element count
+    loop {
+        let enc = *data.get(pos).ok_or("Truncated listpack")?;
+        if enc == 0xFF {
+            break;
+        }
+
+        let (item, entry_len) = if enc & 0x80 == 0 {
+            ((enc & 0x7F).to_string().into_bytes(), 1)
+        } else if enc & 0xC0 == 0x80 {
+            let len = (enc & 0x3F) as usize;
+            (slice(data, pos + 1, len)?.to_vec(), 1 + len)
+        } else if enc & 0xE0 == 0xC0 {
+            let raw = (((enc & 0x1F) as i64) << 8) | *data.get(pos + 1).ok_or("Truncated listpack")? as i64;
+            let value = if raw >= 1 << 12 { raw - (1 << 13) } else { raw };
+            (value.to_string().into_bytes(), 2)
+        } else if enc & 0xF0 == 0xE0 {
+            let len = (((enc & 0x0F) as usize) << 8) | *data.get(pos + 1).ok_or("Truncated listpack")? as usize;
+            (slice(data, pos + 2, len)?.to_vec(), 2 + len)
+        } else {
+            match enc {
+                0xF0 => {
+                    let len = u32::from_le_bytes(slice(data, pos + 1, 4)?.try_into().unwrap()) as usize;
+                    (slice(data, pos + 5, len)?.to_vec(), 5 + len)
+                }
+                0xF1 => (int_le(slice(data, pos + 1, 2)?).to_string().into_bytes(), 3),
+                0xF2 => (int_le(slice(data, pos + 1, 3)?).to_string().into_bytes(), 4),
+                0xF3 => (int_le(slice(data, pos + 1, 4)?).to_string().into_bytes(), 5),
+                0xF4 => (int_le(slice(data, pos + 1, 8)?).to_string().into_bytes(), 9),
+                _ => return Err(format!("Invalid listpack encoding 0x{:02x}", enc)),
+            }
+        };
+
+        items.push(item);
+        pos += entry_len + listpack_backlen_size(entry_len);
+    }
+    Ok(items)
+}
+
+fn listpack_backlen_size(entry_len: usize) -> usize {
+    match entry_len {
+        0..=127 => 1,
+        128..=16382 => 2,
+        16383..=2097150 => 3,
+        2097151..=268435454 => 4,
+        _ => 5,
+    }
+}
+
+pub fn decode_zipmap(data: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    let mut items = Vec::new();
+    let mut pos = 1; // zmlen
+    let read_len = |pos: &mut usize| -> Result<Option<usize>, String> {
+        let b = *data.get(*pos).ok_or("Truncated zipmap")?;
+        match b {
+            0xFF => Ok(None),
+            0xFE => {
+                let len = u32::from_le_bytes(slice(data, *pos + 1, 4)?.try_into().unwrap()) as usize;
+                *pos += 5;
+                Ok(Some(len))
+            }
+            _ => {
+                *pos += 1;
+                Ok(Some(b as usize))
+            }
+        }
+    };
+
+    while let Some(key_len) = read_len(&mut pos)? {
+        items.push(slice(data, pos, key_len)?.to_vec());
+        pos += key_len;
+        let value_len = read_len(&mut pos)?.ok_or("Truncated zipmap")?;
+        let free = *data.get(pos).ok_or("Truncated zipmap")? as usize;
+        pos += 1;
+        items.push(slice(data, pos, value_len)?.to_vec());
+        pos += value_len + free;
+    }
+    Ok(items)
+}
+
+/// Builds a `DatabaseAnalysis` from a local RDB file without touching any
+/// server. Memory figures are serialized value sizes, not in-memory usage.
+pub fn analyze_rdb_file(path: &str, namespace_options: &NamespaceOptions) -> Result<DatabaseAnalysis, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let mut parser = RdbParser::new(BufReader::new(file))?;
+    let mut acc = AnalysisAccumulator::with_namespace_options(namespace_options);
+    let now_ms = chrono::Utc::now().timestamp_millis();
+
+    while let Some(entry) = parser.next_entry()? {
+        // TTLs are relative to when the snapshot was taken, not to today
+        let reference_ms = parser.created_at_ms().unwrap_or(now_ms);
+        let ttl = match entry.expire_at_ms {
+            Some(at) => {
+                let remaining_ms = at - reference_ms;
+                if remaining_ms <= 0 {
+                    // Already expired when saved; Redis drops these on load
+                    continue;
+                }
+                (remaining_ms + 999) / 1000
+            }
+            None => -1,
+        };
+        let key = String::from_utf8_lossy(&entry.key);
+        acc.record(&key, entry.value.type_name(), entry.serialized_size, ttl);
+    }
+
+    Ok(acc.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(bytes: &[u8]) -> Vec<u8> {
+        let mut out = vec![bytes.len() as u8];
+        out.extend_from_slice(bytes);
+        out
+    }
+
+    fn sample_rdb() -> Vec<u8> {
+        let mut rdb = b"REDIS0011".to_vec();
+        rdb.push(OPCODE_AUX);
+        rdb.extend(string(b"ctime"));
+        rdb.extend(string(b"1700000000"));
+        rdb.extend([OPCODE_SELECTDB, 0, OPCODE_RESIZEDB, 3, 1]);
+
+        rdb.push(TYPE_STRING);
+        rdb.extend(string(b"user:1:name"));
+        rdb.extend(string(b"alice"));
+
+        // Expires one hour after ctime
+        rdb.push(OPCODE_EXPIRETIME_MS);
+        rdb.extend((1_700_003_600_000i64).to_le_bytes());
+        rdb.push(TYPE_SET_INTSET);
+        rdb.extend(string(b"ids"));
+        rdb.extend(string(&[2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 2, 0]));
+
+        rdb.push(TYPE_HASH_LISTPACK);
+        rdb.extend(string(b"user:1:profile"));
+        rdb.extend(string(&[12, 0, 0, 0, 2, 0, 0x81, b'a', 2, 0x01, 1, 0xFF]));
+
+        rdb.push(OPCODE_EOF);
+        rdb.extend([0u8; 8]);
+        rdb
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let data = sample_rdb();
+        let mut parser = RdbParser::new(data.as_slice()).unwrap();
+        assert_eq!(parser.version, 11);
+
+        let first = parser.next_entry().unwrap().unwrap();
+        assert_eq!(first.key, b"user:1:name");
+        assert_eq!(first.value, RdbValue::String(b"alice".to_vec()));
+        assert_eq!(first.serialized_size, 6);
+
+        let second = parser.next_entry().unwrap().unwrap();
+        assert_eq!(second.value, RdbValue::Set(vec![b"1".to_vec(), b"2".to_vec()]));
+        assert_eq!(second.expire_at_ms, Some(1_700_003_600_000));
+
+        let third = parser.next_entry().unwrap().unwrap();
+        assert_eq!(third.value, RdbValue::Hash(vec![(b"a".to_vec(), b"1".to_vec())]));
+
+        assert!(parser.next_entry().unwrap().is_none());
+        assert_eq!(parser.created_at_ms(), Some(1_700_000_000_000));
+        assert_eq!(parser.position(), data.len() as u64);
+    }
+
+    #[test]
+    fn test_lzf_decompress() {
+        // Literal "abc" followed by a back reference repeating it
+        let compressed = [0x02, b'a', b'b', b'c', 0x20, 0x02];
+        assert_eq!(lzf_decompress(&compressed, 6).unwrap(), b"abcabc");
+        assert!(lzf_decompress(&compressed, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_hash_field_expiry() {
+        let mut rdb = b"REDIS0012".to_vec();
+        rdb.extend([OPCODE_SELECTDB, 0]);
+
+        rdb.push(TYPE_HASH_METADATA_PRE_GA);
+        rdb.extend(string(b"pre"));
+        rdb.push(1);
+        rdb.extend((1_700_000_000_000i64).to_le_bytes());
+        rdb.extend(string(b"f"));
+        rdb.extend(string(b"v"));
+
+        rdb.push(TYPE_HASH_METADATA);
+        rdb.extend(string(b"ga"));
+        rdb.extend((1_700_000_000_000i64).to_le_bytes());
+        rdb.push(1);
+        rdb.push(5);
+        rdb.extend(string(b"f"));
+        rdb.extend(string(b"v"));
+
+        rdb.push(OPCODE_EOF);
+        rdb.extend([0u8; 8]);
+
+        let mut parser = RdbParser::new(rdb.as_slice()).unwrap();
+        let field = vec![(b"f".to_vec(), b"v".to_vec())];
+        assert_eq!(parser.next_entry().unwrap().unwrap().value, RdbValue::Hash(field.clone()));
+        assert_eq!(parser.next_entry().unwrap().unwrap().value, RdbValue::Hash(field));
+        assert!(parser.next_entry().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        assert!(RdbParser::new(b"REDIS0099".as_slice()).is_err());
+        assert!(RdbParser::new(b"NOTREDIS0".as_slice()).is_err());
+    }
+}
@@ -1,24 +1,74 @@
+use crate::acl;
+use crate::acl_monitor::{default_alerts, AclAlertWindow, AclEventStore, AclLogTracker};
+use crate::audit::{redact_command, summarize_reply, AuditLog};
+use crate::analysis::{fetch_key_samples, run_analysis_job, supports_memory_usage, AnalysisAccumulator};
+use crate::bigkeys::run_bigkey_scan;
+use crate::config;
+use crate::commands::{assess_risk, changes_connection_state, denied_by, CommandCatalog, CommandSpec, Confirmations};
+use crate::crypto;
+use crate::bulk::{preview, run_bulk_job, BulkAction, TtlChange};
+use crate::health::{backoff_delay, ping_state, HealthTracker, LOST_AFTER_FAILURES, PING_INTERVAL, PING_TIMEOUT};
+use crate::error::{AppError, ErrorKind};
+use crate::deletion::{delete_command, run_incremental_delete, supports_unlink};
+use crate::jobs::{JobHandle, JobInfo, JobRegistry, JobResults};
+use crate::journal::UndoJournal;
+use crate::latency;
+use crate::lint::{self, LintRuleStore};
+use crate::rename::{preview_rename, RenameRule};
+use crate::slowlog::{self, SlowLogStore, SlowLogTracker};
+use crate::snapshots::{diff_snapshots, summarize, SnapshotStore};
 use crate::types::*;
+use parking_lot::Mutex;
 use redis::aio::MultiplexedConnection;
-use redis::Client;
+use redis::{AsyncConnectionConfig, Client};
 use regex::Regex;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::RwLock;
 
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5_000;
+const DEFAULT_RESPONSE_TIMEOUT_MS: u64 = 10_000;
+const DEFAULT_ACL_POLL_SECS: u64 = 5;
+const ACL_LOG_BATCH: u32 = 128;
+const DEFAULT_SLOWLOG_POLL_SECS: u64 = 10;
+const DEFAULT_SLOWLOG_BATCH: u32 = 128;
+
 #[allow(dead_code)]
 struct RedisConnection {
     client: Client,
     conn: MultiplexedConnection,
     server: RedisServer,
     monitor_stop: Arc<AtomicBool>,
+    /// Whether MONITOR should be restarted after a reconnect.
+    monitor_active: Arc<AtomicBool>,
+    supports_unlink: bool,
+    commands: CommandCatalog,
+    health: Arc<HealthTracker>,
+    health_stop: Arc<AtomicBool>,
+    config: AsyncConnectionConfig,
+    /// Connections to databases other than `server.db`, keyed by index.
+    db_conns: Arc<Mutex<HashMap<u8, MultiplexedConnection>>>,
 }
 
 pub struct RedisManager {
     connections: Arc<RwLock<HashMap<String, RedisConnection>>>,
     config_path: String,
+    jobs: Arc<JobRegistry>,
+    analysis_reports: Arc<RwLock<JobResults<AnalysisReport>>>,
+    bigkey_reports: Arc<RwLock<JobResults<BigKeyScanReport>>>,
+    bulk_results: Arc<RwLock<JobResults<BulkJobProgress>>>,
+    snapshots: SnapshotStore,
+    journal: UndoJournal,
+    audit: Arc<AuditLog>,
+    confirmations: Confirmations,
+    acl_events: Arc<AclEventStore>,
+    /// Running background poller job per (server, kind).
+    pollers: Arc<Mutex<HashMap<(String, &'static str), String>>>,
+    lint_rules: LintRuleStore,
+    slowlog: Arc<SlowLogStore>,
 }
 
 impl RedisManager {
@@ -32,22 +82,80 @@ impl RedisManager {
         Self {
             connections: Arc::new(RwLock::new(HashMap::new())),
             config_path,
+            jobs: Arc::new(JobRegistry::default()),
+            analysis_reports: Arc::new(RwLock::new(JobResults::default())),
+            bigkey_reports: Arc::new(RwLock::new(JobResults::default())),
+            bulk_results: Arc::new(RwLock::new(JobResults::default())),
+            snapshots: SnapshotStore::new(config_dir.join("snapshots")),
+            journal: UndoJournal::new(config_dir.join("undo_journal.jsonl")),
+            audit: Arc::new(AuditLog::new(config_dir.join("audit.log"), crypto::audit_key())),
+            confirmations: Confirmations::default(),
+            acl_events: Arc::new(AclEventStore::new(config_dir.join("acl_events.json"))),
+            pollers: Arc::new(Mutex::new(HashMap::new())),
+            lint_rules: LintRuleStore::new(config_dir.join("lint_rules.json")),
+            slowlog: Arc::new(SlowLogStore::new(config_dir.join("slowlog"))),
         }
     }
 
-    pub async fn get_servers(&self) -> Result<Vec<RedisServer>, String> {
+    // Clones the multiplexed connection so long-running work doesn't hold the map lock
+    async fn get_conn(&self, server_id: &str) -> Result<MultiplexedConnection, AppError> {
+        let connections = self.connections.read().await;
+        let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+        ensure_reachable(redis_conn)?;
+        Ok(redis_conn.conn.clone())
+    }
+
+    // Routes a call to the server's own db, or to a connection dedicated to
+    // `db` opened on first use. SELECT on the shared connection would switch
+    // the db under every other caller.
+    async fn get_db_conn(&self, server_id: &str, db: Option<u8>) -> Result<MultiplexedConnection, AppError> {
+        let (conn, client, config, default_db, db_conns) = {
+            let connections = self.connections.read().await;
+            let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+            ensure_reachable(redis_conn)?;
+            (
+                redis_conn.conn.clone(),
+                redis_conn.client.clone(),
+                redis_conn.config.clone(),
+                redis_conn.server.db.unwrap_or(0),
+                redis_conn.db_conns.clone(),
+            )
+        };
+        let Some(db) = db.filter(|db| *db != default_db) else {
+            return Ok(conn);
+        };
+        if let Some(conn) = db_conns.lock().get(&db) {
+            return Ok(conn.clone());
+        }
+
+        let mut info = client.get_connection_info().clone();
+        info.redis.db = db as i64;
+        let conn = Client::open(info)?.get_multiplexed_async_connection_with_config(&config).await?;
+        db_conns.lock().insert(db, conn.clone());
+        Ok(conn)
+    }
+
+    pub fn list_jobs(&self) -> Vec<JobInfo> {
+        self.jobs.list()
+    }
+
+    pub fn cancel_job(&self, job_id: &str) -> bool {
+        self.jobs.cancel(job_id)
+    }
+
+    pub async fn get_servers(&self) -> Result<Vec<RedisServer>, AppError> {
         match std::fs::read_to_string(&self.config_path) {
-            Ok(content) => serde_json::from_str(&content).map_err(|e| e.to_string()),
+            Ok(content) => serde_json::from_str(&content).map_err(AppError::from),
             Err(_) => Ok(Vec::new()),
         }
     }
 
-    pub async fn save_servers(&self, servers: Vec<RedisServer>) -> Result<(), String> {
-        let content = serde_json::to_string_pretty(&servers).map_err(|e| e.to_string())?;
-        std::fs::write(&self.config_path, content).map_err(|e| e.to_string())
+    pub async fn save_servers(&self, servers: Vec<RedisServer>) -> Result<(), AppError> {
+        let content = serde_json::to_string_pretty(&servers).map_err(AppError::from)?;
+        std::fs::write(&self.config_path, content).map_err(AppError::from)
     }
 
-    pub async fn connect(&self, server: &RedisServer) -> Result<(), String> {
+    pub async fn connect(&self, server: &RedisServer, app: AppHandle) -> Result<(), AppError> {
         let scheme = if server.tls.unwrap_or(false) { "rediss" } else { "redis" };
         
         let url = match (&server.username, &server.password) {
@@ -93,199 +201,246 @@ impl RedisManager {
             }
         };
 
-        let client = Client::open(url.as_str()).map_err(|e| format!("Failed to create client: {}", e))?;
-        let conn = client
-            .get_multiplexed_async_connection()
+        let client = Client::open(url.as_str()).map_err(|e| AppError::from(e).context("Failed to create client"))?;
+        let config = connection_config(server);
+        let mut conn = client
+            .get_multiplexed_async_connection_with_config(&config)
             .await
-            .map_err(|e| format!("Failed to connect: {}", e))?;
-
+            .map_err(|e| match e.is_timeout() {
+                true => AppError::new(
+                    ErrorKind::Timeout,
+                    format!("Timed out connecting after {}ms", server.connect_timeout_ms.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS)),
+                ),
+                false => AppError::from(e).context("Failed to connect"),
+            })?;
+        let supports_unlink = supports_unlink(&mut conn).await;
+
+        let health = Arc::new(HealthTracker::new(&server.id, app.clone()));
+        let health_stop = Arc::new(AtomicBool::new(false));
         let redis_conn = RedisConnection {
             client,
             conn,
             server: server.clone(),
             monitor_stop: Arc::new(AtomicBool::new(false)),
+            monitor_active: Arc::new(AtomicBool::new(false)),
+            supports_unlink,
+            commands: CommandCatalog::default(),
+            health: health.clone(),
+            health_stop: health_stop.clone(),
+            config: config.clone(),
+            db_conns: Arc::new(Mutex::new(HashMap::new())),
         };
 
-        self.connections.write().await.insert(server.id.clone(), redis_conn);
+        // Reconnecting replaces the old entry; stop its background tasks first
+        if let Some(previous) = self.connections.write().await.insert(server.id.clone(), redis_conn) {
+            previous.health_stop.store(true, Ordering::SeqCst);
+            previous.monitor_stop.store(true, Ordering::SeqCst);
+        }
+        health.update(ConnectionState::Ready, None, None, 0);
+        tokio::spawn(watch_connection(self.connections.clone(), server.id.clone(), config, health, health_stop, app));
         Ok(())
     }
 
-    pub async fn disconnect(&self, server_id: &str) -> Result<(), String> {
+    pub async fn disconnect(&self, server_id: &str) -> Result<(), AppError> {
         if let Some(conn) = self.connections.write().await.remove(server_id) {
             conn.monitor_stop.store(true, Ordering::SeqCst);
+            conn.health_stop.store(true, Ordering::SeqCst);
         }
+        self.jobs.cancel_server(server_id);
         Ok(())
     }
 
-    pub async fn get_info(&self, server_id: &str) -> Result<RedisInfo, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections
-            .get_mut(server_id)
-            .ok_or("Server not connected")?;
+    pub async fn list_connection_health(&self) -> Vec<ConnectionHealth> {
+        self.connections.read().await.values().map(|c| c.health.snapshot()).collect()
+    }
+
+    pub async fn get_info(&self, server_id: &str) -> Result<RedisInfo, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         parse_redis_info(&info_str)
     }
 
-    pub async fn get_clients(&self, server_id: &str) -> Result<Vec<ClientInfo>, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections
-            .get_mut(server_id)
-            .ok_or("Server not connected")?;
+    pub async fn list_databases(&self, server_id: &str) -> Result<Vec<DatabaseSummary>, AppError> {
+        let default_db = self.server_db(server_id).await;
+        let mut conn = self.get_conn(server_id).await?;
+
+        let info_str: String = redis::cmd("INFO")
+            .arg("keyspace")
+            .query_async(&mut conn)
+            .await
+            .map_err(AppError::from)?;
+        let keyspace: HashMap<u8, KeyspaceDbInfo> = info_str
+            .lines()
+            .filter_map(|line| line.trim().split_once(':'))
+            .filter_map(|(db, value)| Some((db.strip_prefix("db")?.parse().ok()?, parse_keyspace_db(value)?)))
+            .collect();
+
+        // CONFIG may be renamed or denied by ACL; fall back to the Redis default
+        let configured: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("databases")
+            .query_async(&mut conn)
+            .await
+            .unwrap_or_default();
+        let count = configured.get(1).and_then(|v| v.parse::<u16>().ok()).unwrap_or(16).min(256);
+        let highest = keyspace.keys().copied().max().map_or(0, |db| db as u16 + 1);
+
+        Ok((0..count.max(highest))
+            .map(|index| {
+                let index = index as u8;
+                let info = keyspace.get(&index);
+                DatabaseSummary {
+                    index,
+                    keys: info.map_or(0, |i| i.keys),
+                    expires: info.map_or(0, |i| i.expires),
+                    avg_ttl: info.map_or(0, |i| i.avg_ttl),
+                    is_default: index == default_db,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn get_clients(&self, server_id: &str) -> Result<Vec<ClientInfo>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let client_list: String = redis::cmd("CLIENT")
             .arg("LIST")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         Ok(parse_client_list(&client_list))
     }
 
-    pub async fn start_monitor(&self, server_id: &str, app: AppHandle) -> Result<(), String> {
-        let (server, stop_flag) = {
+    pub async fn start_monitor(&self, server_id: &str, app: AppHandle) -> Result<(), AppError> {
+        let (server, stop_flag, active) = {
             let connections = self.connections.read().await;
-            let redis_conn = connections.get(server_id).ok_or("Server not connected")?;
-            (redis_conn.server.clone(), redis_conn.monitor_stop.clone())
+            let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+            (redis_conn.server.clone(), redis_conn.monitor_stop.clone(), redis_conn.monitor_active.clone())
         };
 
         stop_flag.store(false, Ordering::SeqCst);
-
-        let scheme = if server.tls.unwrap_or(false) { "rediss" } else { "redis" };
-        let url = match (&server.username, &server.password) {
-            (Some(username), Some(password)) => {
-                format!("{}://{}:{}@{}:{}", scheme, urlencoding::encode(username), urlencoding::encode(password), server.host, server.port)
-            }
-            (Some(username), None) => {
-                format!("{}://{}@{}:{}", scheme, urlencoding::encode(username), server.host, server.port)
-            }
-            (None, Some(password)) => {
-                format!("{}://:{}@{}:{}", scheme, urlencoding::encode(password), server.host, server.port)
-            }
-            _ => {
-                format!("{}://{}:{}", scheme, server.host, server.port)
-            }
-        };
-
-        let client = Client::open(url).map_err(|e| e.to_string())?;
-
-        tokio::spawn(async move {
-            let monitor_regex = Regex::new(
-                r#"(\d+\.\d+)\s+\[(\d+)\s+([^\]]+)\]\s+"([^"]+)"(.*)$"#
-            ).unwrap();
-
-            let mut pubsub = match client.get_multiplexed_async_connection().await {
-                Ok(c) => c,
-                Err(_) => return,
-            };
-
-            if redis::cmd("MONITOR")
-                .query_async::<String>(&mut pubsub)
-                .await
-                .is_err()
-            {
-                return;
-            }
-
-            loop {
-                if stop_flag.load(Ordering::SeqCst) {
-                    break;
-                }
-
-                let result: Result<String, _> = redis::cmd("")
-                    .query_async(&mut pubsub)
-                    .await;
-
-                match result {
-                    Ok(line) => {
-                        if let Some(event) = parse_monitor_line(&line, &monitor_regex) {
-                            app.emit("redis-monitor", event).ok();
-                        }
-                    }
-                    Err(_) => {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                    }
-                }
-            }
-        });
-
+        active.store(true, Ordering::SeqCst);
+        spawn_monitor(monitor_client(&server)?, stop_flag, app);
         Ok(())
     }
 
-    pub async fn stop_monitor(&self, server_id: &str) -> Result<(), String> {
+    pub async fn stop_monitor(&self, server_id: &str) -> Result<(), AppError> {
         let connections = self.connections.read().await;
         if let Some(redis_conn) = connections.get(server_id) {
             redis_conn.monitor_stop.store(true, Ordering::SeqCst);
+            redis_conn.monitor_active.store(false, Ordering::SeqCst);
         }
         Ok(())
     }
 
-    pub async fn get_slow_log(&self, server_id: &str, count: u32) -> Result<Vec<SlowLogEntry>, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_slow_log(&self, server_id: &str, count: u32) -> Result<Vec<SlowLogEntry>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let result: Vec<redis::Value> = redis::cmd("SLOWLOG")
             .arg("GET")
             .arg(count)
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         Ok(parse_slow_log(&result))
     }
 
-    pub async fn get_memory_stats(&self, server_id: &str) -> Result<MemoryStats, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    /// Starts copying the slow log into local history before the server's
+    /// ring buffer rotates it away.
+    pub async fn start_slowlog_collector(&self, server_id: &str, interval_secs: Option<u64>, batch_size: Option<u32>) -> Result<String, AppError> {
+        // Fail up front when SLOWLOG is renamed or denied
+        self.get_slow_log(server_id, 1).await?;
+
+        let job = self.start_poller(server_id, "slowlog-collector");
+        let job_id = job.id.clone();
+        tokio::spawn(run_slowlog_collector(
+            self.connections.clone(),
+            job,
+            server_id.to_string(),
+            Duration::from_secs(interval_secs.unwrap_or(DEFAULT_SLOWLOG_POLL_SECS).max(1)),
+            batch_size.unwrap_or(DEFAULT_SLOWLOG_BATCH).max(1),
+            self.slowlog.clone(),
+            self.pollers.clone(),
+        ));
+        Ok(job_id)
+    }
+
+    pub fn stop_slowlog_collector(&self, server_id: &str) -> bool {
+        self.stop_poller(server_id, "slowlog-collector")
+    }
+
+    pub fn get_slowlog_history(&self, server_id: &str, from: Option<u64>, to: Option<u64>, limit: Option<usize>) -> Vec<SlowLogEntry> {
+        let mut entries = self.slowlog.range(server_id, from, to);
+        entries.truncate(limit.unwrap_or(1000));
+        entries
+    }
+
+    pub fn aggregate_slowlog(&self, server_id: &str, from: Option<u64>, to: Option<u64>) -> SlowLogAggregate {
+        let entries = self.slowlog.range(server_id, from, to);
+        SlowLogAggregate {
+            server_id: server_id.to_string(),
+            from,
+            to,
+            total_entries: entries.len() as u64,
+            shapes: slowlog::aggregate(&entries),
+        }
+    }
+
+    pub fn clear_slowlog_history(&self, server_id: &str) -> Result<(), AppError> {
+        self.slowlog.clear(server_id).map_err(AppError::from)
+    }
+
+    pub async fn get_memory_stats(&self, server_id: &str) -> Result<MemoryStats, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let result: Vec<redis::Value> = redis::cmd("MEMORY")
             .arg("STATS")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         parse_memory_stats(&result)
     }
 
-    pub async fn get_memory_doctor(&self, server_id: &str) -> Result<String, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_memory_doctor(&self, server_id: &str) -> Result<String, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let result: String = redis::cmd("MEMORY")
             .arg("DOCTOR")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         Ok(result)
     }
 
-    pub async fn get_command_stats(&self, server_id: &str) -> Result<Vec<CommandStat>, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_command_stats(&self, server_id: &str) -> Result<Vec<CommandStat>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
             .arg("commandstats")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         Ok(parse_command_stats(&info_str))
     }
 
-    pub async fn get_cluster_info(&self, server_id: &str) -> Result<Option<ClusterInfo>, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_cluster_info(&self, server_id: &str) -> Result<Option<ClusterInfo>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
             .arg("cluster")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         if !info_str.contains("cluster_enabled:1") {
             return Ok(None);
@@ -293,79 +448,127 @@ impl RedisManager {
 
         let cluster_info: String = redis::cmd("CLUSTER")
             .arg("INFO")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
             .unwrap_or_default();
 
         Ok(Some(parse_cluster_info(&cluster_info)))
     }
 
-    pub async fn get_cluster_nodes(&self, server_id: &str) -> Result<Vec<ClusterNode>, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_cluster_nodes(&self, server_id: &str) -> Result<Vec<ClusterNode>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let nodes_str: String = redis::cmd("CLUSTER")
             .arg("NODES")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
             .unwrap_or_default();
 
         Ok(parse_cluster_nodes(&nodes_str))
     }
 
-    pub async fn get_persistence_info(&self, server_id: &str) -> Result<PersistenceInfo, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_persistence_info(&self, server_id: &str) -> Result<PersistenceInfo, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
             .arg("persistence")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         Ok(parse_persistence_info(&info_str))
     }
 
-    pub async fn get_cpu_stats(&self, server_id: &str) -> Result<CpuStats, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_cpu_stats(&self, server_id: &str) -> Result<CpuStats, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
             .arg("cpu")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         Ok(parse_cpu_stats(&info_str))
     }
 
-    pub async fn get_error_stats(&self, server_id: &str) -> Result<Vec<ErrorStat>, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_error_stats(&self, server_id: &str) -> Result<Vec<ErrorStat>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
             .arg("errorstats")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
             .unwrap_or_default();
 
         Ok(parse_error_stats(&info_str))
     }
 
-    pub async fn get_latency_doctor(&self, server_id: &str) -> Result<String, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_latency_doctor(&self, server_id: &str) -> Result<String, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let result: String = redis::cmd("LATENCY")
             .arg("DOCTOR")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
             .unwrap_or_else(|_| "Latency monitoring not enabled. Use CONFIG SET latency-monitor-threshold 100".to_string());
 
         Ok(result)
     }
 
-    pub async fn get_advanced_analytics(&self, server_id: &str) -> Result<AdvancedAnalytics, String> {
+    pub async fn get_latency_overview(&self, server_id: &str) -> Result<LatencyOverview, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        let events = latency::latest(&mut conn).await?;
+        let configured: Vec<String> = redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("latency-monitor-threshold")
+            .query_async(&mut conn)
+            .await
+            .unwrap_or_default();
+        let threshold_ms = configured.get(1).and_then(|v| v.parse::<u64>().ok());
+        let monitoring_enabled = threshold_ms.map_or(!events.is_empty(), |t| t > 0);
+        Ok(LatencyOverview {
+            threshold_ms,
+            monitoring_enabled,
+            suggested_threshold_ms: (!monitoring_enabled).then_some(latency::SUGGESTED_THRESHOLD_MS),
+            events,
+        })
+    }
+
+    pub async fn get_latency_history(&self, server_id: &str, event: &str) -> Result<Vec<LatencyHistoryEntry>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        latency::history(&mut conn, event).await
+    }
+
+    pub async fn get_latency_histograms(&self, server_id: &str, commands: &[String]) -> Result<Vec<CommandLatencyHistogram>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        latency::histograms(&mut conn, commands).await
+    }
+
+    /// Clears the given events' history, or all of it; returns how many
+    /// events were reset.
+    pub async fn reset_latency(&self, server_id: &str, events: &[String]) -> Result<u64, AppError> {
+        self.ensure_allowed(server_id, &["LATENCY", "RESET"]).await?;
+        let mut parts = vec!["LATENCY", "RESET"];
+        parts.extend(events.iter().map(|e| e.as_str()));
+        self.audited(server_id, None, &parts, async {
+            let mut conn = self.get_conn(server_id).await?;
+            let reset: u64 = redis::cmd("LATENCY").arg("RESET").arg(events).query_async(&mut conn).await?;
+            Ok(reset)
+        }).await
+    }
+
+    /// Turns the latency monitor on so LATENCY LATEST/HISTORY start
+    /// recording, going through the same checks as the config editor.
+    pub async fn enable_latency_monitor(&self, server_id: &str, threshold_ms: Option<u64>) -> Result<ConfigApplyResult, AppError> {
+        let threshold = threshold_ms.unwrap_or(latency::SUGGESTED_THRESHOLD_MS);
+        if threshold == 0 {
+            return Err(AppError::invalid("The latency threshold must be above 0 ms"));
+        }
+        let update = ConfigUpdate { parameter: "latency-monitor-threshold".to_string(), value: threshold.to_string() };
+        self.apply_config(server_id, &[update], false).await
+    }
+
+    pub async fn get_advanced_analytics(&self, server_id: &str) -> Result<AdvancedAnalytics, AppError> {
         let sid = server_id.to_string();
         
         // Fast analytics - only use INFO commands which are very fast
@@ -404,7 +607,7 @@ impl RedisManager {
         })
     }
 
-    pub async fn get_memory_analytics(&self, server_id: &str) -> Result<(Option<MemoryStats>, Option<String>), String> {
+    pub async fn get_memory_analytics(&self, server_id: &str) -> Result<(Option<MemoryStats>, Option<String>), AppError> {
         let sid = server_id.to_string();
         let (memory_stats, memory_doctor) = tokio::join!(
             self.get_memory_stats(&sid),
@@ -413,13 +616,12 @@ impl RedisManager {
         Ok((memory_stats.ok(), memory_doctor.ok()))
     }
 
-    pub async fn get_latency_analytics(&self, server_id: &str) -> Result<Option<String>, String> {
+    pub async fn get_latency_analytics(&self, server_id: &str) -> Result<Option<String>, AppError> {
         self.get_latency_doctor(server_id).await.map(Some)
     }
 
-    pub async fn scan_keys(&self, server_id: &str, pattern: &str, cursor: &str, count: u32) -> Result<KeyScanResult, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn scan_keys(&self, server_id: &str, db: Option<u8>, pattern: &str, cursor: &str, count: u32) -> Result<KeyScanResult, AppError> {
+        let mut conn = self.get_db_conn(server_id, db).await?;
 
         let pattern = if pattern.is_empty() { "*" } else { pattern };
         
@@ -429,13 +631,13 @@ impl RedisManager {
             .arg(pattern)
             .arg("COUNT")
             .arg(count)
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         // Use pipelining to fetch key info in batches - MUCH faster than sequential calls
         let keys_to_fetch: Vec<&String> = keys.iter().take(100).collect();
-        let key_infos = self.get_key_infos_pipelined(&mut redis_conn.conn, &keys_to_fetch).await?;
+        let key_infos = self.get_key_infos_pipelined(&mut conn, &keys_to_fetch).await?;
 
         Ok(KeyScanResult {
             keys: key_infos,
@@ -446,7 +648,7 @@ impl RedisManager {
     }
 
     // Fetch key info for multiple keys using pipelining - single round trip
-    async fn get_key_infos_pipelined(&self, conn: &mut MultiplexedConnection, keys: &[&String]) -> Result<Vec<KeyInfo>, String> {
+    async fn get_key_infos_pipelined(&self, conn: &mut MultiplexedConnection, keys: &[&String]) -> Result<Vec<KeyInfo>, AppError> {
         if keys.is_empty() {
             return Ok(vec![]);
         }
@@ -462,7 +664,7 @@ impl RedisManager {
         let results: Vec<redis::Value> = pipe
             .query_async(conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         // Parse results - every 2 values is (type, ttl) for one key
         let mut key_infos = Vec::with_capacity(keys.len());
@@ -493,9 +695,8 @@ impl RedisManager {
         Ok(key_infos)
     }
 
-    pub async fn get_key_value(&self, server_id: &str, key: &str) -> Result<KeyValue, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_key_value(&self, server_id: &str, db: Option<u8>, key: &str) -> Result<KeyValue, AppError> {
+        let mut conn = self.get_db_conn(server_id, db).await?;
 
         // Pipeline TYPE and TTL together for speed
         let mut pipe = redis::pipe();
@@ -503,9 +704,9 @@ impl RedisManager {
         pipe.cmd("TTL").arg(key);
         
         let results: Vec<redis::Value> = pipe
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         let key_type = match results.get(0) {
             Some(redis::Value::SimpleString(s)) => s.clone(),
@@ -525,7 +726,7 @@ impl RedisManager {
             "string" => {
                 let v: String = redis::cmd("GET")
                     .arg(key)
-                    .query_async(&mut redis_conn.conn)
+                    .query_async(&mut conn)
                     .await
                     .unwrap_or_default();
                 KeyValueData::String(v)
@@ -535,7 +736,7 @@ impl RedisManager {
                     .arg(key)
                     .arg(0)
                     .arg(999)
-                    .query_async(&mut redis_conn.conn)
+                    .query_async(&mut conn)
                     .await
                     .unwrap_or_default();
                 KeyValueData::List(v)
@@ -543,7 +744,7 @@ impl RedisManager {
             "set" => {
                 let v: Vec<String> = redis::cmd("SMEMBERS")
                     .arg(key)
-                    .query_async(&mut redis_conn.conn)
+                    .query_async(&mut conn)
                     .await
                     .unwrap_or_default();
                 KeyValueData::Set(v)
@@ -554,7 +755,7 @@ impl RedisManager {
                     .arg(0)
                     .arg(999)
                     .arg("WITHSCORES")
-                    .query_async(&mut redis_conn.conn)
+                    .query_async(&mut conn)
                     .await
                     .unwrap_or_default();
                 KeyValueData::ZSet(v.into_iter().map(|(member, score)| ZSetMember { member, score }).collect())
@@ -562,7 +763,7 @@ impl RedisManager {
             "hash" => {
                 let v: HashMap<String, String> = redis::cmd("HGETALL")
                     .arg(key)
-                    .query_async(&mut redis_conn.conn)
+                    .query_async(&mut conn)
                     .await
                     .unwrap_or_default();
                 KeyValueData::Hash(v)
@@ -574,7 +775,7 @@ impl RedisManager {
                     .arg("+")
                     .arg("COUNT")
                     .arg(100)
-                    .query_async(&mut redis_conn.conn)
+                    .query_async(&mut conn)
                     .await
                     .unwrap_or_default();
                 KeyValueData::Stream(parse_stream_entries(&entries))
@@ -591,14 +792,13 @@ impl RedisManager {
         })
     }
 
-    pub async fn get_server_capabilities(&self, server_id: &str) -> Result<ServerCapabilities, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn get_server_capabilities(&self, server_id: &str) -> Result<ServerCapabilities, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
 
         let info_str: String = redis::cmd("INFO")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(AppError::from)?;
 
         let mut map: HashMap<String, String> = HashMap::new();
         let mut total_keys: u64 = 0;
@@ -638,7 +838,7 @@ impl RedisManager {
         
         let cluster_info_result: Result<String, _> = redis::cmd("CLUSTER")
             .arg("INFO")
-            .query_async(&mut redis_conn.conn)
+            .query_async(&mut conn)
             .await;
         
         let (cluster_enabled, cluster_mode) = match cluster_info_result {
@@ -666,13 +866,13 @@ impl RedisManager {
 
         let supports_memory = redis::cmd("MEMORY")
             .arg("DOCTOR")
-            .query_async::<String>(&mut redis_conn.conn)
+            .query_async::<String>(&mut conn)
             .await
             .is_ok();
 
         let supports_latency = redis::cmd("LATENCY")
             .arg("DOCTOR")
-            .query_async::<String>(&mut redis_conn.conn)
+            .query_async::<String>(&mut conn)
             .await
             .is_ok();
 
@@ -694,7 +894,7 @@ impl RedisManager {
         })
     }
 
-    pub async fn check_operation_impact(&self, server_id: &str, operation: &str, pattern: &str) -> Result<PerformanceWarning, String> {
+    pub async fn check_operation_impact(&self, server_id: &str, operation: &str, pattern: &str) -> Result<PerformanceWarning, AppError> {
         let capabilities = self.get_server_capabilities(server_id).await?;
         
         let (level, message, estimated_impact) = match operation {
@@ -748,48 +948,261 @@ impl RedisManager {
         })
     }
 
-    pub async fn delete_key(&self, server_id: &str, key: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    async fn ensure_writable(&self, server_id: &str) -> Result<(), AppError> {
+        let connections = self.connections.read().await;
+        let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+        if redis_conn.server.read_only.unwrap_or(false) {
+            return Err(AppError::new(ErrorKind::Denied, format!("{} is read-only", redis_conn.server.name)).on_server(server_id));
+        }
+        Ok(())
+    }
 
-        let result: i64 = redis::cmd("DEL")
-            .arg(key)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
+    // For app-issued admin commands (CONFIG SET) that bypass guard_command:
+    // honours the read-only flag and the deny list
+    async fn ensure_allowed(&self, server_id: &str, parts: &[&str]) -> Result<(), AppError> {
+        self.ensure_writable(server_id).await?;
+        let connections = self.connections.read().await;
+        let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+        if let Some(entry) = denied_by(parts, redis_conn.server.denied_commands.as_deref().unwrap_or_default()) {
+            return Err(AppError::new(ErrorKind::Denied, format!("{} is denied on {}", entry, redis_conn.server.name)).on_server(server_id));
+        }
+        Ok(())
+    }
+
+    // Checks a command line against the server's deny list and read-only
+    // flag, then holds risky commands back until they come with a valid
+    // confirmation token. Returns the command's spec, if the server could
+    // describe it, and the confirmation to show when held back.
+    async fn guard_command(
+        &self,
+        server_id: &str,
+        parts: &[&str],
+        confirmation_token: Option<&str>,
+    ) -> Result<(Option<CommandSpec>, Option<CommandConfirmation>), AppError> {
+        let (server, catalog, mut conn) = {
+            let connections = self.connections.read().await;
+            let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+            (redis_conn.server.clone(), redis_conn.commands.clone(), redis_conn.conn.clone())
+        };
+        let name = parts[0].to_uppercase();
 
-        Ok(result > 0)
+        if let Some(entry) = denied_by(parts, server.denied_commands.as_deref().unwrap_or_default()) {
+            return Err(AppError::new(ErrorKind::Denied, format!("{} is denied on {}", entry, server.name)).on_server(server_id));
+        }
+        if let Some(entry) = changes_connection_state(parts) {
+            let hint = if entry == "SELECT" { "; pick the database instead" } else { "" };
+            return Err(AppError::invalid(format!("{} would change the shared connection's state{}", entry, hint)).on_server(server_id));
+        }
+
+        let spec = catalog.lookup(&mut conn, parts).await;
+        if server.read_only.unwrap_or(false) {
+            // Commands the server can't describe are refused rather than guessed at
+            let message = match spec.clone()? {
+                Some(spec) if spec.is_read_only() => None,
+                Some(_) => Some(format!("{} is read-only; {} is not a read-only command", server.name, name)),
+                None => Some(format!("{} is read-only and {} could not be verified as read-only", server.name, name)),
+            };
+            if let Some(message) = message {
+                return Err(AppError::new(ErrorKind::Denied, message).on_server(server_id));
+            }
+        }
+
+        let spec = spec.ok().flatten();
+        let total_keys: u64 = redis::cmd("DBSIZE").query_async(&mut conn).await.unwrap_or(0);
+        let reasons = assess_risk(parts, spec.as_ref(), total_keys);
+        if reasons.is_empty() {
+            return Ok((spec, None));
+        }
+
+        let command = parts.join(" ");
+        if confirmation_token.is_some_and(|token| self.confirmations.redeem(token, server_id, &command)) {
+            return Ok((spec, None));
+        }
+        let (token, expires_in_secs) = self.confirmations.issue(server_id, &command);
+        Ok((spec, Some(CommandConfirmation { token, command, reasons, expires_in_secs })))
     }
 
-    pub async fn set_key_ttl(&self, server_id: &str, key: &str, ttl: i64) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    async fn server_db(&self, server_id: &str) -> u8 {
+        self.connections.read().await
+            .get(server_id)
+            .and_then(|c| c.server.db)
+            .unwrap_or(0)
+    }
 
-        let result: i64 = if ttl < 0 {
-            redis::cmd("PERSIST")
-                .arg(key)
-                .query_async(&mut redis_conn.conn)
-                .await
-                .map_err(|e| e.to_string())?
-        } else {
-            redis::cmd("EXPIRE")
+    async fn record_audit(&self, server_id: &str, db: Option<u8>, command: String, outcome: Result<String, String>, started: std::time::Instant) {
+        let db = match db {
+            Some(db) => db,
+            None => self.server_db(server_id).await,
+        };
+        if let Err(e) = self.audit.record(server_id, db, command, outcome, started.elapsed().as_millis() as u64) {
+            log::warn!("Failed to write audit entry: {}", e);
+        }
+    }
+
+    // Runs a write and appends it to the audit log with its outcome
+    async fn audited<T>(&self, server_id: &str, db: Option<u8>, parts: &[&str], op: impl std::future::Future<Output = Result<T, AppError>>) -> Result<T, AppError> {
+        let started = std::time::Instant::now();
+        let result = op.await;
+        let outcome = match &result {
+            Ok(_) => Ok("OK".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        self.record_audit(server_id, db, redact_command(parts, None), outcome, started).await;
+        result
+    }
+
+    pub fn query_audit_log(&self, query: &AuditQuery) -> Vec<AuditEntry> {
+        self.audit.query(query)
+    }
+
+    pub fn verify_audit_log(&self) -> AuditVerification {
+        self.audit.verify()
+    }
+
+    pub fn export_audit_log(&self, query: &AuditQuery, path: &str, format: &str) -> Result<usize, AppError> {
+        self.audit.export(query, path, format)
+    }
+
+    // Snapshots keys into the undo journal; a failed capture never blocks the edit
+    async fn capture_undo(&self, server_id: &str, db: Option<u8>, operation: &str, keys: &[&str]) {
+        // Pin the entry to the db it was captured from, not whatever the
+        // server defaults to when it's restored
+        let db = match db {
+            Some(db) => db,
+            None => self.server_db(server_id).await,
+        };
+        let result = match self.get_db_conn(server_id, Some(db)).await {
+            Ok(mut conn) => self.journal.capture(&mut conn, server_id, Some(db), operation, keys).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            log::warn!("Undo capture for {} failed: {}", operation, e);
+        }
+    }
+
+    pub fn list_undo_entries(&self, server_id: Option<&str>, limit: usize) -> Vec<UndoEntry> {
+        self.journal.list(server_id, limit)
+    }
+
+    pub async fn restore_undo_entry(&self, entry_id: &str) -> Result<Vec<String>, AppError> {
+        let entry = self.journal.get(entry_id).ok_or("Undo entry not found")?;
+        self.ensure_writable(&entry.server_id).await?;
+        let keys: Vec<&str> = entry.keys.iter().map(|k| k.key.as_str()).collect();
+        // Capture the current state first so the restore itself can be undone
+        self.capture_undo(&entry.server_id, entry.db, "RESTORE", &keys).await;
+
+        let mut parts = vec!["RESTORE"];
+        parts.extend(keys.iter().copied());
+        self.audited(&entry.server_id, entry.db, &parts, async {
+            let mut conn = self.get_db_conn(&entry.server_id, entry.db).await?;
+            self.journal.restore(&mut conn, entry_id).await
+        }).await
+    }
+
+    pub async fn delete_key(&self, server_id: &str, db: Option<u8>, key: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "DEL", &[key]).await;
+        self.audited(server_id, db, &["DEL", key], async {
+            let (mut conn, command) = self.get_conn_for_delete(server_id, db).await?;
+
+            let result: i64 = redis::cmd(command)
                 .arg(key)
-                .arg(ttl)
-                .query_async(&mut redis_conn.conn)
+                .query_async(&mut conn)
                 .await
-                .map_err(|e| e.to_string())?
+                .map_err(AppError::from)?;
+
+            Ok(result > 0)
+        }).await
+    }
+
+    pub async fn start_incremental_delete(
+        &self,
+        server_id: &str,
+        db: Option<u8>,
+        key: &str,
+        options: IncrementalDeleteOptions,
+        app: AppHandle,
+    ) -> Result<String, AppError> {
+        self.ensure_writable(server_id).await?;
+        let (conn, delete_cmd) = self.get_conn_for_delete(server_id, db).await?;
+        let job = self.jobs.start(server_id, "incremental-delete");
+        let job_id = job.id.clone();
+        let audit = self.audit.clone();
+        let db = match db {
+            Some(db) => db,
+            None => self.server_db(server_id).await,
         };
+        let server_id = server_id.to_string();
+        let key = key.to_string();
 
-        Ok(result > 0)
+        tokio::spawn(async move {
+            let started = std::time::Instant::now();
+            let progress = run_incremental_delete(conn, &job, &server_id, &key, &options, delete_cmd, &app).await;
+            let outcome = match progress.error {
+                Some(e) => Err(e),
+                None => Ok(format!("{}: {} members removed", progress.status, progress.removed)),
+            };
+            if let Err(e) = audit.record(&server_id, db, format!("INCREMENTAL DELETE {}", key), outcome, started.elapsed().as_millis() as u64) {
+                log::warn!("Failed to write audit entry: {}", e);
+            }
+        });
+
+        Ok(job_id)
     }
 
-    pub async fn execute_command(&self, server_id: &str, command: &str) -> Result<CommandResult, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn set_key_ttl(&self, server_id: &str, db: Option<u8>, key: &str, ttl: i64) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "EXPIRE", &[key]).await;
+        self.audited(server_id, db, &[if ttl < 0 { "PERSIST" } else { "EXPIRE" }, key, &ttl.to_string()], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
+
+            let result: i64 = if ttl < 0 {
+                redis::cmd("PERSIST")
+                    .arg(key)
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(AppError::from)?
+            } else {
+                redis::cmd("EXPIRE")
+                    .arg(key)
+                    .arg(ttl)
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(AppError::from)?
+            };
 
+            Ok(result > 0)
+        }).await
+    }
+
+    pub async fn execute_command(
+        &self,
+        server_id: &str,
+        db: Option<u8>,
+        command: &str,
+        confirmation_token: Option<&str>,
+        timeout_ms: Option<u64>,
+    ) -> Result<CommandResult, AppError> {
         let parts: Vec<&str> = command.split_whitespace().collect();
         if parts.is_empty() {
-            return Err("Empty command".to_string());
+            return Err(AppError::invalid("Empty command"));
+        }
+        let (spec, confirmation) = self.guard_command(server_id, &parts, confirmation_token).await?;
+        if let Some(confirmation) = confirmation {
+            return Ok(CommandResult {
+                success: false,
+                result: String::new(),
+                execution_time_ms: 0,
+                error: Some(format!("Confirmation required: {}", confirmation.reasons.join("; "))),
+                timed_out: false,
+                confirmation: Some(confirmation),
+            });
+        }
+
+        let mut conn = self.get_db_conn(server_id, db).await?;
+        // Long-running admin commands can ask for more (or less) than the server default
+        if let Some(ms) = timeout_ms {
+            conn.set_response_timeout(Duration::from_millis(ms));
         }
 
         let start = std::time::Instant::now();
@@ -798,309 +1211,533 @@ impl RedisManager {
             cmd.arg(*arg);
         }
 
-        let result: Result<redis::Value, _> = cmd.query_async(&mut redis_conn.conn).await;
+        let result: Result<redis::Value, _> = cmd.query_async(&mut conn).await;
         let execution_time_ms = start.elapsed().as_millis() as u64;
 
+        // Reads stay out of the audit log; commands the server couldn't
+        // describe are logged in case they wrote
+        let audit = spec.as_ref().map_or(true, |s| s.is_write() || s.has_flag("admin") || s.has_category("@admin"));
+        if audit {
+            let outcome = match &result {
+                Ok(value) => Ok(summarize_reply(value)),
+                Err(e) => Err(e.to_string()),
+            };
+            self.record_audit(server_id, db, redact_command(&parts, spec.as_ref()), outcome, start).await;
+        }
+
         match result {
             Ok(value) => Ok(CommandResult {
                 success: true,
                 result: format_redis_value(&value),
                 execution_time_ms,
                 error: None,
+                timed_out: false,
+                confirmation: None,
             }),
             Err(e) => Ok(CommandResult {
                 success: false,
                 result: String::new(),
                 execution_time_ms,
-                error: Some(e.to_string()),
+                error: Some(match e.is_timeout() {
+                    true => format!("Timed out after {}ms", execution_time_ms),
+                    false => e.to_string(),
+                }),
+                timed_out: e.is_timeout(),
+                confirmation: None,
             }),
         }
     }
 
-    pub async fn set_string(&self, server_id: &str, key: &str, value: &str, ttl: Option<i64>) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
-
-        let mut cmd = redis::cmd("SET");
-        cmd.arg(key).arg(value);
-        if let Some(t) = ttl {
-            if t > 0 {
-                cmd.arg("EX").arg(t);
+    pub async fn set_string(&self, server_id: &str, db: Option<u8>, key: &str, value: &str, ttl: Option<i64>) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "SET", &[key]).await;
+        self.audited(server_id, db, &["SET", key, value], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
+
+            let mut cmd = redis::cmd("SET");
+            cmd.arg(key).arg(value);
+            if let Some(t) = ttl {
+                if t > 0 {
+                    cmd.arg("EX").arg(t);
+                }
             }
-        }
 
-        let _: String = cmd.query_async(&mut redis_conn.conn).await.map_err(|e| e.to_string())?;
-        Ok(true)
+            let _: String = cmd.query_async(&mut conn).await.map_err(AppError::from)?;
+            Ok(true)
+        }).await
     }
 
-    pub async fn hash_set(&self, server_id: &str, key: &str, field: &str, value: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn hash_set(&self, server_id: &str, db: Option<u8>, key: &str, field: &str, value: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "HSET", &[key]).await;
+        self.audited(server_id, db, &["HSET", key, field, value], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let _: i64 = redis::cmd("HSET")
-            .arg(key).arg(field).arg(value)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(true)
+            let _: i64 = redis::cmd("HSET")
+                .arg(key).arg(field).arg(value)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(true)
+        }).await
     }
 
-    pub async fn hash_delete(&self, server_id: &str, key: &str, field: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn hash_delete(&self, server_id: &str, db: Option<u8>, key: &str, field: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "HDEL", &[key]).await;
+        self.audited(server_id, db, &["HDEL", key, field], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let result: i64 = redis::cmd("HDEL")
-            .arg(key).arg(field)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(result > 0)
+            let result: i64 = redis::cmd("HDEL")
+                .arg(key).arg(field)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result > 0)
+        }).await
     }
 
-    pub async fn list_push(&self, server_id: &str, key: &str, value: &str, position: &str) -> Result<i64, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn list_push(&self, server_id: &str, db: Option<u8>, key: &str, value: &str, position: &str) -> Result<i64, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "PUSH", &[key]).await;
+        self.audited(server_id, db, &[if position == "left" { "LPUSH" } else { "RPUSH" }, key, value], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let cmd = if position == "left" { "LPUSH" } else { "RPUSH" };
-        let result: i64 = redis::cmd(cmd)
-            .arg(key).arg(value)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(result)
+            let cmd = if position == "left" { "LPUSH" } else { "RPUSH" };
+            let result: i64 = redis::cmd(cmd)
+                .arg(key).arg(value)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result)
+        }).await
     }
 
-    pub async fn list_remove(&self, server_id: &str, key: &str, index: i64) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn list_remove(&self, server_id: &str, db: Option<u8>, key: &str, index: i64) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "LREM", &[key]).await;
+        self.audited(server_id, db, &["LREM", key, &index.to_string()], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let placeholder = "__DELETED__";
-        let _: () = redis::cmd("LSET")
-            .arg(key).arg(index).arg(placeholder)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        let _: i64 = redis::cmd("LREM")
-            .arg(key).arg(1).arg(placeholder)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(true)
+            let placeholder = "__DELETED__";
+            let _: () = redis::cmd("LSET")
+                .arg(key).arg(index).arg(placeholder)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            let _: i64 = redis::cmd("LREM")
+                .arg(key).arg(1).arg(placeholder)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(true)
+        }).await
     }
 
-    pub async fn set_add(&self, server_id: &str, key: &str, member: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn set_add(&self, server_id: &str, db: Option<u8>, key: &str, member: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "SADD", &[key]).await;
+        self.audited(server_id, db, &["SADD", key, member], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let result: i64 = redis::cmd("SADD")
-            .arg(key).arg(member)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(result > 0)
+            let result: i64 = redis::cmd("SADD")
+                .arg(key).arg(member)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result > 0)
+        }).await
     }
 
-    pub async fn set_remove(&self, server_id: &str, key: &str, member: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn set_remove(&self, server_id: &str, db: Option<u8>, key: &str, member: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "SREM", &[key]).await;
+        self.audited(server_id, db, &["SREM", key, member], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let result: i64 = redis::cmd("SREM")
-            .arg(key).arg(member)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(result > 0)
+            let result: i64 = redis::cmd("SREM")
+                .arg(key).arg(member)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result > 0)
+        }).await
     }
 
-    pub async fn zset_add(&self, server_id: &str, key: &str, score: f64, member: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn zset_add(&self, server_id: &str, db: Option<u8>, key: &str, score: f64, member: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "ZADD", &[key]).await;
+        self.audited(server_id, db, &["ZADD", key, &score.to_string(), member], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let result: i64 = redis::cmd("ZADD")
-            .arg(key).arg(score).arg(member)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(result >= 0)
+            let result: i64 = redis::cmd("ZADD")
+                .arg(key).arg(score).arg(member)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result >= 0)
+        }).await
     }
 
-    pub async fn zset_remove(&self, server_id: &str, key: &str, member: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn zset_remove(&self, server_id: &str, db: Option<u8>, key: &str, member: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "ZREM", &[key]).await;
+        self.audited(server_id, db, &["ZREM", key, member], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let result: i64 = redis::cmd("ZREM")
-            .arg(key).arg(member)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(result > 0)
+            let result: i64 = redis::cmd("ZREM")
+                .arg(key).arg(member)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result > 0)
+        }).await
     }
 
-    pub async fn bulk_delete(&self, server_id: &str, pattern: &str) -> Result<BulkDeleteResult, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    async fn get_conn_for_delete(&self, server_id: &str, db: Option<u8>) -> Result<(MultiplexedConnection, &'static str), AppError> {
+        let conn = self.get_db_conn(server_id, db).await?;
+        let connections = self.connections.read().await;
+        let redis_conn = connections.get(server_id).ok_or_else(|| AppError::not_connected(server_id))?;
+        Ok((conn, delete_command(redis_conn.supports_unlink)))
+    }
 
-        let start = std::time::Instant::now();
-        let mut deleted_count = 0u64;
-        let mut failed_count = 0u64;
-        let mut errors = Vec::new();
-        let mut cursor = "0".to_string();
+    pub async fn preview_bulk_job(&self, server_id: &str, filter: &BulkKeyFilter, options: &BulkJobOptions) -> Result<BulkPreview, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        preview(&mut conn, filter, options).await
+    }
 
-        loop {
-            let (new_cursor, keys): (String, Vec<String>) = redis::cmd("SCAN")
-                .arg(&cursor)
-                .arg("MATCH").arg(pattern)
-                .arg("COUNT").arg(100)
-                .query_async(&mut redis_conn.conn)
-                .await
-                .map_err(|e| e.to_string())?;
-
-            for key in keys {
-                match redis::cmd("DEL").arg(&key).query_async::<i64>(&mut redis_conn.conn).await {
-                    Ok(n) => deleted_count += n as u64,
-                    Err(e) => {
-                        failed_count += 1;
-                        if errors.len() < 10 {
-                            errors.push(format!("{}: {}", key, e));
-                        }
-                    }
-                }
-            }
+    async fn spawn_bulk_job(
+        &self,
+        server_id: &str,
+        conn: MultiplexedConnection,
+        filter: BulkKeyFilter,
+        options: BulkJobOptions,
+        action: BulkAction,
+        app: AppHandle,
+    ) -> String {
+        let job = self.jobs.start(server_id, &format!("bulk-{}", action.name()));
+        let job_id = job.id.clone();
+        let results = self.bulk_results.clone();
+        let audit = self.audit.clone();
+        let db = self.server_db(server_id).await;
+        let server_id = server_id.to_string();
 
-            cursor = new_cursor;
-            if cursor == "0" {
-                break;
+        tokio::spawn(async move {
+            let started = std::time::Instant::now();
+            let result = run_bulk_job(conn, &job, &server_id, &filter, &options, action, &app).await;
+            let summary = format!(
+                "{}: {} processed, {} skipped, {} failed",
+                result.status, result.processed_count, result.skipped_count, result.failed_count
+            );
+            let outcome = if result.status == "failed" { Err(summary) } else { Ok(summary) };
+            let command = format!("BULK {} {}", result.operation.to_uppercase(), filter.pattern);
+            if let Err(e) = audit.record(&server_id, db, command, outcome, started.elapsed().as_millis() as u64) {
+                log::warn!("Failed to write audit entry: {}", e);
             }
-        }
+            let job_id = result.job_id.clone();
+            results.write().await.insert(&server_id, &job_id, result);
+        });
 
-        Ok(BulkDeleteResult {
-            deleted_count,
-            failed_count,
-            execution_time_ms: start.elapsed().as_millis() as u64,
-            errors,
-        })
+        job_id
     }
 
-    pub async fn analyze_database(&self, server_id: &str, sample_size: u32) -> Result<DatabaseAnalysis, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
-
-        let mut type_counts: HashMap<String, u64> = HashMap::new();
-        let mut type_memory: HashMap<String, u64> = HashMap::new();
-        let mut namespaces: HashMap<String, (u64, u64)> = HashMap::new();
-        let mut top_keys: Vec<KeyMemoryInfo> = Vec::new();
-        let mut keys_with_ttl = 0u64;
-        let mut keys_without_ttl = 0u64;
-        let mut expiring_1h = 0u64;
-        let mut expiring_24h = 0u64;
-        let mut expiring_7d = 0u64;
-        let mut memory_1h = 0u64;
-        let mut memory_24h = 0u64;
-        let mut total_memory = 0u64;
-        let mut cursor = "0".to_string();
-        let mut sampled = 0u32;
+    pub async fn start_bulk_delete(&self, server_id: &str, filter: BulkKeyFilter, options: BulkJobOptions, app: AppHandle) -> Result<String, AppError> {
+        self.ensure_writable(server_id).await?;
+        let (conn, command) = self.get_conn_for_delete(server_id, None).await?;
+        Ok(self.spawn_bulk_job(server_id, conn, filter, options, BulkAction::Delete { command }, app).await)
+    }
 
-        while sampled < sample_size {
-            let (new_cursor, keys): (String, Vec<String>) = redis::cmd("SCAN")
-                .arg(&cursor)
-                .arg("COUNT").arg(100)
-                .query_async(&mut redis_conn.conn)
-                .await
-                .map_err(|e| e.to_string())?;
+    pub async fn start_bulk_ttl(
+        &self,
+        server_id: &str,
+        filter: BulkKeyFilter,
+        operation: BulkTtlOperation,
+        options: BulkJobOptions,
+        app: AppHandle,
+    ) -> Result<String, AppError> {
+        self.ensure_writable(server_id).await?;
+        let change = TtlChange::from_operation(&operation).map_err(AppError::invalid)?;
+        let conn = self.get_conn(server_id).await?;
+        Ok(self.spawn_bulk_job(server_id, conn, filter, options, BulkAction::Ttl(change), app).await)
+    }
 
-            for key in keys {
-                if sampled >= sample_size { break; }
-                sampled += 1;
+    async fn rename_rule(&self, server_id: &str, spec: &BulkRenameSpec) -> Result<RenameRule, AppError> {
+        let cluster = self.get_cluster_info(server_id).await.unwrap_or(None).is_some();
+        RenameRule::new(spec, cluster).map_err(AppError::invalid)
+    }
 
-                let key_type: String = redis::cmd("TYPE").arg(&key)
-                    .query_async(&mut redis_conn.conn).await.unwrap_or_else(|_| "unknown".to_string());
+    pub async fn preview_bulk_rename(&self, server_id: &str, spec: &BulkRenameSpec, options: &BulkJobOptions) -> Result<BulkRenamePreview, AppError> {
+        let rule = self.rename_rule(server_id, spec).await?;
+        let mut conn = self.get_conn(server_id).await?;
+        preview_rename(&mut conn, &rule, options).await
+    }
 
-                let ttl: i64 = redis::cmd("TTL").arg(&key)
-                    .query_async(&mut redis_conn.conn).await.unwrap_or(-1);
+    pub async fn start_bulk_rename(&self, server_id: &str, spec: BulkRenameSpec, options: BulkJobOptions, app: AppHandle) -> Result<String, AppError> {
+        self.ensure_writable(server_id).await?;
+        let rule = self.rename_rule(server_id, &spec).await?;
+        let conn = self.get_conn(server_id).await?;
+        let filter = rule.scan_filter();
+        Ok(self.spawn_bulk_job(server_id, conn, filter, options, BulkAction::Rename(rule), app).await)
+    }
 
-                let mem: u64 = redis::cmd("MEMORY").arg("USAGE").arg(&key).arg("SAMPLES").arg(0)
-                    .query_async(&mut redis_conn.conn).await.unwrap_or(0);
+    pub async fn get_bulk_job_result(&self, job_id: &str) -> Result<Option<BulkJobProgress>, AppError> {
+        Ok(self.bulk_results.read().await.get(job_id).cloned())
+    }
 
-                *type_counts.entry(key_type.clone()).or_insert(0) += 1;
-                *type_memory.entry(key_type.clone()).or_insert(0) += mem;
-                total_memory += mem;
+    pub async fn analyze_database(&self, server_id: &str, sample_size: u32, namespace_options: &NamespaceOptions) -> Result<DatabaseAnalysis, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        let with_memory = supports_memory_usage(&mut conn).await;
 
-                if ttl > 0 {
-                    keys_with_ttl += 1;
-                    if ttl <= 3600 { expiring_1h += 1; memory_1h += mem; }
-                    if ttl <= 86400 { expiring_24h += 1; memory_24h += mem; }
-                    if ttl <= 604800 { expiring_7d += 1; }
-                } else {
-                    keys_without_ttl += 1;
-                }
+        let mut acc = AnalysisAccumulator::with_namespace_options(namespace_options);
+        let mut cursor = "0".to_string();
 
-                let ns = key.split(':').next().unwrap_or(&key).to_string();
-                let entry = namespaces.entry(ns).or_insert((0, 0));
-                entry.0 += 1;
-                entry.1 += mem;
+        while acc.sampled() < sample_size as u64 {
+            let (new_cursor, mut keys): (String, Vec<String>) = redis::cmd("SCAN")
+                .arg(&cursor)
+                .arg("COUNT").arg(100)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
 
-                top_keys.push(KeyMemoryInfo { key: key.clone(), key_type, memory_bytes: mem, ttl });
+            keys.truncate((sample_size as u64 - acc.sampled()) as usize);
+            for sample in fetch_key_samples(&mut conn, &keys, with_memory).await? {
+                acc.record(&sample.key, &sample.key_type, sample.memory, sample.ttl);
             }
 
             cursor = new_cursor;
             if cursor == "0" { break; }
         }
 
-        top_keys.sort_by(|a, b| b.memory_bytes.cmp(&a.memory_bytes));
-        top_keys.truncate(20);
+        Ok(acc.finish())
+    }
 
-        let total_keys = sampled as u64;
-        let type_distribution: Vec<TypeDistribution> = type_counts.iter()
-            .map(|(t, c)| TypeDistribution {
-                key_type: t.clone(),
-                count: *c,
-                percentage: if total_keys > 0 { (*c as f64 / total_keys as f64) * 100.0 } else { 0.0 },
-            }).collect();
+    pub async fn start_analysis(&self, server_id: &str, options: AnalysisOptions, app: AppHandle) -> Result<String, AppError> {
+        let conn = self.get_conn(server_id).await?;
+        let job = self.jobs.start(server_id, "analysis");
+        let job_id = job.id.clone();
+        let reports = self.analysis_reports.clone();
+        let server_id = server_id.to_string();
 
-        let memory_by_type: Vec<TypeMemory> = type_memory.iter()
-            .map(|(t, m)| TypeMemory {
-                key_type: t.clone(),
-                memory_bytes: *m,
-                percentage: if total_memory > 0 { (*m as f64 / total_memory as f64) * 100.0 } else { 0.0 },
-            }).collect();
+        tokio::spawn(async move {
+            let report = run_analysis_job(conn, &job, &server_id, &options, &app).await;
+            let job_id = report.job_id.clone();
+            reports.write().await.insert(&server_id, &job_id, report);
+        });
 
-        let mut ns_vec: Vec<NamespaceInfo> = namespaces.iter()
-            .map(|(n, (c, m))| NamespaceInfo { namespace: n.clone(), key_count: *c, memory_bytes: *m })
+        Ok(job_id)
+    }
+
+    pub async fn get_analysis_report(&self, job_id: &str) -> Result<Option<AnalysisReport>, AppError> {
+        Ok(self.analysis_reports.read().await.get(job_id).cloned())
+    }
+
+    pub async fn list_analysis_reports(&self, server_id: &str) -> Result<Vec<AnalysisReport>, AppError> {
+        let mut reports: Vec<AnalysisReport> = self.analysis_reports.read().await
+            .for_server(server_id)
+            .cloned()
             .collect();
-        ns_vec.sort_by(|a, b| b.memory_bytes.cmp(&a.memory_bytes));
-        ns_vec.truncate(20);
+        reports.sort_by_key(|r| std::cmp::Reverse(r.finished_at));
+        Ok(reports)
+    }
 
-        let mut recommendations = Vec::new();
-        if keys_without_ttl > keys_with_ttl {
-            recommendations.push("Consider setting TTL on keys to prevent memory growth".to_string());
+    pub async fn start_bigkey_scan(&self, server_id: &str, options: BigKeyScanOptions, app: AppHandle) -> Result<String, AppError> {
+        let conn = self.get_conn(server_id).await?;
+        let job = self.jobs.start(server_id, "bigkeys");
+        let job_id = job.id.clone();
+        let reports = self.bigkey_reports.clone();
+        let server_id = server_id.to_string();
+
+        tokio::spawn(async move {
+            let report = run_bigkey_scan(conn, &job, &server_id, &options, &app).await;
+            let job_id = report.job_id.clone();
+            reports.write().await.insert(&server_id, &job_id, report);
+        });
+
+        Ok(job_id)
+    }
+
+    pub async fn get_bigkey_report(&self, job_id: &str) -> Result<Option<BigKeyScanReport>, AppError> {
+        Ok(self.bigkey_reports.read().await.get(job_id).cloned())
+    }
+
+    pub async fn get_config_map(&self, server_id: &str) -> Result<HashMap<String, String>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        redis::cmd("CONFIG")
+            .arg("GET")
+            .arg("*")
+            .query_async(&mut conn)
+            .await
+            .map_err(AppError::from)
+    }
+
+    pub async fn get_config(&self, server_id: &str) -> Result<Vec<ConfigParameter>, AppError> {
+        Ok(config::annotate(&self.get_config_map(server_id).await?))
+    }
+
+    /// Validates and applies `updates` one by one, putting back the earlier
+    /// ones if any fails, then optionally persists them with CONFIG REWRITE.
+    pub async fn apply_config(&self, server_id: &str, updates: &[ConfigUpdate], rewrite: bool) -> Result<ConfigApplyResult, AppError> {
+        if updates.is_empty() {
+            return Err(AppError::invalid("No configuration changes given"));
+        }
+        self.ensure_allowed(server_id, &["CONFIG", "SET"]).await?;
+        if rewrite {
+            self.ensure_allowed(server_id, &["CONFIG", "REWRITE"]).await?;
+        }
+        let problems: Vec<String> = updates.iter()
+            .filter_map(|u| config::validate(&u.parameter, &u.value).err())
+            .collect();
+        if !problems.is_empty() {
+            return Err(AppError::invalid(problems.join("; ")));
         }
-        if let Some(top) = top_keys.first() {
-            if top.memory_bytes > 1024 * 1024 {
-                recommendations.push(format!("Large key detected: {} ({} MB)", top.key, top.memory_bytes / (1024 * 1024)));
+
+        let before = self.get_config_map(server_id).await?;
+        let mut conn = self.get_conn(server_id).await?;
+        for (i, update) in updates.iter().enumerate() {
+            let result = self.audited(server_id, None, &["CONFIG", "SET", &update.parameter, &update.value], async {
+                redis::cmd("CONFIG").arg("SET").arg(&update.parameter).arg(&update.value)
+                    .query_async::<()>(&mut conn)
+                    .await
+                    .map_err(AppError::from)
+            }).await;
+            let Err(e) = result else {
+                continue;
+            };
+            for done in updates[..i].iter().rev() {
+                let Some(old) = before.get(&done.parameter.to_lowercase()) else {
+                    continue;
+                };
+                let restored = self.audited(server_id, None, &["CONFIG", "SET", &done.parameter, old], async {
+                    redis::cmd("CONFIG").arg("SET").arg(&done.parameter).arg(old)
+                        .query_async::<()>(&mut conn)
+                        .await
+                        .map_err(AppError::from)
+                }).await;
+                if let Err(restore_error) = restored {
+                    log::warn!("Could not restore {} after a failed CONFIG SET: {}", done.parameter, restore_error);
+                }
             }
+            return Err(AppError { message: format!("CONFIG SET {} failed: {}", update.parameter, e.message), ..e });
         }
 
-        Ok(DatabaseAnalysis {
-            total_keys,
-            total_memory,
-            type_distribution,
-            memory_by_type,
-            expiry_analysis: ExpiryAnalysis {
-                keys_with_ttl,
-                keys_without_ttl,
-                expiring_in_1h: expiring_1h,
-                expiring_in_24h: expiring_24h,
-                expiring_in_7d: expiring_7d,
-                memory_to_free_1h: memory_1h,
-                memory_to_free_24h: memory_24h,
-            },
-            top_keys_by_memory: top_keys,
-            namespaces: ns_vec,
-            recommendations,
+        let (rewritten, rewrite_error) = if rewrite {
+            let result = self.audited(server_id, None, &["CONFIG", "REWRITE"], async {
+                redis::cmd("CONFIG").arg("REWRITE").query_async::<()>(&mut conn).await.map_err(AppError::from)
+            }).await;
+            match result {
+                Ok(()) => (true, None),
+                Err(e) => (false, Some(e.message)),
+            }
+        } else {
+            (false, None)
+        };
+
+        let after = self.get_config_map(server_id).await?;
+        let changes = updates.iter()
+            .map(|u| {
+                let name = u.parameter.to_lowercase();
+                ConfigChange {
+                    before: before.get(&name).map(|v| config::display_value(&name, v)),
+                    after: after.get(&name).map(|v| config::display_value(&name, v)),
+                    parameter: name,
+                }
+            })
+            .collect();
+        Ok(ConfigApplyResult { changes, rewritten, rewrite_error })
+    }
+
+    pub async fn compare_server_configs(
+        &self,
+        left_server_id: &str,
+        right_server_id: &str,
+        include_instance_specific: bool,
+    ) -> Result<ConfigComparison, AppError> {
+        let left = self.get_config_map(left_server_id).await.map_err(|e| e.on_server(left_server_id))?;
+        let right = self.get_config_map(right_server_id).await.map_err(|e| e.on_server(right_server_id))?;
+        let ignored: Vec<String> = if include_instance_specific {
+            Vec::new()
+        } else {
+            config::INSTANCE_SPECIFIC.iter().map(|p| p.to_string()).collect()
+        };
+        let (differences, matching) = config::compare(&left, &right, &ignored);
+        Ok(ConfigComparison {
+            left_server_id: left_server_id.to_string(),
+            right_server_id: right_server_id.to_string(),
+            differences,
+            matching,
+            ignored,
+        })
+    }
+
+    pub async fn lint_config(&self, server_id: &str) -> Result<LintReport, AppError> {
+        let config = self.get_config_map(server_id).await?;
+        let mut conn = self.get_conn(server_id).await?;
+        let info: String = redis::cmd("INFO").query_async(&mut conn).await?;
+        let (findings, rules_evaluated) = lint::lint(&config, &lint::info_fields(&info), &self.lint_rules.load());
+        Ok(LintReport {
+            server_id: server_id.to_string(),
+            findings,
+            rules_evaluated,
+            created_at: chrono::Utc::now().timestamp_millis() as u64,
         })
     }
 
-    pub async fn analyze_clients(&self, server_id: &str) -> Result<ClientAnalysis, String> {
+    pub fn list_lint_rules(&self) -> Vec<LintRuleInfo> {
+        lint::list_rules(&self.lint_rules.load())
+    }
+
+    pub fn get_lint_settings(&self) -> LintSettings {
+        self.lint_rules.load()
+    }
+
+    pub fn save_lint_settings(&self, settings: &LintSettings) -> Result<(), AppError> {
+        self.lint_rules.save(settings).map_err(AppError::invalid)
+    }
+
+    pub async fn save_snapshot(
+        &self,
+        server_id: &str,
+        label: Option<String>,
+        analysis: Option<DatabaseAnalysis>,
+        analytics: Option<AdvancedAnalytics>,
+    ) -> Result<SnapshotSummary, AppError> {
+        // Command stats are cheap to capture, so take them now if the caller didn't
+        let analytics = match analytics {
+            Some(a) => Some(a),
+            None => self.get_advanced_analytics(server_id).await.ok(),
+        };
+        let snapshot = AnalysisSnapshot {
+            id: uuid::Uuid::new_v4().to_string(),
+            server_id: server_id.to_string(),
+            label: label.filter(|l| !l.trim().is_empty()),
+            created_at: chrono::Utc::now().timestamp_millis() as u64,
+            analysis,
+            analytics,
+            config: self.get_config_map(server_id).await.unwrap_or_default(),
+        };
+        self.snapshots.save(&snapshot)?;
+        Ok(summarize(&snapshot))
+    }
+
+    pub fn list_snapshots(&self, server_id: &str) -> Result<Vec<SnapshotSummary>, AppError> {
+        self.snapshots.list(server_id)
+    }
+
+    pub fn get_snapshot(&self, server_id: &str, snapshot_id: &str) -> Result<AnalysisSnapshot, AppError> {
+        self.snapshots.load(server_id, snapshot_id)
+    }
+
+    pub fn delete_snapshot(&self, server_id: &str, snapshot_id: &str) -> Result<(), AppError> {
+        self.snapshots.delete(server_id, snapshot_id)
+    }
+
+    pub fn compare_snapshots(&self, server_id: &str, from_id: &str, to_id: &str) -> Result<SnapshotDiff, AppError> {
+        let from = self.snapshots.load(server_id, from_id)?;
+        let to = self.snapshots.load(server_id, to_id)?;
+        Ok(diff_snapshots(&from, &to))
+    }
+
+    pub async fn analyze_clients(&self, server_id: &str) -> Result<ClientAnalysis, AppError> {
         let clients = self.get_clients(server_id).await?;
         
         let mut idle_clients = Vec::new();
@@ -1169,6 +1806,26 @@ impl RedisManager {
             });
         }
 
+        // Only known while the ACL monitor is (or recently was) running
+        let since = chrono::Utc::now().timestamp_millis() as u64 - 3_600_000;
+        let failing: Vec<(String, u64)> = self.acl_events.auth_failures_by_ip(server_id, since)
+            .into_iter()
+            .filter(|(_, count)| *count >= 10)
+            .collect();
+        if !failing.is_empty() {
+            suspicious_patterns.push(SuspiciousPattern {
+                pattern_type: "Repeated Auth Failures".to_string(),
+                severity: "critical".to_string(),
+                description: format!(
+                    "{} addresses failed to authenticate at least 10 times in the last hour ({} failures)",
+                    failing.len(),
+                    failing.iter().map(|(_, count)| count).sum::<u64>()
+                ),
+                affected_clients: failing.iter().take(10).map(|(ip, count)| format!("{} ({})", ip, count)).collect(),
+                recommendation: "Check for brute-force attempts or clients with stale credentials".to_string(),
+            });
+        }
+
         let clients_by_command: Vec<CommandClientInfo> = command_map.iter()
             .map(|(cmd, ips)| CommandClientInfo {
                 command: cmd.clone(),
@@ -1187,28 +1844,427 @@ impl RedisManager {
         })
     }
 
-    pub async fn rename_key(&self, server_id: &str, old_key: &str, new_key: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub async fn rename_key(&self, server_id: &str, db: Option<u8>, old_key: &str, new_key: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "RENAME", &[old_key, new_key]).await;
+        self.audited(server_id, db, &["RENAME", old_key, new_key], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
 
-        let _: () = redis::cmd("RENAME")
-            .arg(old_key).arg(new_key)
-            .query_async(&mut redis_conn.conn)
-            .await
-            .map_err(|e| e.to_string())?;
-        Ok(true)
+            let _: () = redis::cmd("RENAME")
+                .arg(old_key).arg(new_key)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(true)
+        }).await
+    }
+
+    pub async fn copy_key(&self, server_id: &str, db: Option<u8>, source: &str, dest: &str) -> Result<bool, AppError> {
+        self.ensure_writable(server_id).await?;
+        self.capture_undo(server_id, db, "COPY", &[dest]).await;
+        self.audited(server_id, db, &["COPY", source, dest], async {
+            let mut conn = self.get_db_conn(server_id, db).await?;
+
+            let result: i64 = redis::cmd("COPY")
+                .arg(source).arg(dest)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(result > 0)
+        }).await
+    }
+
+    pub async fn list_acl_users(&self, server_id: &str) -> Result<Vec<AclUser>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        acl::list_users(&mut conn).await
+    }
+
+    pub async fn get_acl_user(&self, server_id: &str, username: &str) -> Result<Option<AclUser>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        acl::get_user(&mut conn, username).await
+    }
+
+    /// Applies ACL rules (`on`, `>password`, `~keys:*`, `+@read`, ...) to a
+    /// user, creating it if needed.
+    pub async fn set_acl_user(&self, server_id: &str, username: &str, rules: &[String]) -> Result<(), AppError> {
+        self.ensure_allowed(server_id, &["ACL", "SETUSER"]).await?;
+        let mut parts = vec!["ACL", "SETUSER", username];
+        parts.extend(rules.iter().map(|r| r.as_str()));
+        self.audited(server_id, None, &parts, async {
+            let mut conn = self.get_conn(server_id).await?;
+            let _: () = redis::cmd("ACL")
+                .arg("SETUSER")
+                .arg(username)
+                .arg(rules)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(())
+        }).await
+    }
+
+    pub async fn delete_acl_users(&self, server_id: &str, usernames: &[String]) -> Result<u64, AppError> {
+        self.ensure_allowed(server_id, &["ACL", "DELUSER"]).await?;
+        if usernames.is_empty() {
+            return Ok(0);
+        }
+        let mut parts = vec!["ACL", "DELUSER"];
+        parts.extend(usernames.iter().map(|u| u.as_str()));
+        self.audited(server_id, None, &parts, async {
+            let mut conn = self.get_conn(server_id).await?;
+            let deleted: u64 = redis::cmd("ACL")
+                .arg("DELUSER")
+                .arg(usernames)
+                .query_async(&mut conn)
+                .await
+                .map_err(AppError::from)?;
+            Ok(deleted)
+        }).await
+    }
+
+    pub async fn get_acl_categories(&self, server_id: &str, category: Option<&str>) -> Result<Vec<String>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        acl::categories(&mut conn, category).await
+    }
+
+    pub async fn get_acl_log(&self, server_id: &str, count: Option<u32>) -> Result<Vec<AclLogEntry>, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        acl::log(&mut conn, count).await
+    }
+
+    pub async fn acl_dry_run(&self, server_id: &str, username: &str, command: &[String]) -> Result<AclDryRunResult, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        acl::dry_run(&mut conn, username, command).await
+    }
+
+    // Registers a background poller job, replacing one of the same kind
+    // already running against the server so new settings take effect
+    fn start_poller(&self, server_id: &str, kind: &'static str) -> JobHandle {
+        let key = (server_id.to_string(), kind);
+        if let Some(previous) = self.pollers.lock().remove(&key) {
+            self.jobs.cancel(&previous);
+        }
+        let job = self.jobs.start(server_id, kind);
+        self.pollers.lock().insert(key, job.id.clone());
+        job
+    }
+
+    fn stop_poller(&self, server_id: &str, kind: &'static str) -> bool {
+        match self.pollers.lock().remove(&(server_id.to_string(), kind)) {
+            Some(job_id) => self.jobs.cancel(&job_id),
+            None => false,
+        }
+    }
+
+    /// Starts polling the server's ACL LOG into security events.
+    pub async fn start_acl_monitor(
+        &self,
+        server_id: &str,
+        interval_secs: Option<u64>,
+        alerts: Option<Vec<CustomAlert>>,
+        app: AppHandle,
+    ) -> Result<String, AppError> {
+        let mut conn = self.get_conn(server_id).await?;
+        // Fail up front when ACL LOG isn't available rather than in the background
+        acl::log(&mut conn, Some(1)).await?;
+
+        let job = self.start_poller(server_id, "acl-monitor");
+        let job_id = job.id.clone();
+
+        let interval = Duration::from_secs(interval_secs.unwrap_or(DEFAULT_ACL_POLL_SECS).max(1));
+        tokio::spawn(run_acl_monitor(
+            self.connections.clone(),
+            job,
+            server_id.to_string(),
+            interval,
+            alerts.unwrap_or_else(default_alerts),
+            self.acl_events.clone(),
+            self.pollers.clone(),
+            app,
+        ));
+        Ok(job_id)
     }
 
-    pub async fn copy_key(&self, server_id: &str, source: &str, dest: &str) -> Result<bool, String> {
-        let mut connections = self.connections.write().await;
-        let redis_conn = connections.get_mut(server_id).ok_or("Server not connected")?;
+    pub fn stop_acl_monitor(&self, server_id: &str) -> bool {
+        self.stop_poller(server_id, "acl-monitor")
+    }
+
+    pub fn get_acl_events(&self, server_id: &str, since: Option<u64>, limit: Option<usize>) -> Vec<AclSecurityEvent> {
+        self.acl_events.list(server_id, since, limit.unwrap_or(500))
+    }
+}
 
-        let result: i64 = redis::cmd("COPY")
-            .arg(source).arg(dest)
-            .query_async(&mut redis_conn.conn)
+type ConnectionMap = Arc<RwLock<HashMap<String, RedisConnection>>>;
+
+#[allow(clippy::too_many_arguments)]
+async fn run_acl_monitor(
+    connections: ConnectionMap,
+    job: JobHandle,
+    server_id: String,
+    interval: Duration,
+    alerts: Vec<CustomAlert>,
+    store: Arc<AclEventStore>,
+    pollers: Arc<Mutex<HashMap<(String, &'static str), String>>>,
+    app: AppHandle,
+) {
+    let mut tracker = AclLogTracker::default();
+    let mut window = AclAlertWindow::default();
+    while !job.is_cancelled() {
+        // Looked up every poll so the monitor follows reconnects
+        let Some(mut conn) = connections.read().await.get(&server_id).map(|c| c.conn.clone()) else {
+            break;
+        };
+        match acl::log(&mut conn, Some(ACL_LOG_BATCH)).await {
+            Ok(entries) => {
+                // The first poll only records where the log stands; its
+                // entries happened before the monitor started
+                let baseline = !tracker.primed();
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                let events = tracker.observe(&server_id, &entries, now);
+                if !baseline {
+                    for event in &events {
+                        app.emit("acl-security-event", event).ok();
+                    }
+                    for trigger in window.evaluate(&events, &alerts, now) {
+                        app.emit("alert-triggered", &trigger).ok();
+                    }
+                    if let Err(e) = store.append(events) {
+                        log::warn!("Failed to save ACL events: {}", e);
+                    }
+                }
+            }
+            Err(e) => log::warn!("ACL monitor poll failed for {}: {}", server_id, e),
+        }
+
+        wait_for_next_poll(&job, interval).await;
+    }
+    pollers.lock().retain(|_, id| *id != job.id);
+}
+
+async fn run_slowlog_collector(
+    connections: ConnectionMap,
+    job: JobHandle,
+    server_id: String,
+    interval: Duration,
+    batch_size: u32,
+    store: Arc<SlowLogStore>,
+    pollers: Arc<Mutex<HashMap<(String, &'static str), String>>>,
+) {
+    let mut tracker = SlowLogTracker::resume(store.newest(&server_id).as_ref());
+    while !job.is_cancelled() {
+        let Some(mut conn) = connections.read().await.get(&server_id).map(|c| c.conn.clone()) else {
+            break;
+        };
+        let reply: Result<Vec<redis::Value>, _> = redis::cmd("SLOWLOG").arg("GET").arg(batch_size).query_async(&mut conn).await;
+        match reply {
+            Ok(reply) => {
+                let (entries, missed) = tracker.observe(parse_slow_log(&reply));
+                if missed > 0 {
+                    log::warn!("Slow log for {} rotated {} entries away between polls", server_id, missed);
+                }
+                if let Err(e) = store.append(&server_id, &entries) {
+                    log::warn!("Failed to save slow log entries: {}", e);
+                }
+            }
+            Err(e) => log::warn!("Slow log poll failed for {}: {}", server_id, e),
+        }
+        wait_for_next_poll(&job, interval).await;
+    }
+    pollers.lock().retain(|_, id| *id != job.id);
+}
+
+// Sleeps in short steps so cancelling a poller takes effect quickly
+async fn wait_for_next_poll(job: &JobHandle, interval: Duration) {
+    let mut waited = Duration::ZERO;
+    while waited < interval && !job.is_cancelled() {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        waited += Duration::from_secs(1);
+    }
+}
+
+fn connection_config(server: &RedisServer) -> AsyncConnectionConfig {
+    AsyncConnectionConfig::new()
+        .set_connection_timeout(Duration::from_millis(server.connect_timeout_ms.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS)))
+        .set_response_timeout(Duration::from_millis(server.response_timeout_ms.unwrap_or(DEFAULT_RESPONSE_TIMEOUT_MS)))
+}
+
+fn ensure_reachable(redis_conn: &RedisConnection) -> Result<(), AppError> {
+    match redis_conn.health.state() {
+        ConnectionState::Lost | ConnectionState::Connecting => {
+            Err(AppError::new(ErrorKind::ConnectionLost, format!("Connection to {} was lost; reconnecting", redis_conn.server.name))
+                .on_server(&redis_conn.server.id))
+        }
+        _ => Ok(()),
+    }
+}
+
+// MONITOR gets its own connection without a db in the URL
+fn monitor_client(server: &RedisServer) -> Result<Client, AppError> {
+    let scheme = if server.tls.unwrap_or(false) { "rediss" } else { "redis" };
+    let url = match (&server.username, &server.password) {
+        (Some(username), Some(password)) => {
+            format!("{}://{}:{}@{}:{}", scheme, urlencoding::encode(username), urlencoding::encode(password), server.host, server.port)
+        }
+        (Some(username), None) => {
+            format!("{}://{}@{}:{}", scheme, urlencoding::encode(username), server.host, server.port)
+        }
+        (None, Some(password)) => {
+            format!("{}://:{}@{}:{}", scheme, urlencoding::encode(password), server.host, server.port)
+        }
+        _ => {
+            format!("{}://{}:{}", scheme, server.host, server.port)
+        }
+    };
+
+    Client::open(url).map_err(AppError::from)
+}
+
+fn spawn_monitor(client: Client, stop_flag: Arc<AtomicBool>, app: AppHandle) {
+    tokio::spawn(async move {
+        let monitor_regex = Regex::new(
+            r#"(\d+\.\d+)\s+\[(\d+)\s+([^\]]+)\]\s+"([^"]+)"(.*)$"#
+        ).unwrap();
+
+        let mut pubsub = match client.get_multiplexed_async_connection().await {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        if redis::cmd("MONITOR")
+            .query_async::<String>(&mut pubsub)
             .await
-            .map_err(|e| e.to_string())?;
-        Ok(result > 0)
+            .is_err()
+        {
+            return;
+        }
+
+        loop {
+            if stop_flag.load(Ordering::SeqCst) {
+                break;
+            }
+
+            let result: Result<String, _> = redis::cmd("")
+                .query_async(&mut pubsub)
+                .await;
+
+            match result {
+                Ok(line) => {
+                    if let Some(event) = parse_monitor_line(&line, &monitor_regex) {
+                        app.emit("redis-monitor", event).ok();
+                    }
+                }
+                Err(_) => {
+                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                }
+            }
+        }
+    });
+}
+
+/// Pings a connection in the background until it is disconnected. Slow or
+/// failed pings mark it degraded; repeated failures mark it lost and start
+/// reconnecting with exponential backoff.
+async fn watch_connection(
+    connections: ConnectionMap,
+    server_id: String,
+    config: AsyncConnectionConfig,
+    health: Arc<HealthTracker>,
+    stop: Arc<AtomicBool>,
+    app: AppHandle,
+) {
+    let mut failures = 0u32;
+    loop {
+        tokio::time::sleep(PING_INTERVAL).await;
+        if stop.load(Ordering::SeqCst) {
+            return;
+        }
+        let Some(mut conn) = connections.read().await.get(&server_id).map(|c| c.conn.clone()) else {
+            return;
+        };
+
+        let started = std::time::Instant::now();
+        let error = match tokio::time::timeout(PING_TIMEOUT, redis::cmd("PING").query_async::<String>(&mut conn)).await {
+            Ok(Ok(_)) => {
+                failures = 0;
+                let latency_ms = started.elapsed().as_millis() as u64;
+                health.update(ping_state(latency_ms), Some(latency_ms), None, 0);
+                continue;
+            }
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => "PING timed out".to_string(),
+        };
+
+        failures += 1;
+        if failures < LOST_AFTER_FAILURES {
+            health.update(ConnectionState::Degraded, None, Some(error), 0);
+            continue;
+        }
+        health.update(ConnectionState::Lost, None, Some(error), 0);
+        if !reconnect(&connections, &server_id, &config, &health, &stop, &app).await {
+            return;
+        }
+        failures = 0;
+    }
+}
+
+// Retries until a new connection is up (true) or the server is disconnected
+// (false), then swaps it into the map and restarts MONITOR if it was running
+async fn reconnect(
+    connections: &ConnectionMap,
+    server_id: &str,
+    config: &AsyncConnectionConfig,
+    health: &HealthTracker,
+    stop: &AtomicBool,
+    app: &AppHandle,
+) -> bool {
+    let Some(client) = connections.read().await.get(server_id).map(|c| c.client.clone()) else {
+        return false;
+    };
+    let mut attempt = 0u32;
+    loop {
+        tokio::time::sleep(backoff_delay(attempt)).await;
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        attempt += 1;
+        health.update(ConnectionState::Connecting, None, None, attempt);
+
+        let conn = match client.get_multiplexed_async_connection_with_config(config).await {
+            Ok(conn) => conn,
+            Err(e) => {
+                health.update(ConnectionState::Lost, None, Some(e.to_string()), attempt);
+                continue;
+            }
+        };
+
+        let monitor = {
+            let mut connections = connections.write().await;
+            let Some(redis_conn) = connections.get_mut(server_id) else {
+                return false;
+            };
+            redis_conn.conn = conn;
+            // The restarted server may have a different command table
+            redis_conn.commands = CommandCatalog::default();
+            // Per-db connections died with the old one; they reopen on next use
+            redis_conn.db_conns.lock().clear();
+            if redis_conn.monitor_active.load(Ordering::SeqCst) {
+                // The old MONITOR loop keeps its flag and exits; the new one gets a fresh flag
+                redis_conn.monitor_stop.store(true, Ordering::SeqCst);
+                redis_conn.monitor_stop = Arc::new(AtomicBool::new(false));
+                Some((redis_conn.server.clone(), redis_conn.monitor_stop.clone()))
+            } else {
+                None
+            }
+        };
+        if let Some((server, stop_flag)) = monitor {
+            match monitor_client(&server) {
+                Ok(monitor) => spawn_monitor(monitor, stop_flag, app.clone()),
+                Err(e) => log::warn!("Could not restart MONITOR for {}: {}", server.name, e),
+            }
+        }
+
+        health.update(ConnectionState::Ready, None, None, 0);
+        return true;
     }
 }
 
@@ -1280,7 +2336,7 @@ fn parse_stream_entries(entries: &[redis::Value]) -> Vec<StreamEntry> {
     }).collect()
 }
 
-fn parse_redis_info(info: &str) -> Result<RedisInfo, String> {
+fn parse_redis_info(info: &str) -> Result<RedisInfo, AppError> {
     let mut map: HashMap<String, String> = HashMap::new();
     let mut keyspace: HashMap<String, KeyspaceDbInfo> = HashMap::new();
 
@@ -1473,7 +2529,7 @@ fn parse_slow_log(entries: &[redis::Value]) -> Vec<SlowLogEntry> {
     }).collect()
 }
 
-fn parse_memory_stats(values: &[redis::Value]) -> Result<MemoryStats, String> {
+fn parse_memory_stats(values: &[redis::Value]) -> Result<MemoryStats, AppError> {
     let mut map: HashMap<String, i64> = HashMap::new();
     let mut iter = values.iter();
     
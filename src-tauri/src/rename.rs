@@ -0,0 +1,360 @@
+use crate::bulk::{scan_step, PREVIEW_SCAN_LIMIT};
+use crate::error::AppError;
+use crate::types::*;
+use redis::aio::MultiplexedConnection;
+use regex::Regex;
+
+const PREVIEW_SAMPLE_SIZE: usize = 20;
+const DEFAULT_BATCH_SIZE: u32 = 500;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConflictMode {
+    Skip,
+    Overwrite,
+    Fail,
+}
+
+/// CRC16/XMODEM as used by Redis Cluster for key slots.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &b in data {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Hash slot of a key, honouring `{hash tags}`.
+pub fn key_hash_slot(key: &[u8]) -> u16 {
+    let hashed = match key.iter().position(|&c| c == b'{') {
+        Some(open) => match key[open + 1..].iter().position(|&c| c == b'}') {
+            Some(len) if len > 0 => &key[open + 1..open + 1 + len],
+            _ => key,
+        },
+        None => key,
+    };
+    crc16(hashed) % 16384
+}
+
+/// Converts a Redis glob into an anchored regex where every `*` and `?` is a
+/// capture group, so `legacy:*` → `v2:*` can carry the wildcard across.
+fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let mut chars = glob.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => re.push_str("(.*)"),
+            '?' => re.push_str("(.)"),
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    re.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            '[' => {
+                re.push('[');
+                for class_char in chars.by_ref() {
+                    if class_char == ']' {
+                        break;
+                    }
+                    if class_char == '\\' || class_char == '[' {
+                        re.push('\\');
+                    }
+                    re.push(class_char);
+                }
+                re.push(']');
+            }
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    re
+}
+
+/// Rewrites the `*` and `?` in a glob-mode replacement into numbered
+/// captures, counted in order like the groups `glob_to_regex` produces.
+fn glob_template(replacement: &str) -> String {
+    let mut group = 0;
+    replacement.chars().fold(String::new(), |mut out, c| {
+        match c {
+            '*' | '?' => {
+                group += 1;
+                out.push_str(&format!("${{{}}}", group));
+            }
+            '$' => out.push_str("$$"),
+            _ => out.push(c),
+        }
+        out
+    })
+}
+
+/// Longest literal prefix of an anchored regex, used to narrow the SCAN.
+fn regex_scan_pattern(source: &str) -> String {
+    let Some(body) = source.strip_prefix('^') else {
+        return "*".to_string();
+    };
+    // `^a|b` only anchors the first alternative
+    if has_top_level_alternation(body) {
+        return "*".to_string();
+    }
+    let mut prefix: Vec<char> = body.chars().take_while(|c| !".^$*+?()[]{}|\\".contains(*c)).collect();
+    // `users?` or `a{0,2}` make the last literal optional
+    if matches!(body.chars().nth(prefix.len()), Some('?' | '*' | '{')) {
+        prefix.pop();
+    }
+    let escaped: String = prefix.into_iter().fold(String::new(), |mut out, c| {
+        if "*?[]\\".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+        out
+    });
+    format!("{}*", escaped)
+}
+
+fn has_top_level_alternation(pattern: &str) -> bool {
+    let mut depth = 0usize;
+    let mut in_class = false;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => depth += 1,
+            ')' if !in_class => depth = depth.saturating_sub(1),
+            '|' if !in_class && depth == 0 => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+pub struct RenameRule {
+    matcher: Regex,
+    template: String,
+    scan_pattern: String,
+    pub conflict: ConflictMode,
+    cluster: bool,
+}
+
+impl RenameRule {
+    pub fn new(spec: &BulkRenameSpec, cluster: bool) -> Result<Self, String> {
+        if spec.source.is_empty() {
+            return Err("Source pattern is required".to_string());
+        }
+        let (matcher, template, scan_pattern) = if spec.regex.unwrap_or(false) {
+            let matcher = Regex::new(&spec.source).map_err(|e| format!("Invalid regex: {}", e))?;
+            (matcher, spec.replacement.clone(), regex_scan_pattern(&spec.source))
+        } else {
+            let matcher = Regex::new(&glob_to_regex(&spec.source)).map_err(|e| format!("Invalid pattern: {}", e))?;
+            (matcher, glob_template(&spec.replacement), spec.source.clone())
+        };
+        let conflict = match spec.on_conflict.as_deref().unwrap_or("skip") {
+            "skip" => ConflictMode::Skip,
+            "overwrite" => ConflictMode::Overwrite,
+            "fail" => ConflictMode::Fail,
+            other => return Err(format!("Unknown conflict mode: {}", other)),
+        };
+        Ok(Self { matcher, template, scan_pattern, conflict, cluster })
+    }
+
+    pub fn scan_filter(&self) -> BulkKeyFilter {
+        BulkKeyFilter { pattern: self.scan_pattern.clone(), ..Default::default() }
+    }
+
+    pub fn target(&self, key: &str) -> Option<String> {
+        if !self.matcher.is_match(key) {
+            return None;
+        }
+        let target = self.matcher.replace(key, self.template.as_str()).to_string();
+        (target != key).then_some(target)
+    }
+
+    /// A target that matches the source again would be picked up by a later
+    /// SCAN page and renamed twice.
+    fn rematches(&self, target: &str) -> bool {
+        self.matcher.is_match(target)
+    }
+
+    fn cross_slot(&self, from: &str, to: &str) -> bool {
+        self.cluster && key_hash_slot(from.as_bytes()) != key_hash_slot(to.as_bytes())
+    }
+
+    /// Renames the keys in a batch that are safe to rename and returns how
+    /// many were renamed. In `Fail` mode any existing target aborts the batch
+    /// before anything is touched.
+    pub async fn apply(&self, conn: &mut MultiplexedConnection, keys: &[String]) -> Result<u64, AppError> {
+        let plans: Vec<(&String, String)> = keys.iter()
+            .filter_map(|k| self.target(k).map(|t| (k, t)))
+            .filter(|(k, t)| !self.rematches(t) && !self.cross_slot(k, t))
+            .collect();
+        if plans.is_empty() {
+            return Ok(0);
+        }
+
+        let mut exists_pipe = redis::pipe();
+        for (_, target) in &plans {
+            exists_pipe.cmd("EXISTS").arg(target);
+        }
+        let exists: Vec<bool> = exists_pipe.query_async(conn).await?;
+
+        if self.conflict == ConflictMode::Fail {
+            if let Some(((from, to), _)) = plans.iter().zip(&exists).find(|(_, e)| **e) {
+                return Err(format!("Cannot rename {} to {}: target already exists", from, to).into());
+            }
+        }
+
+        let plans: Vec<&(&String, String)> = plans.iter()
+            .zip(&exists)
+            .filter(|(_, e)| self.conflict == ConflictMode::Overwrite || !**e)
+            .map(|(p, _)| p)
+            .collect();
+        let command = if self.conflict == ConflictMode::Overwrite { "RENAME" } else { "RENAMENX" };
+
+        let mut pipe = redis::pipe();
+        for (from, to) in &plans {
+            pipe.cmd(command).arg(*from).arg(to);
+        }
+        match pipe.query_async::<Vec<redis::Value>>(conn).await {
+            Ok(results) => Ok(results.iter().filter(|v| renamed(v)).count() as u64),
+            // A source key that vanished since SCAN fails the whole pipeline;
+            // retry one by one so the rest of the batch still goes through
+            Err(_) => {
+                let mut renamed_count = 0;
+                for (from, to) in plans {
+                    if let Ok(v) = redis::cmd(command).arg(*from).arg(to).query_async::<redis::Value>(conn).await {
+                        if renamed(&v) {
+                            renamed_count += 1;
+                        }
+                    }
+                }
+                Ok(renamed_count)
+            }
+        }
+    }
+}
+
+fn renamed(value: &redis::Value) -> bool {
+    matches!(value, redis::Value::Okay | redis::Value::Int(1))
+}
+
+/// Dry run for a bulk rename: what would be renamed, into what, and which
+/// renames would conflict or cross hash slots.
+pub async fn preview_rename(conn: &mut MultiplexedConnection, rule: &RenameRule, options: &BulkJobOptions) -> Result<BulkRenamePreview, AppError> {
+    let batch_size = options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).clamp(10, 10_000);
+    let limit = options.max_keys.unwrap_or(u64::MAX);
+    let filter = rule.scan_filter();
+    let mut preview = BulkRenamePreview {
+        scanned_count: 0,
+        matched_count: 0,
+        conflict_count: 0,
+        cross_slot_count: 0,
+        rematch_count: 0,
+        cluster_enabled: rule.cluster,
+        samples: Vec::new(),
+        capped: false,
+        partial: false,
+    };
+    let mut cursor = "0".to_string();
+
+    loop {
+        let (next, scanned, keys) = scan_step(conn, &cursor, &filter, batch_size).await?;
+        preview.scanned_count += scanned;
+
+        let plans: Vec<(String, String)> = keys.into_iter()
+            .filter_map(|k| rule.target(&k).map(|t| (k, t)))
+            .collect();
+        if !plans.is_empty() {
+            let mut pipe = redis::pipe();
+            for (_, target) in &plans {
+                pipe.cmd("EXISTS").arg(target);
+            }
+            let exists: Vec<bool> = pipe.query_async(conn).await?;
+
+            for ((from, to), target_exists) in plans.into_iter().zip(exists) {
+                let cross_slot = rule.cross_slot(&from, &to);
+                preview.matched_count += 1;
+                preview.conflict_count += target_exists as u64;
+                preview.cross_slot_count += cross_slot as u64;
+                preview.rematch_count += rule.rematches(&to) as u64;
+                if preview.samples.len() < PREVIEW_SAMPLE_SIZE {
+                    preview.samples.push(RenamePlanItem { from, to, target_exists, cross_slot });
+                }
+            }
+        }
+
+        if preview.matched_count >= limit {
+            preview.capped = true;
+            break;
+        }
+        cursor = next;
+        if cursor == "0" {
+            break;
+        }
+        if preview.scanned_count >= PREVIEW_SCAN_LIMIT {
+            preview.partial = true;
+            break;
+        }
+    }
+
+    Ok(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(source: &str, replacement: &str, regex: bool) -> RenameRule {
+        let spec = BulkRenameSpec {
+            source: source.to_string(),
+            regex: Some(regex),
+            replacement: replacement.to_string(),
+            on_conflict: None,
+        };
+        RenameRule::new(&spec, false).unwrap()
+    }
+
+    #[test]
+    fn glob_and_regex_targets() {
+        let glob = rule("legacy:*", "v2:*", false);
+        assert_eq!(glob.target("legacy:user:1").as_deref(), Some("v2:user:1"));
+        assert_eq!(glob.target("other:user:1"), None);
+        assert_eq!(glob.scan_filter().pattern, "legacy:*");
+
+        let regex = rule(r"^legacy:(\w+):(\d+)$", "v2:$2:$1", true);
+        assert_eq!(regex.target("legacy:user:42").as_deref(), Some("v2:42:user"));
+        assert_eq!(regex.scan_filter().pattern, "legacy:*");
+
+        assert_eq!(regex_scan_pattern("^users?:"), "user*");
+        assert_eq!(regex_scan_pattern("^a|b"), "*");
+        assert_eq!(regex_scan_pattern("^(a|b):x"), "*");
+        assert_eq!(regex_scan_pattern("^ab[|]c"), "ab*");
+    }
+
+    #[test]
+    fn glob_wildcards_keep_their_order() {
+        let mixed = rule("a?:*", "b?:*", false);
+        assert_eq!(mixed.target("ax:foo").as_deref(), Some("bx:foo"));
+        let positional = rule("*:?", "?-*", false);
+        assert_eq!(positional.target("user:7").as_deref(), Some("user-7"));
+        let dollar = rule("price:*", "$:*", false);
+        assert_eq!(dollar.target("price:5").as_deref(), Some("$:5"));
+    }
+
+    #[test]
+    fn detects_rematching_targets() {
+        let nested = rule("a:*", "a:b:*", false);
+        assert!(nested.rematches(&nested.target("a:1").unwrap()));
+    }
+
+    #[test]
+    fn hash_slots_follow_hash_tags() {
+        assert_eq!(key_hash_slot(b"foo"), 12182);
+        assert_eq!(key_hash_slot(b"{user1000}.following"), key_hash_slot(b"{user1000}.followers"));
+        assert_eq!(key_hash_slot(b"foo{}bar"), crc16(b"foo{}bar") % 16384);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::types::*;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::PathBuf;
+
+const MAX_ENTRIES_PER_SERVER: usize = 20_000;
+const TOP_CLIENTS: usize = 5;
+
+/// Container commands whose subcommand is part of the shape.
+const CONTAINER_COMMANDS: &[&str] = &[
+    "ACL", "CLIENT", "CLUSTER", "COMMAND", "CONFIG", "DEBUG", "FUNCTION", "LATENCY", "MEMORY",
+    "MODULE", "OBJECT", "PUBSUB", "SCRIPT", "SLOWLOG", "XGROUP", "XINFO",
+];
+
+/// Option tokens kept verbatim so `SET ? EX ?` and `SET ? NX` stay apart.
+const KEYWORDS: &[&str] = &[
+    "EX", "PX", "EXAT", "PXAT", "NX", "XX", "GT", "LT", "CH", "INCR", "KEEPTTL", "GET", "PERSIST",
+    "MATCH", "COUNT", "TYPE", "NOVALUES", "WITHSCORES", "WITHSCORE", "WITHVALUES", "LIMIT", "BYSCORE",
+    "BYLEX", "REV", "LEFT", "RIGHT", "BEFORE", "AFTER", "ASC", "DESC", "ALPHA", "STORE", "BY",
+    "WEIGHTS", "AGGREGATE", "SUM", "MIN", "MAX", "MAXLEN", "MINID", "NOMKSTREAM", "BLOCK", "STREAMS",
+    "GROUP", "NOACK", "JUSTID", "FORCE", "IDLE", "RETRYCOUNT", "REPLACE", "ABSTTL", "FREQ", "COPY",
+    "AUTH", "AUTH2", "KEYS", "FIELDS", "LEN", "IDX",
+];
+
+/// The command with every argument replaced by `?`, except subcommands and
+/// option keywords. Runs of arguments collapse into one placeholder so
+/// `MGET a b` and `MGET a b c` share a shape.
+pub fn normalize_command(command: &str, args: &[String]) -> String {
+    let name = command.to_uppercase();
+    let mut shape = vec![name.clone()];
+    let mut rest = args;
+    if CONTAINER_COMMANDS.contains(&name.as_str()) {
+        if let Some((sub, tail)) = args.split_first() {
+            shape.push(sub.to_uppercase());
+            rest = tail;
+        }
+    }
+    for arg in rest {
+        let upper = arg.to_uppercase();
+        if KEYWORDS.contains(&upper.as_str()) {
+            shape.push(upper);
+        } else if shape.last().map(|s| s.as_str()) != Some("?") {
+            shape.push("?".to_string());
+        }
+    }
+    shape.join(" ")
+}
+
+/// Picks out entries not seen yet from successive `SLOWLOG GET` replies.
+/// Ids only grow while the server runs, so anything above the last id is
+/// new; ids that go backwards mean the server restarted, and then entries
+/// newer than the last one seen count as new.
+#[derive(Default)]
+pub struct SlowLogTracker {
+    last_id: Option<u64>,
+    last_timestamp: u64,
+}
+
+impl SlowLogTracker {
+    /// Resumes after the newest stored entry so a restarted collector
+    /// doesn't store entries twice.
+    pub fn resume(last: Option<&SlowLogEntry>) -> Self {
+        Self { last_id: last.map(|e| e.id), last_timestamp: last.map_or(0, |e| e.timestamp) }
+    }
+
+    /// Returns new entries oldest first, and how many rotated out of the
+    /// ring buffer between polls.
+    pub fn observe(&mut self, mut entries: Vec<SlowLogEntry>) -> (Vec<SlowLogEntry>, u64) {
+        entries.sort_by_key(|e| e.id);
+        let (Some(first), Some(newest)) = (entries.first(), entries.last()) else {
+            return (Vec::new(), 0);
+        };
+        let (first_id, newest_id) = (first.id, newest.id);
+        // Ids restart at 0 with the server; a restart that has already
+        // logged past the old ids shows up as an old id with a newer time
+        let restarted = self.last_id.is_some_and(|last| {
+            newest_id < last || (first_id <= last && first.timestamp > self.last_timestamp)
+        });
+
+        let (new, missed): (Vec<SlowLogEntry>, u64) = match self.last_id {
+            Some(last) if !restarted => {
+                let missed = first_id.saturating_sub(last + 1);
+                (entries.into_iter().filter(|e| e.id > last).collect(), missed)
+            }
+            Some(_) => {
+                let since = self.last_timestamp;
+                (entries.into_iter().filter(|e| e.timestamp >= since).collect(), 0)
+            }
+            None => (entries, 0),
+        };
+        if let Some(last) = new.last() {
+            self.last_id = Some(last.id);
+            self.last_timestamp = last.timestamp;
+        } else if self.last_id.is_some_and(|last| newest_id < last) {
+            // Restarted and nothing logged since: follow the new ids
+            self.last_id = Some(newest_id);
+        }
+        (new, missed)
+    }
+}
+
+/// Collected slow log entries, one JSON-lines file per server under
+/// `<config dir>/slowlog/`.
+pub struct SlowLogStore {
+    dir: PathBuf,
+    cache: Mutex<HashMap<String, VecDeque<SlowLogEntry>>>,
+}
+
+impl SlowLogStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, cache: Mutex::new(HashMap::new()) }
+    }
+
+    fn path(&self, server_id: &str) -> PathBuf {
+        let name: String = server_id.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.jsonl", name))
+    }
+
+    fn with_entries<T>(&self, server_id: &str, f: impl FnOnce(&mut VecDeque<SlowLogEntry>) -> T) -> T {
+        let mut cache = self.cache.lock();
+        let entries = cache.entry(server_id.to_string()).or_insert_with(|| {
+            std::fs::read_to_string(self.path(server_id))
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        });
+        f(entries)
+    }
+
+    pub fn newest(&self, server_id: &str) -> Option<SlowLogEntry> {
+        self.with_entries(server_id, |entries| entries.back().cloned())
+    }
+
+    pub fn append(&self, server_id: &str, new_entries: &[SlowLogEntry]) -> Result<(), String> {
+        if new_entries.is_empty() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let path = self.path(server_id);
+        self.with_entries(server_id, |entries| {
+            entries.extend(new_entries.iter().cloned());
+            // Appending is the common case; the file is only rewritten once
+            // it has grown a quarter past the cap
+            if entries.len() > MAX_ENTRIES_PER_SERVER + MAX_ENTRIES_PER_SERVER / 4 {
+                let excess = entries.len() - MAX_ENTRIES_PER_SERVER;
+                entries.drain(..excess);
+                let content: String = entries.iter()
+                    .filter_map(|e| serde_json::to_string(e).ok())
+                    .map(|line| line + "\n")
+                    .collect();
+                return std::fs::write(&path, content).map_err(|e| e.to_string());
+            }
+            let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path).map_err(|e| e.to_string())?;
+            for entry in new_entries {
+                let line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+                writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Entries with `from <= timestamp <= to` (unix seconds), newest first.
+    pub fn range(&self, server_id: &str, from: Option<u64>, to: Option<u64>) -> Vec<SlowLogEntry> {
+        self.with_entries(server_id, |entries| {
+            entries.iter().rev()
+                .filter(|e| from.map_or(true, |from| e.timestamp >= from) && to.map_or(true, |to| e.timestamp <= to))
+                .cloned()
+                .collect()
+        })
+    }
+
+    pub fn clear(&self, server_id: &str) -> Result<(), String> {
+        self.cache.lock().remove(server_id);
+        match std::fs::remove_file(self.path(server_id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Nearest-rank percentile of sorted durations
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Groups entries by normalized shape, most total time first.
+pub fn aggregate(entries: &[SlowLogEntry]) -> Vec<SlowLogShape> {
+    let mut groups: HashMap<String, Vec<&SlowLogEntry>> = HashMap::new();
+    for entry in entries {
+        groups.entry(normalize_command(&entry.command, &entry.args)).or_default().push(entry);
+    }
+
+    let mut shapes: Vec<SlowLogShape> = groups.into_iter()
+        .map(|(shape, group)| {
+            let mut durations: Vec<u64> = group.iter().map(|e| e.duration_us).collect();
+            durations.sort_unstable();
+
+            // Clients reconnect on new ports, so count by host
+            let mut clients: HashMap<String, u64> = HashMap::new();
+            for entry in &group {
+                let addr = entry.client_addr.as_deref().unwrap_or_default();
+                let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host);
+                let client = match &entry.client_name {
+                    Some(name) => format!("{} ({})", host, name),
+                    None => host.to_string(),
+                };
+                *clients.entry(client).or_default() += 1;
+            }
+            let mut top_clients: Vec<SlowLogClientCount> = clients.into_iter()
+                .map(|(client, count)| SlowLogClientCount { client, count })
+                .collect();
+            top_clients.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.client.cmp(&b.client)));
+            top_clients.truncate(TOP_CLIENTS);
+
+            let slowest = group.iter().max_by_key(|e| e.duration_us).unwrap();
+            SlowLogShape {
+                shape,
+                count: group.len() as u64,
+                total_duration_us: durations.iter().sum(),
+                p50_duration_us: percentile(&durations, 50.0),
+                p99_duration_us: percentile(&durations, 99.0),
+                max_duration_us: durations.last().copied().unwrap_or(0),
+                first_seen: group.iter().map(|e| e.timestamp).min().unwrap_or(0),
+                last_seen: group.iter().map(|e| e.timestamp).max().unwrap_or(0),
+                slowest_example: std::iter::once(slowest.command.clone()).chain(slowest.args.iter().cloned()).collect::<Vec<_>>().join(" "),
+                top_clients,
+            }
+        })
+        .collect();
+    shapes.sort_by_key(|s| std::cmp::Reverse(s.total_duration_us));
+    shapes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u64, timestamp: u64, duration_us: u64, command: &str, client: &str) -> SlowLogEntry {
+        let mut parts = command.split_whitespace().map(String::from);
+        SlowLogEntry {
+            id,
+            timestamp,
+            duration_us,
+            command: parts.next().unwrap(),
+            args: parts.collect(),
+            client_addr: Some(client.to_string()),
+            client_name: None,
+        }
+    }
+
+    #[test]
+    fn normalizes_command_shapes() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(normalize_command("set", &args("user:1 hello EX 60")), "SET ? EX ?");
+        assert_eq!(normalize_command("MGET", &args("a b c")), "MGET ?");
+        assert_eq!(normalize_command("config", &args("get maxmemory")), "CONFIG GET ?");
+        assert_eq!(normalize_command("keys", &args("*")), "KEYS ?");
+    }
+
+    #[test]
+    fn dedupes_across_polls_and_restarts() {
+        let mut tracker = SlowLogTracker::default();
+        let (new, _) = tracker.observe(vec![entry(2, 100, 10, "GET a", "1.1.1.1:1"), entry(1, 90, 10, "GET a", "1.1.1.1:1")]);
+        assert_eq!(new.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2]);
+
+        let (new, missed) = tracker.observe(vec![entry(6, 130, 10, "GET a", "1.1.1.1:1"), entry(5, 120, 10, "GET a", "1.1.1.1:1")]);
+        assert_eq!(new.len(), 2);
+        assert_eq!(missed, 2);
+
+        // Server restarted: ids start over
+        let (new, _) = tracker.observe(vec![entry(0, 200, 10, "GET a", "1.1.1.1:1")]);
+        assert_eq!(new.len(), 1);
+        assert!(tracker.observe(vec![entry(0, 200, 10, "GET a", "1.1.1.1:1")]).0.is_empty());
+
+        // Restarted and already logged past the old newest id
+        let mut tracker = SlowLogTracker::resume(Some(&entry(2, 100, 10, "GET a", "1.1.1.1:1")));
+        let restarted: Vec<SlowLogEntry> = (0..4).map(|id| entry(id, 300 + id, 10, "GET a", "1.1.1.1:1")).collect();
+        let (new, missed) = tracker.observe(restarted);
+        assert_eq!(new.iter().map(|e| e.id).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(missed, 0);
+    }
+
+    #[test]
+    fn aggregates_by_shape_with_percentiles() {
+        let entries: Vec<SlowLogEntry> = (1..=100)
+            .map(|i| entry(i, i, i * 1000, &format!("HGETALL user:{}", i), if i % 4 == 0 { "10.0.0.2:5000" } else { "10.0.0.1:5000" }))
+            .chain(std::iter::once(entry(101, 101, 50, "KEYS *", "10.0.0.3:1")))
+            .collect();
+        let shapes = aggregate(&entries);
+        assert_eq!(shapes[0].shape, "HGETALL ?");
+        assert_eq!(shapes[0].count, 100);
+        assert_eq!(shapes[0].p50_duration_us, 50_000);
+        assert_eq!(shapes[0].p99_duration_us, 99_000);
+        assert_eq!(shapes[0].top_clients[0].client, "10.0.0.1");
+        assert_eq!(shapes[0].top_clients[0].count, 75);
+        assert_eq!(shapes[1].shape, "KEYS ?");
+    }
+}
@@ -0,0 +1,292 @@
+use crate::config;
+use crate::error::AppError;
+use crate::types::*;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+const MAX_NAMESPACE_CHANGES: usize = 50;
+
+/// Stores analysis snapshots as one JSON file each under
+/// `<config dir>/snapshots/<server id>/`.
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn server_dir(&self, server_id: &str) -> PathBuf {
+        self.dir.join(sanitize(server_id))
+    }
+
+    pub fn save(&self, snapshot: &AnalysisSnapshot) -> Result<(), AppError> {
+        let dir = self.server_dir(&snapshot.server_id);
+        std::fs::create_dir_all(&dir)?;
+        let content = serde_json::to_string(&mask_secrets(snapshot.clone()))?;
+        std::fs::write(dir.join(format!("{}.json", sanitize(&snapshot.id))), content).map_err(AppError::from)
+    }
+
+    pub fn load(&self, server_id: &str, snapshot_id: &str) -> Result<AnalysisSnapshot, AppError> {
+        let path = self.server_dir(server_id).join(format!("{}.json", sanitize(snapshot_id)));
+        let content = std::fs::read_to_string(&path).map_err(|_| format!("Snapshot {} not found", snapshot_id))?;
+        // Snapshots saved before masking still hold the raw values
+        serde_json::from_str(&content).map(mask_secrets).map_err(AppError::from)
+    }
+
+    pub fn delete(&self, server_id: &str, snapshot_id: &str) -> Result<(), AppError> {
+        let path = self.server_dir(server_id).join(format!("{}.json", sanitize(snapshot_id)));
+        std::fs::remove_file(path).map_err(AppError::from)
+    }
+
+    /// Newest first.
+    pub fn list(&self, server_id: &str) -> Result<Vec<SnapshotSummary>, AppError> {
+        let entries = match std::fs::read_dir(self.server_dir(server_id)) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut summaries: Vec<SnapshotSummary> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+            .filter_map(|content| serde_json::from_str::<AnalysisSnapshot>(&content).ok())
+            .map(|s| summarize(&s))
+            .collect();
+        summaries.sort_by_key(|s| Reverse(s.created_at));
+        Ok(summaries)
+    }
+}
+
+// Passwords never reach disk or the UI; a changed one shows as unchanged
+fn mask_secrets(mut snapshot: AnalysisSnapshot) -> AnalysisSnapshot {
+    for (name, value) in snapshot.config.iter_mut() {
+        *value = config::display_value(name, value);
+    }
+    snapshot
+}
+
+// Server and snapshot ids end up in file names
+fn sanitize(id: &str) -> String {
+    id.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+pub fn summarize(snapshot: &AnalysisSnapshot) -> SnapshotSummary {
+    SnapshotSummary {
+        id: snapshot.id.clone(),
+        server_id: snapshot.server_id.clone(),
+        label: snapshot.label.clone(),
+        created_at: snapshot.created_at,
+        total_keys: snapshot.analysis.as_ref().map(|a| a.total_keys),
+        total_memory: snapshot.analysis.as_ref().map(|a| a.total_memory),
+        has_analytics: snapshot.analytics.is_some(),
+    }
+}
+
+/// Compares two snapshots; `from` is treated as the baseline.
+pub fn diff_snapshots(from: &AnalysisSnapshot, to: &AnalysisSnapshot) -> SnapshotDiff {
+    let empty = DatabaseAnalysis {
+        total_keys: 0,
+        total_memory: 0,
+        type_distribution: vec![],
+        memory_by_type: vec![],
+        expiry_analysis: ExpiryAnalysis {
+            keys_with_ttl: 0,
+            keys_without_ttl: 0,
+            expiring_in_1h: 0,
+            expiring_in_24h: 0,
+            expiring_in_7d: 0,
+            memory_to_free_1h: 0,
+            memory_to_free_24h: 0,
+        },
+        top_keys_by_memory: vec![],
+        namespaces: vec![],
+        namespace_tree: vec![],
+        recommendations: vec![],
+    };
+    let before = from.analysis.as_ref().unwrap_or(&empty);
+    let after = to.analysis.as_ref().unwrap_or(&empty);
+
+    SnapshotDiff {
+        from: summarize(from),
+        to: summarize(to),
+        total_keys_delta: after.total_keys as i64 - before.total_keys as i64,
+        total_memory_delta: after.total_memory as i64 - before.total_memory as i64,
+        namespace_changes: diff_namespaces(before, after),
+        new_big_keys: new_big_keys(before, after),
+        type_changes: diff_types(before, after),
+        command_stat_deltas: diff_command_stats(
+            from.analytics.as_ref().map(|a| a.command_stats.as_slice()).unwrap_or(&[]),
+            to.analytics.as_ref().map(|a| a.command_stats.as_slice()).unwrap_or(&[]),
+        ),
+        config_changes: diff_config(&from.config, &to.config),
+    }
+}
+
+// Flattens the namespace tree so growth is visible at every level (`user`,
+// `user:*`, `user:*:session`); older snapshots without a tree fall back to
+// the top-level list.
+fn namespace_sizes(analysis: &DatabaseAnalysis) -> HashMap<String, (u64, u64)> {
+    fn walk(nodes: &[NamespaceNode], out: &mut HashMap<String, (u64, u64)>) {
+        for node in nodes {
+            out.insert(node.path.clone(), (node.key_count, node.memory_bytes));
+            walk(&node.children, out);
+        }
+    }
+
+    let mut sizes = HashMap::new();
+    if analysis.namespace_tree.is_empty() {
+        for ns in &analysis.namespaces {
+            sizes.insert(ns.namespace.clone(), (ns.key_count, ns.memory_bytes));
+        }
+    } else {
+        walk(&analysis.namespace_tree, &mut sizes);
+    }
+    sizes
+}
+
+fn diff_namespaces(before: &DatabaseAnalysis, after: &DatabaseAnalysis) -> Vec<NamespaceChange> {
+    let before = namespace_sizes(before);
+    let after = namespace_sizes(after);
+    let names: HashSet<&String> = before.keys().chain(after.keys()).collect();
+
+    let mut changes: Vec<NamespaceChange> = names.into_iter()
+        .filter_map(|name| {
+            let (before_keys, before_memory) = before.get(name).copied().unwrap_or((0, 0));
+            let (after_keys, after_memory) = after.get(name).copied().unwrap_or((0, 0));
+            if before_keys == after_keys && before_memory == after_memory {
+                return None;
+            }
+            Some(NamespaceChange {
+                namespace: name.clone(),
+                before_keys,
+                after_keys,
+                before_memory,
+                after_memory,
+                key_delta: after_keys as i64 - before_keys as i64,
+                memory_delta: after_memory as i64 - before_memory as i64,
+            })
+        })
+        .collect();
+    changes.sort_by_key(|c| Reverse(c.memory_delta.unsigned_abs()));
+    changes.truncate(MAX_NAMESPACE_CHANGES);
+    changes
+}
+
+fn new_big_keys(before: &DatabaseAnalysis, after: &DatabaseAnalysis) -> Vec<KeyMemoryInfo> {
+    let known: HashSet<&str> = before.top_keys_by_memory.iter().map(|k| k.key.as_str()).collect();
+    after.top_keys_by_memory.iter()
+        .filter(|k| !known.contains(k.key.as_str()))
+        .cloned()
+        .collect()
+}
+
+fn diff_types(before: &DatabaseAnalysis, after: &DatabaseAnalysis) -> Vec<TypeMixChange> {
+    let lookup = |a: &DatabaseAnalysis, t: &str| a.type_distribution.iter()
+        .find(|d| d.key_type == t)
+        .map(|d| (d.count, d.percentage))
+        .unwrap_or((0, 0.0));
+    let types: BTreeSet<&str> = before.type_distribution.iter()
+        .chain(after.type_distribution.iter())
+        .map(|d| d.key_type.as_str())
+        .collect();
+
+    types.into_iter()
+        .map(|t| {
+            let (before_count, before_percentage) = lookup(before, t);
+            let (after_count, after_percentage) = lookup(after, t);
+            TypeMixChange { key_type: t.to_string(), before_count, after_count, before_percentage, after_percentage }
+        })
+        .filter(|c| c.before_count != c.after_count)
+        .collect()
+}
+
+fn diff_command_stats(before: &[CommandStat], after: &[CommandStat]) -> Vec<CommandStatDelta> {
+    let before: HashMap<&str, &CommandStat> = before.iter().map(|c| (c.command.as_str(), c)).collect();
+
+    let mut deltas: Vec<CommandStatDelta> = after.iter()
+        .map(|stat| {
+            let prev = before.get(stat.command.as_str());
+            // Counters go backwards after a restart or CONFIG RESETSTAT; treat it as a fresh start
+            let reset = prev.is_some_and(|p| p.calls > stat.calls);
+            let (prev_calls, prev_failed) = match prev {
+                Some(p) if !reset => (p.calls, p.failed_calls),
+                _ => (0, 0),
+            };
+            CommandStatDelta {
+                command: stat.command.clone(),
+                calls_delta: stat.calls as i64 - prev_calls as i64,
+                failed_calls_delta: stat.failed_calls as i64 - prev_failed as i64,
+                usec_per_call_before: prev.map(|p| p.usec_per_call).unwrap_or(0.0),
+                usec_per_call_after: stat.usec_per_call,
+            }
+        })
+        .filter(|d| d.calls_delta != 0 || d.failed_calls_delta != 0)
+        .collect();
+    deltas.sort_by_key(|d| Reverse(d.calls_delta));
+    deltas
+}
+
+fn diff_config(before: &HashMap<String, String>, after: &HashMap<String, String>) -> Vec<ConfigChange> {
+    let params: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    params.into_iter()
+        .filter(|p| before.get(*p) != after.get(*p))
+        .map(|p| ConfigChange {
+            parameter: p.clone(),
+            before: before.get(p).cloned(),
+            after: after.get(p).cloned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(id: &str, keys: u64, config: &[(&str, &str)]) -> AnalysisSnapshot {
+        let mut acc = crate::analysis::AnalysisAccumulator::default();
+        for i in 0..keys {
+            acc.record(&format!("user:{}:session", i), "hash", 100, -1);
+        }
+        AnalysisSnapshot {
+            id: id.to_string(),
+            server_id: "s1".to_string(),
+            label: None,
+            created_at: 0,
+            analysis: Some(acc.finish()),
+            analytics: None,
+            config: config.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn reports_namespace_growth_and_config_changes() {
+        let from = snapshot("a", 2, &[("maxmemory", "0"), ("appendonly", "no")]);
+        let to = snapshot("b", 5, &[("maxmemory", "1gb"), ("appendonly", "no")]);
+        let diff = diff_snapshots(&from, &to);
+
+        assert_eq!(diff.total_keys_delta, 3);
+        let session = diff.namespace_changes.iter().find(|c| c.namespace == "user:*:session").unwrap();
+        assert_eq!(session.key_delta, 3);
+        assert_eq!(session.memory_delta, 300);
+        assert_eq!(diff.config_changes.len(), 1);
+        assert_eq!(diff.config_changes[0].after.as_deref(), Some("1gb"));
+        assert_eq!(diff.type_changes[0].after_count, 5);
+    }
+
+    #[test]
+    fn stores_config_without_secrets() {
+        let dir = std::env::temp_dir().join(format!("snapshots-{}", uuid::Uuid::new_v4()));
+        let store = SnapshotStore::new(dir.clone());
+        store.save(&snapshot("a", 1, &[("requirepass", "hunter2"), ("maxmemory", "0")])).unwrap();
+
+        let raw = std::fs::read_to_string(dir.join("s1").join("a.json")).unwrap();
+        assert!(!raw.contains("hunter2"));
+        let loaded = store.load("s1", "a").unwrap();
+        assert_ne!(loaded.config["requirepass"], "hunter2");
+        assert_eq!(loaded.config["maxmemory"], "0");
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
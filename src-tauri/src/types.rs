@@ -11,6 +11,14 @@ pub struct RedisServer {
     pub password: Option<String>,
     pub db: Option<u8>,
     pub tls: Option<bool>,
+    /// Refuse every write from the app (editors, bulk jobs, write commands).
+    pub read_only: Option<bool>,
+    /// Commands `execute_command` refuses outright, e.g. `FLUSHALL` or `CONFIG SET`.
+    pub denied_commands: Option<Vec<String>>,
+    /// Defaults to 5s.
+    pub connect_timeout_ms: Option<u64>,
+    /// How long any single command may take; defaults to 10s.
+    pub response_timeout_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +68,18 @@ pub struct KeyspaceDbInfo {
     pub avg_ttl: u64,
 }
 
+/// One entry of the db picker: every configured database, including empty ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseSummary {
+    pub index: u8,
+    pub keys: u64,
+    pub expires: u64,
+    pub avg_ttl: u64,
+    /// The db the server profile connects to.
+    pub is_default: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RedisInfo {
     pub server: ServerInfo,
@@ -110,6 +130,42 @@ pub struct SlowLogEntry {
     pub client_name: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowLogClientCount {
+    /// Client host, with its name when it set one.
+    pub client: String,
+    pub count: u64,
+}
+
+/// Collected slow log entries sharing a normalized command shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowLogShape {
+    /// e.g. `SET ? EX ?`.
+    pub shape: String,
+    pub count: u64,
+    pub total_duration_us: u64,
+    pub p50_duration_us: u64,
+    pub p99_duration_us: u64,
+    pub max_duration_us: u64,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub slowest_example: String,
+    pub top_clients: Vec<SlowLogClientCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlowLogAggregate {
+    pub server_id: String,
+    /// Unix seconds, like `SlowLogEntry.timestamp`.
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub total_entries: u64,
+    pub shapes: Vec<SlowLogShape>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MemoryStats {
@@ -204,15 +260,59 @@ pub struct ErrorStat {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct BigKey {
     pub key: String,
     pub key_type: String,
+    /// Bytes for strings, element count for collections.
     pub size: u64,
     pub encoding: String,
+    pub memory_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BigKeyScanOptions {
+    pub pattern: Option<String>,
+    /// Keys kept per type; defaults to 10.
+    pub top_n: Option<usize>,
+    pub batch_size: Option<u32>,
+    pub throttle_ms: Option<u64>,
+    /// Also fetch MEMORY USAGE and rank by it, like `--memkeys`.
+    pub with_memory: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BigKeyTypeSummary {
+    pub key_type: String,
+    pub key_count: u64,
+    pub total_size: u64,
+    pub total_memory: u64,
+    pub biggest: Vec<BigKey>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BigKeyFound {
+    pub job_id: String,
+    pub server_id: String,
+    pub big_key: BigKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BigKeyScanReport {
+    pub job_id: String,
+    pub server_id: String,
+    pub status: String,
+    pub scanned_keys: u64,
+    pub total_keys: u64,
+    pub elapsed_ms: u64,
+    pub ranked_by_memory: bool,
+    pub types: Vec<BigKeyTypeSummary>,
+    pub error: Option<String>,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatencyHistoryEntry {
@@ -220,6 +320,49 @@ pub struct LatencyHistoryEntry {
     pub latency_ms: u64,
 }
 
+/// One `LATENCY LATEST` row, e.g. `command`, `fork` or `expire-cycle`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyEvent {
+    pub event: String,
+    /// Unix seconds of the latest spike.
+    pub timestamp: u64,
+    pub latest_ms: u64,
+    pub max_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyOverview {
+    /// `None` when CONFIG GET is unavailable.
+    pub threshold_ms: Option<u64>,
+    pub monitoring_enabled: bool,
+    /// Threshold to offer enabling the monitor with, when it's off.
+    pub suggested_threshold_ms: Option<u64>,
+    pub events: Vec<LatencyEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyBucket {
+    /// Upper bound of the bucket in microseconds.
+    pub le_usec: u64,
+    /// Calls at or below `le_usec`.
+    pub cumulative_count: u64,
+}
+
+/// A command's `LATENCY HISTOGRAM` (Redis 7+).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandLatencyHistogram {
+    pub command: String,
+    pub calls: u64,
+    pub buckets: Vec<LatencyBucket>,
+    /// Bucket bounds, so accurate to a power of two.
+    pub p50_usec: Option<u64>,
+    pub p99_usec: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AdvancedAnalytics {
@@ -321,17 +464,146 @@ pub struct CommandResult {
     pub result: String,
     pub execution_time_ms: u64,
     pub error: Option<String>,
+    /// The command hit the response timeout; it may still complete on the server.
+    pub timed_out: bool,
+    /// Set when the command was held back until the user confirms it.
+    pub confirmation: Option<CommandConfirmation>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct BulkDeleteResult {
-    pub deleted_count: u64,
+pub struct CommandConfirmation {
+    pub token: String,
+    pub command: String,
+    pub reasons: Vec<String>,
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkKeyFilter {
+    pub pattern: String,
+    pub key_type: Option<String>,
+    /// "persistent" (no TTL) or "expiring" (has a TTL).
+    pub ttl_state: Option<String>,
+    pub min_ttl_seconds: Option<i64>,
+    pub max_ttl_seconds: Option<i64>,
+    /// Only keys not accessed for at least this long (OBJECT IDLETIME).
+    pub min_idle_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkJobOptions {
+    pub ops_per_second: Option<u32>,
+    /// Stop after this many matching keys.
+    pub max_keys: Option<u64>,
+    pub batch_size: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkTtlOperation {
+    /// "set", "random", "persist" or "extend".
+    pub mode: String,
+    pub seconds: Option<i64>,
+    /// Bounds for "random"; spreading expiries avoids a stampede.
+    pub min_seconds: Option<i64>,
+    pub max_seconds: Option<i64>,
+    /// Added to the current TTL for "extend"; keys without a TTL are skipped.
+    pub delta_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkRenameSpec {
+    /// Glob such as `legacy:*`, or a regex when `regex` is set.
+    pub source: String,
+    pub regex: Option<bool>,
+    /// `$1`/`${name}` capture references; in glob mode each `*` is filled
+    /// with the matching wildcard from `source`.
+    pub replacement: String,
+    /// "skip" (default), "overwrite" (RENAME) or "fail" (RENAMENX, stops the job).
+    pub on_conflict: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamePlanItem {
+    pub from: String,
+    pub to: String,
+    pub target_exists: bool,
+    pub cross_slot: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkRenamePreview {
+    pub scanned_count: u64,
+    pub matched_count: u64,
+    pub conflict_count: u64,
+    /// Renames that would move a key to another hash slot (cluster only).
+    pub cross_slot_count: u64,
+    /// New names that still match the source and would be renamed again.
+    pub rematch_count: u64,
+    pub cluster_enabled: bool,
+    pub samples: Vec<RenamePlanItem>,
+    pub capped: bool,
+    /// The scan stopped at the preview limit; counts cover only the keys seen.
+    pub partial: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkPreview {
+    pub scanned_count: u64,
+    pub matched_count: u64,
+    pub sample_keys: Vec<String>,
+    /// The max-keys cap was reached before the scan finished.
+    pub capped: bool,
+    /// The scan stopped at the preview limit; counts cover only the keys seen.
+    pub partial: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkJobProgress {
+    pub job_id: String,
+    pub server_id: String,
+    pub operation: String,
+    pub status: String,
+    pub scanned_count: u64,
+    pub processed_count: u64,
+    /// Matched but left unchanged, e.g. already gone or a rename conflict.
+    pub skipped_count: u64,
     pub failed_count: u64,
-    pub execution_time_ms: u64,
+    pub elapsed_ms: u64,
+    pub capped: bool,
     pub errors: Vec<String>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncrementalDeleteOptions {
+    /// Members removed per round trip; defaults to 1000.
+    pub chunk_size: Option<u32>,
+    /// Pause between chunks; defaults to 10ms.
+    pub pause_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncrementalDeleteProgress {
+    pub job_id: String,
+    pub server_id: String,
+    pub key: String,
+    pub key_type: String,
+    pub removed: u64,
+    pub remaining: u64,
+    pub status: String,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DatabaseAnalysis {
@@ -342,6 +614,8 @@ pub struct DatabaseAnalysis {
     pub expiry_analysis: ExpiryAnalysis,
     pub top_keys_by_memory: Vec<KeyMemoryInfo>,
     pub namespaces: Vec<NamespaceInfo>,
+    #[serde(default)]
+    pub namespace_tree: Vec<NamespaceNode>,
     pub recommendations: Vec<String>,
 }
 
@@ -390,6 +664,335 @@ pub struct NamespaceInfo {
     pub memory_bytes: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceNode {
+    /// This level's segment, `*` when IDs were collapsed.
+    pub segment: String,
+    /// Full prefix pattern from the root, e.g. `user:*:session`.
+    pub path: String,
+    pub key_count: u64,
+    pub memory_bytes: u64,
+    pub children: Vec<NamespaceNode>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceOptions {
+    /// Each character is treated as a separator; defaults to `:`.
+    pub delimiters: Option<String>,
+    pub max_depth: Option<u32>,
+    /// Fold numeric, UUID and hex segments into `*` (default on).
+    pub collapse_ids: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisOptions {
+    /// Stop after this many keys; `None` walks the whole keyspace.
+    pub sample_size: Option<u64>,
+    pub pattern: Option<String>,
+    pub batch_size: Option<u32>,
+    pub throttle_ms: Option<u64>,
+    pub namespace_options: Option<NamespaceOptions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisProgress {
+    pub job_id: String,
+    pub server_id: String,
+    pub status: String,
+    pub scanned_keys: u64,
+    pub total_keys: u64,
+    pub elapsed_ms: u64,
+    pub partial: Option<DatabaseAnalysis>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisReport {
+    pub job_id: String,
+    pub server_id: String,
+    pub status: String,
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub scanned_keys: u64,
+    pub total_keys: u64,
+    pub complete: bool,
+    pub analysis: DatabaseAnalysis,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalysisSnapshot {
+    pub id: String,
+    pub server_id: String,
+    pub label: Option<String>,
+    pub created_at: u64,
+    pub analysis: Option<DatabaseAnalysis>,
+    pub analytics: Option<AdvancedAnalytics>,
+    /// `CONFIG GET *` at the time of the snapshot; empty if CONFIG is disabled.
+    pub config: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotSummary {
+    pub id: String,
+    pub server_id: String,
+    pub label: Option<String>,
+    pub created_at: u64,
+    pub total_keys: Option<u64>,
+    pub total_memory: Option<u64>,
+    pub has_analytics: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceChange {
+    pub namespace: String,
+    pub before_keys: u64,
+    pub after_keys: u64,
+    pub before_memory: u64,
+    pub after_memory: u64,
+    pub key_delta: i64,
+    pub memory_delta: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeMixChange {
+    pub key_type: String,
+    pub before_count: u64,
+    pub after_count: u64,
+    pub before_percentage: f64,
+    pub after_percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandStatDelta {
+    pub command: String,
+    pub calls_delta: i64,
+    pub failed_calls_delta: i64,
+    pub usec_per_call_before: f64,
+    pub usec_per_call_after: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    pub parameter: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// One `CONFIG GET` parameter with what the app knows about it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigParameter {
+    pub name: String,
+    /// Masked for secrets such as `requirepass`.
+    pub value: String,
+    pub default_value: Option<String>,
+    pub description: Option<String>,
+    /// `bool`, `integer`, `memory`, `enum` or `string`.
+    pub value_type: String,
+    pub allowed_values: Vec<String>,
+    /// `None` when the default isn't known (module or newer parameters).
+    pub is_default: Option<bool>,
+    /// False when the parameter can only be set in redis.conf.
+    pub mutable: bool,
+    pub sensitive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigUpdate {
+    pub parameter: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigApplyResult {
+    /// Values before and after, as read back from the server.
+    pub changes: Vec<ConfigChange>,
+    pub rewritten: bool,
+    /// Why `CONFIG REWRITE` failed, e.g. the server runs without a config file.
+    pub rewrite_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDifference {
+    pub parameter: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub left_is_default: Option<bool>,
+    pub right_is_default: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigComparison {
+    pub left_server_id: String,
+    pub right_server_id: String,
+    pub differences: Vec<ConfigDifference>,
+    pub matching: u64,
+    /// Parameters left out because they are expected to differ per instance.
+    pub ignored: Vec<String>,
+}
+
+/// A user-defined lint condition: `field` from CONFIG GET or INFO compared
+/// with `value`. Numbers and sizes (`1gb`) compare numerically.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintCondition {
+    /// `config` or `info`.
+    pub source: String,
+    pub field: String,
+    /// `eq`, `ne`, `lt`, `lte`, `gt`, `gte`, `contains` or `missing`.
+    pub operator: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+/// A user-defined lint rule; it fires when all its conditions hold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintRule {
+    pub id: String,
+    pub title: String,
+    pub severity: String,
+    pub rationale: String,
+    pub recommendation: Option<String>,
+    pub enabled: bool,
+    pub conditions: Vec<LintCondition>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintSettings {
+    #[serde(default)]
+    pub custom_rules: Vec<LintRule>,
+    /// Ids of built-in or custom rules to skip.
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintRuleInfo {
+    pub id: String,
+    pub title: String,
+    pub severity: String,
+    pub rationale: String,
+    pub builtin: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintFinding {
+    pub rule_id: String,
+    pub title: String,
+    /// `critical`, `warning` or `info`.
+    pub severity: String,
+    pub rationale: String,
+    /// What was observed on this server.
+    pub detail: String,
+    pub parameter: Option<String>,
+    pub recommendation: Option<String>,
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintReport {
+    pub server_id: String,
+    pub findings: Vec<LintFinding>,
+    pub rules_evaluated: u64,
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    pub from: SnapshotSummary,
+    pub to: SnapshotSummary,
+    pub total_keys_delta: i64,
+    pub total_memory_delta: i64,
+    pub namespace_changes: Vec<NamespaceChange>,
+    pub new_big_keys: Vec<KeyMemoryInfo>,
+    pub type_changes: Vec<TypeMixChange>,
+    pub command_stat_deltas: Vec<CommandStatDelta>,
+    pub config_changes: Vec<ConfigChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoKeyState {
+    pub key: String,
+    pub existed: bool,
+    /// Absolute expiry of the captured version, if it had a TTL.
+    pub expire_at_ms: Option<i64>,
+    pub size_bytes: u64,
+    /// False when DUMP was unavailable or the value was too large to keep.
+    pub restorable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoEntry {
+    pub id: String,
+    pub server_id: String,
+    /// `None` for the server's own db.
+    #[serde(default)]
+    pub db: Option<u8>,
+    pub operation: String,
+    pub created_at: u64,
+    pub keys: Vec<UndoKeyState>,
+    pub restored_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub server_id: String,
+    pub db: u8,
+    /// Command with values redacted, e.g. `SET user:1 ***`.
+    pub command: String,
+    pub success: bool,
+    pub result: String,
+    pub duration_ms: u64,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQuery {
+    pub server_id: Option<String>,
+    pub from_ms: Option<u64>,
+    pub to_ms: Option<u64>,
+    pub command_contains: Option<String>,
+    pub failures_only: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerification {
+    pub valid: bool,
+    pub checked_entries: u64,
+    pub first_invalid_seq: Option<u64>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClientAnalysis {
@@ -467,3 +1070,155 @@ pub struct AlertTrigger {
     pub threshold: f64,
     pub message: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AofFileInfo {
+    pub name: String,
+    pub file_type: String,
+    pub size: u64,
+    pub format: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AofCommandCount {
+    pub command: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AofTimelineBucket {
+    pub timestamp: Option<u64>,
+    pub start_offset: u64,
+    pub total_commands: u64,
+    pub commands: Vec<AofCommandCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AofInspection {
+    pub files: Vec<AofFileInfo>,
+    pub total_commands: u64,
+    pub total_bytes: u64,
+    pub base_keys: u64,
+    pub has_timestamps: bool,
+    pub first_timestamp: Option<u64>,
+    pub last_timestamp: Option<u64>,
+    pub truncated: bool,
+    pub command_counts: Vec<AofCommandCount>,
+    pub timeline: Vec<AofTimelineBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AofCommandRecord {
+    pub offset: u64,
+    pub timestamp: Option<u64>,
+    pub db: u64,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AofStateSnapshot {
+    pub offset: u64,
+    pub timestamp: Option<u64>,
+    pub commands_applied: u64,
+    pub total_keys: u64,
+    pub keys: Vec<KeyValue>,
+    pub unsupported_commands: Vec<AofCommandCount>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConnectionState {
+    Connecting,
+    Ready,
+    Degraded,
+    Lost,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionHealth {
+    pub server_id: String,
+    pub state: ConnectionState,
+    /// Round trip of the last successful PING.
+    pub latency_ms: Option<u64>,
+    pub last_error: Option<String>,
+    /// Reconnect attempts made since the connection was lost; 0 when healthy.
+    pub reconnect_attempt: u32,
+    pub changed_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AclSelector {
+    pub commands: String,
+    pub key_patterns: Vec<String>,
+    pub channel_patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AclUser {
+    pub name: String,
+    pub enabled: bool,
+    pub flags: Vec<String>,
+    /// SHA-256 hashes as reported by the server; plain passwords never leave it.
+    pub password_hashes: Vec<String>,
+    /// Command rules such as `-@all +get +set`.
+    pub commands: String,
+    /// Key patterns; read/write-only patterns keep their `%R~`/`%W~` prefix.
+    pub key_patterns: Vec<String>,
+    pub channel_patterns: Vec<String>,
+    pub selectors: Vec<AclSelector>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AclLogEntry {
+    pub count: u64,
+    /// `auth`, `command`, `key` or `channel`.
+    pub reason: String,
+    pub context: String,
+    pub object: String,
+    pub username: String,
+    pub age_seconds: f64,
+    pub client_info: String,
+    pub entry_id: Option<u64>,
+    pub timestamp_created: Option<u64>,
+    pub timestamp_last_updated: Option<u64>,
+}
+
+/// New occurrences of an `ACL LOG` entry seen by the ACL monitor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AclSecurityEvent {
+    pub server_id: String,
+    pub entry_id: Option<u64>,
+    pub reason: String,
+    pub context: String,
+    pub object: String,
+    pub username: String,
+    pub client_addr: Option<String>,
+    pub client_ip: Option<String>,
+    pub client_name: Option<String>,
+    /// Occurrences since the previous poll.
+    pub occurrences: u64,
+    /// The entry's count on the server.
+    pub total_count: u64,
+    pub observed_at: u64,
+    pub last_seen_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AclDryRunResult {
+    pub allowed: bool,
+    /// The server's explanation when the command would be denied.
+    pub reason: Option<String>,
+}
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { formatError } from "@/lib/utils";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Badge } from "@/components/ui/badge";
@@ -149,7 +150,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setCursor(result.cursor);
       setHasMore(result.hasMore);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -167,7 +168,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setKeyValue(value);
     } catch (err) {
       console.error("Failed to load key value:", err);
-      setError(String(err));
+      setError(formatError(err));
       setKeyValue(null);
     } finally {
       setLoadingValue(false);
@@ -186,7 +187,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
         setKeyValue(null);
       }
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -204,7 +205,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setEditMode(false);
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -215,7 +216,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setAddItemValue("");
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -225,7 +226,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       await invoke<boolean>("list_remove", { serverId, key: selectedKey, index });
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -236,7 +237,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setAddItemValue("");
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -246,7 +247,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       await invoke<boolean>("set_remove", { serverId, key: selectedKey, member });
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -259,7 +260,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setAddItemScore("");
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -269,7 +270,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       await invoke<boolean>("zset_remove", { serverId, key: selectedKey, member });
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -281,7 +282,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       setNewHashValue("");
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -291,7 +292,7 @@ export function KeyBrowser({ serverId }: KeyBrowserProps) {
       await invoke<boolean>("hash_delete", { serverId, key: selectedKey, field });
       loadKeyValue(selectedKey);
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
     }
   };
 
@@ -1,5 +1,6 @@
 import { useState, useRef, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { formatError } from "@/lib/utils";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { ScrollArea } from "@/components/ui/scroll-area";
@@ -15,7 +16,7 @@ import {
   History,
   Lightbulb,
 } from "lucide-react";
-import type { CommandResult, PerformanceWarning } from "@/types";
+import type { CommandConfirmation, CommandResult } from "@/types";
 
 interface RedisCLIProps {
   serverId: string;
@@ -27,7 +28,6 @@ interface HistoryEntry {
   timestamp: number;
 }
 
-const DANGEROUS_COMMANDS = ["FLUSHDB", "FLUSHALL", "DEBUG", "SHUTDOWN", "CONFIG SET", "SLAVEOF", "REPLICAOF"];
 const COMMAND_HINTS: Record<string, string> = {
   GET: "GET key - Get the value of a key",
   SET: "SET key value [EX seconds] - Set a key with optional expiry",
@@ -58,7 +58,7 @@ export function RedisCLI({ serverId }: RedisCLIProps) {
   const [command, setCommand] = useState("");
   const [history, setHistory] = useState<HistoryEntry[]>([]);
   const [historyIndex, setHistoryIndex] = useState(-1);
-  const [warning, setWarning] = useState<PerformanceWarning | null>(null);
+  const [pending, setPending] = useState<CommandConfirmation | null>(null);
   const [loading, setLoading] = useState(false);
   const [hint, setHint] = useState<string | null>(null);
   const inputRef = useRef<HTMLInputElement>(null);
@@ -79,40 +79,26 @@ export function RedisCLI({ serverId }: RedisCLIProps) {
     }
   }, [command]);
 
-  const checkDangerous = (cmd: string): boolean => {
-    const upper = cmd.toUpperCase();
-    return DANGEROUS_COMMANDS.some((d) => upper.startsWith(d));
-  };
-
-  const executeCommand = async () => {
-    if (!command.trim()) return;
-
-    if (checkDangerous(command)) {
-      try {
-        const warn = await invoke<PerformanceWarning>("check_operation_impact", {
-          serverId,
-          operation: command.split(" ")[0].toUpperCase(),
-          pattern: command,
-        });
-        if (warn.level === "critical") {
-          setWarning(warn);
-          return;
-        }
-      } catch {}
-    }
-
+  // The server decides which commands need confirming; a held command comes
+  // back with a token that unlocks that exact command line once
+  const runCommand = async (cmd: string, confirmationToken?: string) => {
     setLoading(true);
-    setWarning(null);
+    setPending(null);
 
     try {
       const result = await invoke<CommandResult>("execute_command", {
         serverId,
-        command: command.trim(),
+        command: cmd,
+        confirmationToken,
       });
 
+      if (result.confirmation) {
+        setPending(result.confirmation);
+        return;
+      }
       setHistory((prev) => [
         ...prev,
-        { command: command.trim(), result, timestamp: Date.now() },
+        { command: cmd, result, timestamp: Date.now() },
       ]);
       setCommand("");
       setHistoryIndex(-1);
@@ -120,12 +106,12 @@ export function RedisCLI({ serverId }: RedisCLIProps) {
       setHistory((prev) => [
         ...prev,
         {
-          command: command.trim(),
+          command: cmd,
           result: {
             success: false,
             result: "",
             executionTimeMs: 0,
-            error: String(err),
+            error: formatError(err),
           },
           timestamp: Date.now(),
         },
@@ -135,6 +121,16 @@ export function RedisCLI({ serverId }: RedisCLIProps) {
     }
   };
 
+  const executeCommand = async () => {
+    if (!command.trim()) return;
+    await runCommand(command.trim());
+  };
+
+  const confirmPending = async () => {
+    if (!pending) return;
+    await runCommand(pending.command, pending.token);
+  };
+
   const handleKeyDown = (e: React.KeyboardEvent) => {
     if (e.key === "Enter" && !e.shiftKey) {
       e.preventDefault();
@@ -161,33 +157,6 @@ export function RedisCLI({ serverId }: RedisCLIProps) {
     }
   };
 
-  const confirmDangerous = async () => {
-    setWarning(null);
-    setLoading(true);
-    try {
-      const result = await invoke<CommandResult>("execute_command", {
-        serverId,
-        command: command.trim(),
-      });
-      setHistory((prev) => [
-        ...prev,
-        { command: command.trim(), result, timestamp: Date.now() },
-      ]);
-      setCommand("");
-    } catch (err) {
-      setHistory((prev) => [
-        ...prev,
-        {
-          command: command.trim(),
-          result: { success: false, result: "", executionTimeMs: 0, error: String(err) },
-          timestamp: Date.now(),
-        },
-      ]);
-    } finally {
-      setLoading(false);
-    }
-  };
-
   return (
     <div className="h-full flex flex-col gap-4">
       <div className="flex items-center gap-2">
@@ -206,20 +175,27 @@ export function RedisCLI({ serverId }: RedisCLIProps) {
         </Button>
       </div>
 
-      {warning && (
+      {pending && (
         <div className="p-3 rounded-lg bg-red-500/10 border border-red-500/30 text-red-500">
           <div className="flex items-start gap-3">
             <AlertTriangle className="h-5 w-5 flex-shrink-0 mt-0.5" />
             <div className="flex-1">
-              <div className="font-medium">{warning.message}</div>
-              <div className="text-sm opacity-80">{warning.estimatedImpact}</div>
+              <div className="font-medium font-mono">{pending.command}</div>
+              <ul className="text-sm opacity-80 list-disc pl-4">
+                {pending.reasons.map((reason) => (
+                  <li key={reason}>{reason}</li>
+                ))}
+              </ul>
+              <div className="text-xs opacity-60 mt-1">
+                Confirmation expires in {pending.expiresInSecs}s
+              </div>
             </div>
           </div>
           <div className="flex gap-2 mt-3">
-            <Button size="sm" variant="destructive" onClick={confirmDangerous}>
+            <Button size="sm" variant="destructive" onClick={confirmPending} disabled={loading}>
               Execute Anyway
             </Button>
-            <Button size="sm" variant="outline" onClick={() => setWarning(null)}>
+            <Button size="sm" variant="outline" onClick={() => setPending(null)}>
               Cancel
             </Button>
           </div>
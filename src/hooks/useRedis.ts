@@ -1,5 +1,6 @@
 import { useState, useCallback, useRef, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { formatError } from "@/lib/utils";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type { RedisServer, RedisInfo, ClientInfo, MonitorEvent, IpStats, ConnectionState } from "@/types";
 
@@ -109,7 +110,7 @@ export function useRedis() {
       console.error("Connection failed:", error);
       setConnectionStates((prev) => ({
         ...prev,
-        [serverId]: { serverId, connected: false, monitoring: false, error: String(error) },
+        [serverId]: { serverId, connected: false, monitoring: false, error: formatError(error) },
       }));
     }
   }, [servers]);
@@ -255,18 +255,20 @@ export interface PerformanceWarning {
   estimatedImpact: string;
 }
 
+export interface CommandConfirmation {
+  token: string;
+  command: string;
+  reasons: string[];
+  expiresInSecs: number;
+}
+
 export interface CommandResult {
   success: boolean;
   result: string;
   executionTimeMs: number;
   error?: string;
-}
-
-export interface BulkDeleteResult {
-  deletedCount: number;
-  failedCount: number;
-  executionTimeMs: number;
-  errors: string[];
+  timedOut?: boolean;
+  confirmation?: CommandConfirmation;
 }
 
 export interface TypeDistribution {
@@ -359,3 +361,30 @@ export interface ClientAnalysis {
   suspiciousPatterns: SuspiciousPattern[];
   anomalies: ClientAnomaly[];
 }
+
+export type ErrorKind =
+  | "notConnected"
+  | "connectionLost"
+  | "timeout"
+  | "io"
+  | "auth"
+  | "noPermission"
+  | "redirect"
+  | "clusterDown"
+  | "crossSlot"
+  | "busy"
+  | "readOnly"
+  | "wrongType"
+  | "responseError"
+  | "client"
+  | "denied"
+  | "invalidInput"
+  | "other";
+
+export interface AppError {
+  kind: ErrorKind;
+  message: string;
+  code?: string;
+  serverId?: string;
+  retryable: boolean;
+}